@@ -0,0 +1,64 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::Result;
+use chrono::{Local, NaiveDate};
+use todoism_core::{Config, DailyLogService};
+use todoism_core::repository::DailyLogRepository;
+
+/// How far back startup and `backfill-logs` look for days without a meeting log.
+pub const DEFAULT_BACKFILL_DAYS: u32 = 7;
+
+pub fn missing_dates<L: DailyLogRepository>(service: &DailyLogService<L>, config: &Config, days: u32) -> Result<Vec<NaiveDate>> {
+    let today = Local::now().date_naive();
+    service.missing_dates(today, days, &config.plan.working_days)
+}
+
+/// Headless backfill: records `hours` of meetings for every missing day.
+pub fn backfill<L: DailyLogRepository>(service: &DailyLogService<L>, config: &Config, days: u32, hours: f64) -> Result<()> {
+    let missing = missing_dates(service, config, days)?;
+    if missing.is_empty() {
+        println!("No missing meeting logs in the last {} days.", days);
+        return Ok(());
+    }
+
+    for date in missing {
+        service.add_log(date, hours)?;
+        println!("Logged {:.1}h of meetings for {} ({})", hours, date, date.format("%a"));
+    }
+    Ok(())
+}
+
+/// Asks for the meeting hours of each recent day without a log before the TUI starts.
+/// An empty answer records 0. Skipped entirely when stdin is not a terminal.
+pub fn prompt_backfill<L: DailyLogRepository>(service: &DailyLogService<L>, config: &Config) -> Result<()> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return Ok(());
+    }
+
+    let missing = missing_dates(service, config, DEFAULT_BACKFILL_DAYS)?;
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    println!("{} recent working day(s) have no meeting log (Enter = 0h).", missing.len());
+    for date in missing {
+        loop {
+            print!("Meetings on {} ({}) [0]: ", date, date.format("%a"));
+            io::stdout().flush()?;
+
+            let mut answer = String::new();
+            stdin.lock().read_line(&mut answer)?;
+            let answer = answer.trim();
+            let hours = if answer.is_empty() { Ok(0.0) } else { answer.parse::<f64>() };
+            match hours {
+                Ok(hours) if hours >= 0.0 => {
+                    service.add_log(date, hours)?;
+                    break;
+                },
+                _ => println!("Please enter a number of hours."),
+            }
+        }
+    }
+    Ok(())
+}
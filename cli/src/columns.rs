@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use todoism_core::{format_duration, ListColumn, TaskDto};
+
+/// Display width of a column in the plain-text list; `Name` takes the rest of the line.
+pub fn width(column: ListColumn) -> usize {
+    match column {
+        ListColumn::Id => 8,
+        ListColumn::Score => 8,
+        ListColumn::Priority => 10,
+        ListColumn::Due => 12,
+        ListColumn::Project => 10,
+        ListColumn::Timer => 8,
+        ListColumn::Estimate => 6,
+        ListColumn::Age => 5,
+        ListColumn::Fit => 4,
+        ListColumn::Tags => 12,
+        ListColumn::Name => 20,
+    }
+}
+
+/// The text a task shows in `column`, shared by `list --view` and the TUI views.
+pub fn cell(task: &TaskDto, column: ListColumn, now: DateTime<Utc>) -> String {
+    match column {
+        ListColumn::Id => task.id.to_string().chars().take(8).collect(),
+        ListColumn::Score => format!("{:.1}", task.score),
+        ListColumn::Priority => format!("{:?}", task.priority),
+        ListColumn::Due => task.due.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".to_string()),
+        ListColumn::Project => task.project.clone().unwrap_or_else(|| "-".to_string()),
+        ListColumn::Timer => match (task.is_tracking, task.has_forgotten_timer()) {
+            (false, _) => "-".to_string(),
+            (true, false) => format_duration(task.open_session_time as f64 / 3600.0),
+            (true, true) => format!("{}!", format_duration(task.open_session_time as f64 / 3600.0)),
+        },
+        ListColumn::Estimate => task.estimate.clone().unwrap_or_else(|| "-".to_string()),
+        ListColumn::Age => format!("{}d", task.age_days(now)),
+        ListColumn::Fit => match task.fit {
+            Some(true) => "YES".to_string(),
+            Some(false) => "NO".to_string(),
+            None => "-".to_string(),
+        },
+        ListColumn::Name => name_with_tags(task),
+        ListColumn::Tags => task.tags.join(","),
+    }
+}
+
+/// The task name followed by its tags, e.g. `Buy milk +home +errand`.
+pub fn name_with_tags(task: &TaskDto) -> String {
+    let mut name = task.name.clone();
+    for tag in &task.tags {
+        name.push_str(" +");
+        name.push_str(tag);
+    }
+    name
+}
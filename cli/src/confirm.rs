@@ -0,0 +1,24 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{anyhow, Result};
+
+/// Asks "Are you sure? [y/N]" before a destructive command.
+/// `assume_yes` (the global `--yes`) skips the prompt; without it a non-interactive
+/// stdin is an error instead of a silent hang or an implicit yes.
+pub fn confirm(action: &str, assume_yes: bool) -> Result<bool> {
+    if assume_yes {
+        return Ok(true);
+    }
+
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return Err(anyhow!("Refusing to {} without confirmation: stdin is not a terminal (pass --yes)", action));
+    }
+
+    print!("About to {}. Are you sure? [y/N] ", action);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    stdin.lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
@@ -0,0 +1,66 @@
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{Local, Utc};
+use todoism_core::state::AppState;
+use todoism_core::{TaskRepository, TaskService};
+
+/// Checks for due reminders every `interval_secs` until the process is stopped.
+/// Fired reminders are recorded in the app state, so a restart doesn't repeat them.
+pub fn run<R: TaskRepository>(service: &TaskService<R>, interval_secs: u64, desktop: bool) -> Result<()> {
+    println!("Watching for reminders every {}s (Ctrl-C to stop)...", interval_secs);
+    loop {
+        check_once(service, desktop)?;
+        thread::sleep(Duration::from_secs(interval_secs.max(1)));
+    }
+}
+
+fn check_once<R: TaskRepository>(service: &TaskService<R>, desktop: bool) -> Result<()> {
+    let now = Utc::now();
+    let mut state = AppState::load(None)?;
+    let reminders = service.due_reminders(now, &state.fired_reminders)?;
+    if reminders.is_empty() {
+        return Ok(());
+    }
+
+    for reminder in reminders {
+        let due = reminder.task.due
+            .map(|d| d.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let message = format!("'{}' is due at {}", reminder.task.name, due);
+        println!("[{}] Reminder: {}", Local::now().format("%H:%M"), message);
+        if desktop {
+            if let Err(e) = notify_desktop(&message) {
+                println!("  (desktop notification failed: {})", e);
+            }
+        }
+        state.fired_reminders.insert(reminder.task.id, reminder.remind_at);
+    }
+
+    // Reload right before saving so a TUI started meanwhile keeps its `last_opened`
+    let mut latest = AppState::load(None)?;
+    latest.fired_reminders.extend(state.fired_reminders);
+    latest.save(None)
+}
+
+// Best effort: the platform's own notifier, output discarded.
+fn notify_desktop(message: &str) -> std::io::Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut c = Command::new("osascript");
+        let script = format!("display notification \"{}\" with title \"todoism\"", message.replace('"', "'"));
+        c.args(["-e", &script]);
+        c
+    } else {
+        let mut c = Command::new("notify-send");
+        c.args(["todoism", message]);
+        c
+    };
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|_| ())
+}
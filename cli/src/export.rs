@@ -0,0 +1,167 @@
+use std::io::{self, Write};
+
+use anyhow::Result;
+use todoism_core::{Task, TaskDto, TaskState};
+use todoism_core::service::dto::WeeklyHistory;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    /// The tasks as stored, pretty-printed
+    Json,
+    /// One row per task: id,name,status,priority,due,project,estimate,created_at
+    Csv,
+}
+
+const CSV_HEADER: &str = "id,name,status,priority,due,project,estimate,created_at";
+
+/// Writes `tasks` to `out` in `format`; deleted tasks are left out unless `include_deleted`.
+/// A reader that stops early (e.g. `| head`) just ends the export.
+pub fn export_tasks(tasks: Vec<Task>, format: ExportFormat, include_deleted: bool, out: impl Write) -> Result<()> {
+    match write_tasks(tasks, format, include_deleted, out) {
+        Err(e) if is_broken_pipe(&e) => Ok(()),
+        result => result,
+    }
+}
+
+fn is_broken_pipe(error: &anyhow::Error) -> bool {
+    let kind = error.downcast_ref::<io::Error>().map(io::Error::kind)
+        .or_else(|| error.downcast_ref::<serde_json::Error>().and_then(serde_json::Error::io_error_kind));
+    kind == Some(io::ErrorKind::BrokenPipe)
+}
+
+fn write_tasks(tasks: Vec<Task>, format: ExportFormat, include_deleted: bool, mut out: impl Write) -> Result<()> {
+    let tasks: Vec<Task> = tasks
+        .into_iter()
+        .filter(|t| include_deleted || !matches!(t.state, TaskState::Deleted { .. }))
+        .collect();
+
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, &tasks)?;
+            writeln!(out)?;
+        }
+        ExportFormat::Csv => {
+            writeln!(out, "{}", CSV_HEADER)?;
+            for task in tasks {
+                let dto = TaskDto::from_entity(task, 0.0);
+                let fields = [
+                    dto.id.to_string(),
+                    dto.name,
+                    dto.status,
+                    format!("{:?}", dto.priority),
+                    dto.due.map(|d| d.to_rfc3339()).unwrap_or_default(),
+                    dto.project.unwrap_or_default(),
+                    dto.estimate.unwrap_or_default(),
+                    dto.created_at.to_rfc3339(),
+                ];
+                let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                writeln!(out, "{}", row.join(","))?;
+            }
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+const HISTORY_CSV_HEADER: &str = "year,week,date,day_of_week,est_hours,act_hours,meeting_hours";
+
+/// Writes one row per day of `histories`, oldest first, and returns how many there were.
+/// Without any history only the header is written.
+pub fn export_history_csv(histories: &[WeeklyHistory], mut out: impl Write) -> Result<usize> {
+    let mut rows: Vec<(&str, String)> = histories.iter()
+        .flat_map(|week| week.days.iter().map(move |day| {
+            let fields = [
+                week.year.to_string(),
+                week.week.to_string(),
+                day.date.clone(),
+                day.day_of_week.clone(),
+                format!("{:.2}", day.stats.total_est_hours),
+                format!("{:.2}", day.stats.total_act_hours),
+                format!("{:.2}", day.stats.meeting_hours),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            (day.date.as_str(), row.join(","))
+        }))
+        .collect();
+    // YYYY-MM-DD sorts chronologically as text
+    rows.sort_by(|a, b| a.0.cmp(b.0));
+
+    writeln!(out, "{}", HISTORY_CSV_HEADER)?;
+    for (_, row) in &rows {
+        writeln!(out, "{}", row)?;
+    }
+    out.flush()?;
+    Ok(rows.len())
+}
+
+// RFC 4180: quote fields containing a separator, quote or line break, doubling inner quotes
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_export_quotes_and_skips_deleted() {
+        let mut quoted = Task::new("Call \"Bob\", then Alice".to_string(), None);
+        quoted.project = Some("Work".to_string());
+        let mut deleted = Task::new("Gone".to_string(), None);
+        deleted.delete();
+
+        let mut out = Vec::new();
+        export_tasks(vec![quoted.clone(), deleted.clone()], ExportFormat::Csv, false, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with(&format!("{},\"Call \"\"Bob\"\", then Alice\",Pending,Medium,,Work,,", quoted.id)));
+
+        let mut out = Vec::new();
+        export_tasks(vec![quoted, deleted], ExportFormat::Json, true, &mut out).unwrap();
+        let tasks: Vec<Task> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(tasks.len(), 2);
+    }
+
+    #[test]
+    fn test_history_csv_rows_per_day() {
+        use todoism_core::service::dto::{DailyHistory, HistoryStats};
+
+        let day = |date: &str, day_of_week: &str, est: f64, act: f64, mtg: f64| DailyHistory {
+            date: date.to_string(),
+            day_of_week: day_of_week.to_string(),
+            tasks: Vec::new(),
+            stats: HistoryStats { total_est_hours: est, total_act_hours: act, meeting_hours: mtg, in_progress_est_hours: 0.0 },
+        };
+        let week = |week: u32, days: Vec<DailyHistory>| WeeklyHistory {
+            year: 2025,
+            week,
+            days,
+            stats: HistoryStats { total_est_hours: 0.0, total_act_hours: 0.0, meeting_hours: 0.0, in_progress_est_hours: 0.0 },
+        };
+        let histories = vec![
+            week(3, vec![day("2025-01-14", "Tue", 2.0, 1.5, 0.0)]),
+            week(2, vec![day("2025-01-07", "Tue", 0.0, 0.0, 1.0), day("2025-01-06", "Mon", 4.0, 3.25, 0.5)]),
+        ];
+
+        let mut out = Vec::new();
+        assert_eq!(export_history_csv(&histories, &mut out).unwrap(), 3);
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines, [
+            HISTORY_CSV_HEADER,
+            "2025,2,2025-01-06,Mon,4.00,3.25,0.50",
+            "2025,2,2025-01-07,Tue,0.00,0.00,1.00",
+            "2025,3,2025-01-14,Tue,2.00,1.50,0.00",
+        ]);
+
+        let mut out = Vec::new();
+        assert_eq!(export_history_csv(&[], &mut out).unwrap(), 0);
+        assert_eq!(String::from_utf8(out).unwrap(), format!("{}\n", HISTORY_CSV_HEADER));
+    }
+}
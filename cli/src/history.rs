@@ -1,10 +1,24 @@
-use todoism_core::service::dto::{WeeklyHistory, DailyHistory};
 use todoism_core::usecase::history::HistoryUseCase;
 use todoism_core::repository::{TaskRepository, DailyLogRepository}; 
 use tabled::{Table, Tabled};
 use tabled::settings::{Style, Color, Modify};
-use tabled::settings::object::{Rows};
+use tabled::settings::object::{Cell, Rows};
+use todoism_core::{Priority, TaskDto};
+use todoism_core::service::dto::WeeklyHistory;
+use todoism_core::config::CompletionOrder;
+use todoism_core::model::estimate::HOURS_PER_DAY;
 use anyhow::Result;
+use std::cmp::Ordering;
+
+const PRIORITY_COLUMN: usize = 3;
+
+fn priority_color(priority: &Priority) -> Color {
+    match priority {
+        Priority::High => Color::FG_RED,
+        Priority::Medium => Color::FG_YELLOW,
+        Priority::Low => Color::FG_GREEN,
+    }
+}
 
 // Helper struct for Table Row
 #[derive(Tabled)]
@@ -15,18 +29,72 @@ struct HistoryRow {
     id: String,
     #[tabled(rename = "Description")]
     desc: String,
+    #[tabled(rename = "Pri")]
+    pri: String,
     #[tabled(rename = "Est (d)")]
     est: String,
     #[tabled(rename = "Act (d)")]
     act: String,
 }
 
-pub fn show_history<R: TaskRepository, L: DailyLogRepository>(history_usecase: &HistoryUseCase<R, L>) -> Result<()> {
-    let weekly_history = history_usecase.get_weekly_history()?;
+/// Orders a day's tasks by completion time; tasks still in progress go last.
+fn sort_by_completion(tasks: &mut [TaskDto], order: CompletionOrder) {
+    tasks.sort_by(|a, b| match (a.completed_at, b.completed_at) {
+        (Some(x), Some(y)) => match order {
+            CompletionOrder::Ascending => x.cmp(&y),
+            CompletionOrder::Descending => y.cmp(&x),
+        },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.id.cmp(&b.id),
+    });
+}
+
+/// Cuts the history down to the `limit` most recently completed tasks: days before the
+/// oldest one kept go, and so do weeks left without days. In-progress rows on the days
+/// that stay are kept. Returns how many completed tasks were left out.
+fn limit_history(weeks: &mut Vec<WeeklyHistory>, limit: usize) -> usize {
+    let mut completed: Vec<(Option<chrono::DateTime<chrono::Utc>>, uuid::Uuid, String)> = weeks.iter()
+        .flat_map(|week| week.days.iter())
+        .flat_map(|day| day.tasks.iter()
+            .filter(|t| t.status == "Completed")
+            .map(|t| (t.completed_at, t.id, day.date.clone())))
+        .collect();
+    if completed.len() <= limit {
+        return 0;
+    }
+    completed.sort_by_key(|(completed_at, _, _)| std::cmp::Reverse(*completed_at));
+    let hidden = completed.len() - limit;
+    completed.truncate(limit);
+
+    // YYYY-MM-DD compares chronologically as text; with nothing kept every day goes
+    let Some(cutoff) = completed.iter().map(|(_, _, date)| date.clone()).min() else {
+        weeks.clear();
+        return hidden;
+    };
+    for week in weeks.iter_mut() {
+        week.days.retain(|day| day.date >= cutoff);
+        for day in &mut week.days {
+            day.tasks.retain(|t| t.status != "Completed" || completed.iter().any(|(_, id, _)| *id == t.id));
+        }
+    }
+    weeks.retain(|week| !week.days.is_empty());
+    hidden
+}
+
+/// Prints the weekly history (or its JSON) and returns how many completed tasks `limit` left out.
+pub fn show_history<R: TaskRepository, L: DailyLogRepository>(history_usecase: &HistoryUseCase<R, L>, json: bool, limit: Option<usize>, order: CompletionOrder, hours_per_day: f64) -> Result<usize> {
+    let mut weekly_history = history_usecase.get_weekly_history()?;
+    let hidden = limit.map_or(0, |limit| limit_history(&mut weekly_history, limit));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&weekly_history)?);
+        return Ok(hidden);
+    }
 
     if weekly_history.is_empty() {
         println!("No completed tasks found in history.");
-        return Ok(());
+        return Ok(hidden);
     }
 
     for week_entry in weekly_history {
@@ -34,32 +102,32 @@ pub fn show_history<R: TaskRepository, L: DailyLogRepository>(history_usecase: &
         println!("\n\x1b[1;36mWeek {}, {}\x1b[0m (Est: {:.1}d, Act: {:.1}d, Mtg: {:.1}d)", 
                  week_entry.week, 
                  week_entry.year, 
-                 week_entry.stats.total_est_hours / 8.0, 
-                 week_entry.stats.total_act_hours / 8.0,
-                 week_entry.stats.meeting_hours / 8.0);
+                 week_entry.stats.total_est_hours / hours_per_day, 
+                 week_entry.stats.total_act_hours / hours_per_day,
+                 week_entry.stats.meeting_hours / hours_per_day);
 
         // Construct Table Rows
         let mut rows = Vec::new();
+        let mut priorities = Vec::new();
 
         for day_entry in week_entry.days {
             let day_header = format!("{} ({})\nE:{:.1}d A:{:.1}d M:{:.1}d",
                 day_entry.date,
                 day_entry.day_of_week,
-                day_entry.stats.total_est_hours / 8.0,
-                day_entry.stats.total_act_hours / 8.0,
-                day_entry.stats.meeting_hours / 8.0
+                day_entry.stats.total_est_hours / hours_per_day,
+                day_entry.stats.total_act_hours / hours_per_day,
+                day_entry.stats.meeting_hours / hours_per_day
             );
 
-            // Sort tasks by ID for stability in display
             let mut daily_tasks_sorted = day_entry.tasks;
-            daily_tasks_sorted.sort_by_key(|t| t.id);
+            sort_by_completion(&mut daily_tasks_sorted, order);
 
             for (i, task_dto) in daily_tasks_sorted.iter().enumerate() {
                 let id_short = task_dto.id.to_string()[..8].to_string();
 
                 let est_str = task_dto.estimate.clone().unwrap_or_else(|| "-".to_string());
 
-                let act_str = format!("{:.2}", (task_dto.accumulated_time as f64 / 3600.0) / 8.0);
+                let act_str = format!("{:.2}", (task_dto.accumulated_time as f64 / 3600.0) / HOURS_PER_DAY);
                 
                 // Visual distinction for status
                 let desc_display = if task_dto.status == "Pending" {
@@ -79,9 +147,11 @@ pub fn show_history<R: TaskRepository, L: DailyLogRepository>(history_usecase: &
                     date: date_col,
                     id: id_short,
                     desc: desc_display,
+                    pri: format!("{:?}", task_dto.priority),
                     est: est_str,
                     act: act_str,
                 });
+                priorities.push(task_dto.priority.clone());
             }
         }
 
@@ -90,8 +160,83 @@ pub fn show_history<R: TaskRepository, L: DailyLogRepository>(history_usecase: &
             .with(Style::modern())
             .with(Modify::new(Rows::first()).with(Color::FG_CYAN)); // Header color
 
+        // Row 0 is the header
+        for (i, priority) in priorities.iter().enumerate() {
+            table.with(Modify::new(Cell::new(i + 1, PRIORITY_COLUMN)).with(priority_color(priority)));
+        }
+
         println!("{}", table);
     }
     
-    Ok(())
+    Ok(hidden)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use todoism_core::Task;
+
+    fn completed_dto(name: &str, completed_at: chrono::DateTime<Utc>) -> TaskDto {
+        let mut dto = TaskDto::from_entity(Task::new(name.to_string(), None), 0.0);
+        dto.status = "Completed".to_string();
+        dto.completed_at = Some(completed_at);
+        dto
+    }
+
+    #[test]
+    fn test_day_tasks_sorted_by_completion_time() {
+        let now = Utc::now();
+        let later = completed_dto("Later", now);
+        let earlier = completed_dto("Earlier", now - Duration::hours(2));
+        let in_progress = TaskDto::from_entity(Task::new("Ongoing".to_string(), None), 0.0);
+
+        let mut tasks = vec![in_progress.clone(), later.clone(), earlier.clone()];
+        sort_by_completion(&mut tasks, CompletionOrder::Ascending);
+        let names: Vec<_> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Earlier", "Later", "Ongoing"]);
+
+        sort_by_completion(&mut tasks, CompletionOrder::Descending);
+        let names: Vec<_> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Later", "Earlier", "Ongoing"]);
+    }
+
+    #[test]
+    fn test_limit_keeps_most_recent_completions() {
+        use todoism_core::service::dto::{DailyHistory, HistoryStats};
+
+        let stats = || HistoryStats { total_est_hours: 0.0, total_act_hours: 0.0, meeting_hours: 0.0, in_progress_est_hours: 0.0 };
+        let day = |date: &str, tasks: Vec<TaskDto>| DailyHistory {
+            date: date.to_string(),
+            day_of_week: String::new(),
+            tasks,
+            stats: stats(),
+        };
+        let week = |week: u32, days: Vec<DailyHistory>| WeeklyHistory { year: 2025, week, days, stats: stats() };
+        let at = |date: &str| format!("{}T12:00:00Z", date).parse::<chrono::DateTime<Utc>>().unwrap();
+        let ongoing = TaskDto::from_entity(Task::new("Ongoing".to_string(), None), 0.0);
+        let history = vec![
+            week(3, vec![day("2025-01-14", vec![completed_dto("Newest", at("2025-01-14")), ongoing.clone()])]),
+            week(2, vec![
+                day("2025-01-06", vec![completed_dto("Oldest", at("2025-01-06"))]),
+                day("2025-01-07", vec![completed_dto("Second", at("2025-01-07")), ongoing]),
+            ]),
+        ];
+
+        let mut limited = history.clone();
+        assert_eq!(limit_history(&mut limited, 2), 1);
+        let shown: Vec<Vec<&str>> = limited.iter()
+            .map(|w| w.days.iter().flat_map(|d| d.tasks.iter().map(|t| t.name.as_str())).collect())
+            .collect();
+        assert_eq!(shown, [vec!["Newest", "Ongoing"], vec!["Second", "Ongoing"]]);
+
+        let mut limited = history.clone();
+        assert_eq!(limit_history(&mut limited, 3), 0);
+        assert_eq!(limited.len(), 2);
+        assert_eq!(limited[1].days.len(), 2);
+
+        let mut limited = history;
+        assert_eq!(limit_history(&mut limited, 0), 3);
+        assert!(limited.is_empty());
+    }
 }
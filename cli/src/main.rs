@@ -1,20 +1,41 @@
 mod tui;
 mod history;
 mod stats;
+mod today;
+mod confirm;
+mod backfill;
+mod terminal;
+mod daemon;
+mod columns;
+mod export;
+mod search;
 
 use clap::Parser;
-use todoism_core::service::task_service::{TaskService, SortStrategy};
+use todoism_core::service::task_service::{TaskService, SortStrategy, DueFilter, StatusFilter, MergeStrategy, MIN_ESTIMATE_SAMPLES, set_scoring_config, prerequisites_to_finish};
 use todoism_core::usecase::history::HistoryUseCase;
-use todoism_core::repository::{TaskRepository, DailyLogRepository, FileStatsRepository};
-use todoism_core::{greet, Task, FileTaskRepository, FileDailyLogRepository, parse_args, expand_key, parse_human_date, Priority, DailyLogService};
+use todoism_core::usecase::daily_plan::DailyPlanUseCase;
+use todoism_core::usecase::project_stats::ProjectStatsUseCase;
+use todoism_core::repository::FileStatsRepository;
+use todoism_core::{greet, Task, FileTaskRepository, MemoryTaskRepository, FileDailyLogRepository, parse_args, expand_key, metadata_key_names, METADATA_KEYS, parse_human_date, parse_duration, start_of_day, Priority, DailyLogService, Config, TaskDto, Estimate, format_duration, TaskState, TaskRepository, calculate_urgency};
 use todoism_core::service::archive_service::ArchiveService;
-use anyhow::{Result};
-use std::collections::HashMap;
+use todoism_core::paths;
+use todoism_core::{Backup, BackupService, ViewConfig, ListColumn, resolve_task_id, resolve_task_id_where};
+use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet};
+use std::io::IsTerminal;
 
 #[derive(Parser)]
 #[command(name = "todoism")]
 #[command(about = "A robust CLI task manager", long_about = None)]
 struct Cli {
+    /// Use a separate task set stored under ~/.todoism/profiles/<name> (env: TODOISM_PROFILE)
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Answer yes to confirmation prompts of destructive commands (for scripts)
+    #[arg(short = 'y', long, global = true)]
+    yes: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -28,17 +49,276 @@ enum Commands {
         /// Task details including name and metadata (key:value)
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
+        /// Start tracking the new task right away, stopping any running timer (must precede the name)
+        #[arg(long)]
+        start: bool,
+        /// Create the task even if `due` or `estimate` can't be parsed (must precede the name)
+        #[arg(long)]
+        force: bool,
+    },
+    /// Complete a task by its ID prefix (usage: done a1b2c3d4 actual:0.5)
+    Done {
+        /// ID prefix as shown by `list`
+        id: String,
+        /// Optional `actual:<effort>` recorded as the task's manual actual effort
+        #[arg(trailing_var_arg = true)]
+        args: Vec<String>,
+    },
+    /// Delete a task by its ID prefix; it stays restorable from the trash (asks for confirmation)
+    Delete {
+        /// ID prefix as shown by `list`
+        id: String,
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        force: bool,
+    },
+    /// Change a task's fields (usage: modify a1b2c3d4 "New name" due:fri pri:H)
+    Modify {
+        /// ID prefix as shown by `list`
+        id: String,
+        /// New name and/or metadata (key:value); anything not given stays as it is, `key:` clears it
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// Start tracking time on a task, stopping any other running timer
+    Start {
+        /// ID prefix as shown by `list`
+        id: String,
+    },
+    /// Stop tracking time on a task and show how long the session ran
+    Stop {
+        /// ID prefix as shown by `list`
+        id: String,
     },
     /// List all tasks
-    List,
+    List {
+        /// Only show tasks that fit today's remaining capacity
+        #[arg(long)]
+        fit: bool,
+        /// Only show tasks due: today, week, overdue, or by a date (e.g. eow, +3d, 2025-01-31)
+        #[arg(long)]
+        due: Option<String>,
+        /// Only show pending tasks past their due date (same as --due overdue)
+        #[arg(long, conflicts_with = "due")]
+        overdue: bool,
+        /// Order by urgency (default), priority or due date; overrides the view's sort
+        #[arg(long, value_enum)]
+        sort: Option<ListSort>,
+        /// Only include tasks in this project
+        #[arg(long)]
+        project: Option<String>,
+        /// Only include tasks with this status: pending, completed or deleted
+        #[arg(long)]
+        status: Option<String>,
+        /// Show only the first N tasks after sorting and filtering
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Keep the list on screen and redraw it whenever the task data changes
+        #[arg(long)]
+        watch: bool,
+        /// Use a saved view's columns, sort and filters (built-in: triage, planning);
+        /// --fit, --due and --project still override its filters
+        #[arg(long)]
+        view: Option<String>,
+    },
+    /// Print only the highest-scoring pending task that is not blocked (e.g. for a shell prompt);
+    /// when every task is blocked, names the prerequisites to finish first
+    Next {
+        #[arg(long, value_enum, default_value_t = NextFormat::Plain)]
+        format: NextFormat,
+    },
+    /// Find tasks whose name or description contains the query, ignoring case
+    Search {
+        query: String,
+        /// Treat the query as a regular expression
+        #[arg(long)]
+        regex: bool,
+    },
     /// Open the Terminal User Interface
-    Tui,
+    Tui {
+        /// Start in the board view (columns by status or project)
+        #[arg(long)]
+        board: bool,
+    },
     /// View completed task history (Timesheet)
-    History,
+    History {
+        /// Emit the weekly history as JSON instead of tables
+        #[arg(long)]
+        json: bool,
+        /// Show only the N most recently completed tasks, still grouped by week and day
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// View the tasks worked on today with capacity totals
+    Today {
+        /// Emit today's summary as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
     /// View statistics (TUI)
-    Stats,
+    Stats {
+        /// Print estimated and actual hours per project instead of opening the TUI
+        #[arg(long)]
+        by_project: bool,
+        /// Write the weekly history to this CSV file, one row per day, instead of opening the TUI
+        #[arg(long, value_name = "PATH", conflicts_with = "by_project")]
+        export_csv: Option<std::path::PathBuf>,
+    },
+    /// Report tracked hours per task within a date range
+    Report {
+        /// Start of the window (inclusive, from the start of that day)
+        #[arg(long)]
+        from: String,
+        /// End of the window (inclusive, until the end of that day)
+        #[arg(long)]
+        to: String,
+        /// Only include tasks in this project
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// List pending tasks that have gone untouched for a long time
+    Stale {
+        /// Minimum age in days, also the window checked for recent time logs
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+        /// Complete every listed task (asks for confirmation)
+        #[arg(long, conflicts_with = "delete")]
+        complete: bool,
+        /// Delete every listed task (asks for confirmation)
+        #[arg(long)]
+        delete: bool,
+    },
+    /// Move every overdue pending task to a new due date
+    Reschedule {
+        /// New due date (e.g. tomorrow, mon, +1w, 2025-01-31)
+        #[arg(long)]
+        to: String,
+        /// Only reschedule tasks in this project
+        #[arg(long)]
+        project: Option<String>,
+        /// Show what would be moved without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List deleted tasks, most recently deleted first
+    Trash {
+        /// Restore the deleted task with this ID (a unique prefix is enough)
+        #[arg(long)]
+        restore: Option<String>,
+    },
+    /// Move tasks completed or deleted more than DAYS ago into the monthly archive files (asks for confirmation)
+    Archive {
+        /// Minimum days since completion or deletion
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+        /// Show what would be archived without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Bring an archived task back into the task list as a pending task
+    Restore {
+        /// ID of the archived task (a unique prefix is enough)
+        id: String,
+    },
+    /// Suggest an estimate from the actuals of similar completed tasks
+    SuggestEstimate {
+        /// Task ID (a unique prefix is enough)
+        id: String,
+        /// Compare against completed tasks whose name contains this text instead of the same project
+        #[arg(long)]
+        pattern: Option<String>,
+        /// Store the suggestion on the task if it has no estimate yet
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Run in the foreground and remind about tasks whose `remind:` time before due has come
+    /// (opt-in, independent of the TUI; stop with Ctrl-C)
+    Daemon {
+        /// Seconds between checks
+        #[arg(long, default_value_t = 60)]
+        interval: u64,
+        /// Also show a desktop notification (notify-send / osascript) besides printing
+        #[arg(long)]
+        desktop: bool,
+    },
+    /// Print every pending task's urgency score as TSV (id, name, score) for scoring regression checks
+    #[command(hide = true)]
+    ScoreDump {
+        /// Score as of this time instead of now (RFC 3339, e.g. 2025-01-15T12:00:00Z, or any due date format)
+        #[arg(long)]
+        now: Option<String>,
+    },
+    /// Write every task to stdout as JSON or CSV (e.g. `todoism export --format csv > tasks.csv`)
+    Export {
+        #[arg(long, value_enum, default_value_t = export::ExportFormat::Json)]
+        format: export::ExportFormat,
+        /// Also export deleted tasks
+        #[arg(long)]
+        include_deleted: bool,
+    },
+    /// Add the tasks from a JSON file written by `export`, keeping their IDs
+    Import {
+        /// JSON file with a list of tasks
+        path: std::path::PathBuf,
+        /// For tasks whose ID already exists: skip (keep ours) or overwrite (take the file's)
+        #[arg(long, value_enum, default_value_t = MergeStrategy::Skip)]
+        strategy: MergeStrategy,
+    },
+    /// Bundle tasks, daily logs, stats and archive into one backup file
+    Backup {
+        /// Backup file to write (or read with --restore)
+        file: std::path::PathBuf,
+        /// Replace the current data with the contents of the backup file
+        #[arg(long)]
+        restore: bool,
+    },
+    /// Print the metadata keys accepted by `add` with their formats
+    Schema {
+        /// Emit the keys as JSON for other tools
+        #[arg(long)]
+        json: bool,
+    },
+    /// List existing profiles
+    Profiles,
+    /// Record meeting hours for recent working days that have no log
+    BackfillLogs {
+        /// How many days back to look (at most a year)
+        #[arg(long, default_value_t = backfill::DEFAULT_BACKFILL_DAYS, value_parser = clap::value_parser!(u32).range(..=366))]
+        days: u32,
+        /// Meeting hours to record for each missing day
+        #[arg(long, default_value_t = 0.0)]
+        hours: f64,
+    },
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum NextFormat {
+    /// Name, short ID and score
+    Plain,
+    /// `[a1b2c3d4] name` on one line, for status bars
+    Line,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum ListSort {
+    Urgency,
+    Priority,
+    Due,
 }
 
+impl From<ListSort> for SortStrategy {
+    fn from(sort: ListSort) -> Self {
+        match sort {
+            ListSort::Urgency => SortStrategy::Urgency,
+            ListSort::Priority => SortStrategy::Priority,
+            ListSort::Due => SortStrategy::DueDate,
+        }
+    }
+}
+
+/// How often `list --watch` checks the data files for changes.
+const WATCH_POLL_MS: u64 = 500;
+
 fn parse_priority_str(pri_str: &str) -> Priority {
     match pri_str.to_lowercase().as_str() {
         "h" | "high" => Priority::High,
@@ -49,27 +329,42 @@ fn parse_priority_str(pri_str: &str) -> Priority {
 }
 
 fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    // Must happen before any repository resolves its data directory
+    if let Some(profile) = &cli.profile {
+        paths::select_profile(profile)?;
+    }
+
     let repo = FileTaskRepository::new(None)?;
     let log_repo = FileDailyLogRepository::new(None)?;
     let stats_repo = FileStatsRepository::new(None)?;
     
     // Archive Logic
     let archive_service = ArchiveService::new(repo.clone(), stats_repo.clone());
-    let _ = archive_service.archive_old_tasks(7); // Archive tasks older than 7 days
+    // Archive tasks older than 7 days; `archive` does its own (and `--dry-run` must not write)
+    if !matches!(cli.command, Some(Commands::Archive { .. })) {
+        let _ = archive_service.archive_old_tasks(7);
+    }
 
-    let service = TaskService::new(repo.clone()); 
+    let config = Config::load(None)?;
+    set_scoring_config(config.scoring.clone());
+    let mut service = TaskService::new(repo.clone());
+    if config.stats.live_update {
+        service = service.with_live_stats(stats_repo.clone());
+    }
+    let daily_log_path = log_repo.file_path().to_path_buf();
     let daily_log_service = DailyLogService::new(log_repo);
 
-    // Define known keys for expansion
-    let known_keys = vec!["due", "project", "priority", "description", "estimate"];
+    let known_keys = metadata_key_names();
 
-    let cli = Cli::parse();
+    let assume_yes = cli.yes;
 
     match cli.command {
         Some(Commands::Greet) => {
             println!("{}", greet());
         },
-        Some(Commands::Add { args }) => {
+        Some(Commands::Add { args, start, force }) => {
             if args.is_empty() {
                 println!("Error: Task name is required.");
                 return Ok(());
@@ -95,17 +390,48 @@ fn main() -> Result<()> {
                 }
             }
 
-            let due = if let Some(d) = normalized_metadata.get("due") {
-                match parse_human_date(d) {
-                    Ok(dt) => Some(dt),
-                    Err(e) => {
-                        println!("Warning: Invalid due date '{}': {}", d, e);
-                        None
+            let due = match normalized_metadata.get("due").map(|d| (d, parse_human_date(d))) {
+                Some((_, Ok(dt))) => Some(dt),
+                Some((d, Err(e))) if force => {
+                    println!("Warning: Invalid due date '{}': {}", d, e);
+                    None
+                },
+                Some((d, Err(e))) => {
+                    println!("Error: Invalid due date '{}': {}. Task not added (use --force to add it anyway).", d, e);
+                    return Ok(());
+                },
+                None => None,
+            };
+            if let Some(est) = normalized_metadata.get("estimate") {
+                if Estimate::parse(est).is_none() {
+                    if !force {
+                        println!("Error: Invalid estimate '{}'. Task not added (use --force to add it anyway).", est);
+                        return Ok(());
                     }
+                    println!("Warning: Invalid estimate '{}'", est);
                 }
-            } else {
-                None
-            };
+            }
+            if let Some(remind) = normalized_metadata.get("remind") {
+                if let Err(e) = parse_duration(remind) {
+                    if !force {
+                        println!("Error: Invalid reminder '{}': {}. Task not added (use --force to add it anyway).", remind, e);
+                        return Ok(());
+                    }
+                    println!("Warning: Invalid reminder '{}': {}", remind, e);
+                } else if due.is_none() {
+                    println!("Warning: reminder '{}' has no effect without a due date", remind);
+                }
+            }
+
+            if let Some(recur) = normalized_metadata.get("recur") {
+                if let Err(e) = parse_duration(recur) {
+                    if !force {
+                        println!("Error: Invalid recurrence '{}': {}. Task not added (use --force to add it anyway).", recur, e);
+                        return Ok(());
+                    }
+                    println!("Warning: Invalid recurrence '{}': {}", recur, e);
+                }
+            }
 
             let project = normalized_metadata.get("project").cloned();
             let priority = normalized_metadata.get("priority")
@@ -113,12 +439,32 @@ fn main() -> Result<()> {
                 .unwrap_or_default();
             let description = normalized_metadata.get("description").cloned();
             let estimate = normalized_metadata.get("estimate").cloned();
+            let url = normalized_metadata.get("url").cloned();
+            let remind_before = normalized_metadata.get("remind").cloned();
 
             let mut new_task = Task::new(parsed.name, due);
             new_task.project = project;
             new_task.priority = priority;
             new_task.description = description;
             new_task.estimate = estimate;
+            new_task.url = url;
+            new_task.remind_before = remind_before;
+            new_task.recurrence = normalized_metadata.get("recur").cloned();
+            for tag in &parsed.tags {
+                new_task.add_tag(tag);
+            }
+            for dep in parsed.deps.iter().filter(|d| !d.is_empty()) {
+                match service.resolve_dependency(&new_task.id, dep) {
+                    Ok(dep_id) => {
+                        new_task.add_dependency(dep_id);
+                    },
+                    Err(e) if force => println!("Warning: Invalid dependency '{}': {}", dep, e),
+                    Err(e) => {
+                        println!("Error: Invalid dependency '{}': {}. Task not added (use --force to add it anyway).", dep, e);
+                        return Ok(());
+                    },
+                }
+            }
 
             let created_task = service.create_task(new_task)?;
             println!("Task added: {} (ID: {})", created_task.name, created_task.id);
@@ -128,51 +474,796 @@ fn main() -> Result<()> {
             if let Some(p) = created_task.project {
                 println!("  Project: {}", p);
             }
+            if let Some(u) = created_task.url {
+                println!("  URL: {}", u);
+            }
+            if !created_task.tags.is_empty() {
+                println!("  Tags: {}", created_task.tags.join(", "));
+            }
+            if let Some(r) = created_task.recurrence {
+                println!("  Recurs: every {}", r);
+            }
+            if !created_task.depends_on.is_empty() {
+                println!("  Depends on: {}", task_names(&service, &created_task.depends_on).join(", "));
+            }
             println!("  Priority: {:?}", created_task.priority);
+
+            if start {
+                // Only one timer may run at a time.
+                for stopped in service.stop_all_tracking()? {
+                    println!("Stopped tracking: {}", stopped.name);
+                }
+                service.start_task(&created_task.id)?;
+                println!("Started tracking: {}", created_task.name);
+            }
         },
-        Some(Commands::List) => {
-            let strategy = SortStrategy::Urgency;
-            let tasks = service.get_sorted_tasks(strategy)?;
-            
+        Some(Commands::List { fit, due, overdue, sort, project, status, limit, watch, view }) => {
+            let mut view = match view {
+                Some(name) => config.view(&name)?,
+                None => ViewConfig::default(),
+            };
+            view.fit |= fit;
+            if let Some(due) = due {
+                DueFilter::parse(&due)?;
+                view.due = Some(due);
+            }
+            if overdue {
+                view.due = Some("overdue".to_string());
+            }
+            if let Some(sort) = sort {
+                view.sort = sort.into();
+            }
+            if project.is_some() {
+                view.project = project;
+            }
+            if let Some(status) = status {
+                StatusFilter::parse(&status)?;
+                view.status = Some(status);
+            }
+            let show = || print_list(&service, &daily_log_service, &config, &view, limit);
+
+            if !watch {
+                return show();
+            }
+
+            // Poll the data files' mtimes; only re-render when one of them actually changed.
+            // Ctrl-C ends the process normally; the terminal was never put into raw mode.
+            let watched = [service.repo.file_path().to_path_buf(), daily_log_path];
+            let mtimes = || -> Vec<Option<std::time::SystemTime>> {
+                watched.iter().map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok()).collect()
+            };
+            let mut last_seen = None;
+            loop {
+                let current = mtimes();
+                if last_seen.as_ref() != Some(&current) {
+                    crossterm::execute!(
+                        std::io::stdout(),
+                        crossterm::terminal::Clear(crossterm::terminal::ClearType::All),
+                        crossterm::cursor::MoveTo(0, 0)
+                    )?;
+                    show()?;
+                    println!("\nWatching for changes (Ctrl-C to exit)...");
+                    last_seen = Some(current);
+                }
+                std::thread::sleep(std::time::Duration::from_millis(WATCH_POLL_MS));
+            }
+        },
+        Some(Commands::History { json, limit }) => {
+             let history_usecase = HistoryUseCase::new(&service.repo, &daily_log_service, &stats_repo)
+                 .with_in_progress_estimates(config.stats.in_progress_estimates);
+             let hidden = history::show_history(&history_usecase, json, limit, config.history.completion_order, config.plan.daily_capacity_hours)?;
+             if !json {
+                 print_more(hidden);
+             }
+        },
+        Some(Commands::Today { json }) => {
+            let tasks = service.get_sorted_tasks(SortStrategy::Urgency)?;
+            let usecase = DailyPlanUseCase::new(&daily_log_service, config.plan.clone());
+            let summary = usecase.get_today_summary(tasks)?;
+            today::show_today(&summary, json, config.display.time_display)?;
+        },
+        Some(Commands::Report { from, to, project }) => {
+            let start = start_of_day(parse_human_date(&from)?);
+            let end = parse_human_date(&to)?;
+            if end <= start {
+                println!("Error: --to must be after --from.");
+                return Ok(());
+            }
+
+            let entries = service.time_report(start, end, project.as_deref())?;
+            if entries.is_empty() {
+                println!("No tracked time between {} and {}.", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"));
+                return Ok(());
+            }
+
+            println!("{:<8} {:<10} {:>8} {:<20}", "ID", "Project", "Hours", "Description");
+            println!("{:-<8} {:-<10} {:->8} {:-<20}", "", "", "", "");
+
+            let mut total_seconds = 0;
+            for entry in &entries {
+                let id_str = entry.task.id.to_string();
+                let project = entry.task.project.clone().unwrap_or_else(|| "-".to_string());
+                total_seconds += entry.seconds;
+                println!("{:<8} {:<10} {:>8.2} {}", &id_str[..8], project, entry.seconds as f64 / 3600.0, entry.task.name);
+            }
+            println!("{:-<8} {:-<10} {:->8} {:-<20}", "", "", "", "");
+            println!("{:<8} {:<10} {:>8.2}", "Total", "", total_seconds as f64 / 3600.0);
+        },
+        Some(Commands::Stale { days, complete, delete }) => {
+            let entries = service.stale_tasks(days)?;
+            if entries.is_empty() {
+                println!("No stale tasks (older than {} days without activity).", days);
+                return Ok(());
+            }
+
+            let now = chrono::Utc::now();
+            println!("{:<8} {:>6} {:<14} {:<20}", "ID", "Age", "Last activity", "Description");
+            println!("{:-<8} {:->6} {:-<14} {:-<20}", "", "", "", "");
+            for entry in &entries {
+                let id_str = entry.task.id.to_string();
+                let age = entry.task.age_days(now);
+                let last = entry.last_activity
+                    .map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string())
+                    .unwrap_or_else(|| "never".to_string());
+                println!("{:<8} {:>5}d {:<14} {}", &id_str[..8], age, last, entry.task.name);
+            }
+
+            if complete || delete {
+                let verb = if complete { "complete" } else { "delete" };
+                if !confirm::confirm(&format!("{} {} stale task(s)", verb, entries.len()), assume_yes)? {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+                for entry in &entries {
+                    if complete {
+                        service.complete_task(&entry.task.id)?;
+                    } else {
+                        service.delete_task(&entry.task.id)?;
+                    }
+                }
+                println!("{} {} task(s).", if complete { "Completed" } else { "Deleted" }, entries.len());
+            }
+        },
+        Some(Commands::Reschedule { to, project, dry_run }) => {
+            let new_due = parse_human_date(&to)?;
+            let overdue = service.overdue_tasks(project.as_deref())?;
+            if overdue.is_empty() {
+                println!("No overdue tasks.");
+                return Ok(());
+            }
+
+            let new_due_str = new_due.with_timezone(&chrono::Local).format("%Y-%m-%d");
+            if dry_run {
+                print_task_table(&overdue);
+                println!();
+                println!("Would move {} task(s) to {}.", overdue.len(), new_due_str);
+            } else {
+                let count = service.reschedule_overdue(new_due, project.as_deref())?;
+                println!("Moved {} overdue task(s) to {}.", count, new_due_str);
+            }
+        },
+        Some(Commands::Archive { days, dry_run }) => {
+            let tasks = archive_service.archivable_tasks(days as i64)?;
             if tasks.is_empty() {
-                println!("No tasks found.");
+                println!("Nothing to archive (no task completed or deleted more than {} days ago).", days);
+                return Ok(());
+            }
+            if !dry_run {
+                if !confirm::confirm(&format!("archive {} task(s)", tasks.len()), assume_yes)? {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+                let count = archive_service.archive_old_tasks(days as i64)?;
+                println!("Archived {} task(s) (restore one with `restore <id>`).", count);
+                return Ok(());
+            }
+
+            println!("{:<8} {:<10} {:<12} {:<20}", "ID", "State", "Since", "Description");
+            println!("{:-<8} {:-<10} {:-<12} {:-<20}", "", "", "", "");
+            for task in &tasks {
+                let (state, since) = match task.state {
+                    TaskState::Completed { completed_at, .. } => ("Completed", completed_at),
+                    TaskState::Deleted { deleted_at } => ("Deleted", deleted_at),
+                    TaskState::Pending { .. } => continue,
+                };
+                let since = since.with_timezone(&chrono::Local).format("%Y-%m-%d");
+                println!("{:<8} {:<10} {:<12} {}", &task.id.to_string()[..8], state, since, task.name);
+            }
+            println!();
+            println!("Would archive {} task(s).", tasks.len());
+        },
+        Some(Commands::Restore { id: prefix }) => {
+            let archived = MemoryTaskRepository::new();
+            for task in archive_service.archived_tasks()? {
+                archived.create(task)?;
+            }
+            let id = resolve_task_id(&archived, &prefix)?;
+            let task = archive_service.restore_task(&id)?;
+            // Left completed or deleted, the next run would archive it again
+            match task.state {
+                TaskState::Completed { .. } => service.toggle_status(&id)?,
+                TaskState::Deleted { .. } => { service.restore_task(&id)?; },
+                TaskState::Pending { .. } => {},
+            }
+            println!("Restored from the archive: {} (ID: {})", task.name, task.id);
+        },
+        Some(Commands::Trash { restore }) => {
+            let entries = service.deleted_tasks()?;
+
+            if let Some(prefix) = restore {
+                let id = resolve_task_id_where(&service.repo, &prefix, |t| t.deleted_at().is_some())?;
+                let restored = service.restore_task(&id)?;
+                println!("Restored: {} (ID: {})", restored.name, restored.id);
+                return Ok(());
+            }
+
+            if entries.is_empty() {
+                println!("Trash is empty.");
+                return Ok(());
+            }
+            println!("{:<8} {:<16} {:<20}", "ID", "Deleted", "Description");
+            println!("{:-<8} {:-<16} {:-<20}", "", "", "");
+            for entry in &entries {
+                let id_str = entry.task.id.to_string();
+                let deleted = entry.deleted_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+                println!("{:<8} {:<16} {}", &id_str[..8], deleted, entry.task.name);
+            }
+        },
+        Some(Commands::Modify { id, args }) => {
+            let id = resolve_task_id_where(&service.repo, &id, |t| t.deleted_at().is_none())?;
+            let mut task = service.get_task(&id)?;
+            let parsed = parse_args(&args);
+            let mut changes = Vec::new();
+
+            if !parsed.name.is_empty() && parsed.name != task.name {
+                task.name = parsed.name;
+                changes.push(format!("name: {}", task.name));
+            }
+            for (key, value) in parsed.metadata {
+                let full_key = match expand_key(&key, &known_keys) {
+                    Ok(full_key) => full_key,
+                    Err(e) => {
+                        println!("Warning: {}", e);
+                        continue;
+                    }
+                };
+                if value.is_empty() {
+                    if task.clear_field(&full_key) {
+                        changes.push(format!("{}: (cleared)", full_key));
+                    }
+                    continue;
+                }
+                match full_key.as_str() {
+                    "due" => match parse_human_date(&value) {
+                        Ok(d) => task.due = Some(d),
+                        Err(e) => {
+                            println!("Error: Invalid due date '{}': {}. Task not modified.", value, e);
+                            return Ok(());
+                        }
+                    },
+                    "estimate" if Estimate::parse(&value).is_none() => {
+                        println!("Error: Invalid estimate '{}'. Task not modified.", value);
+                        return Ok(());
+                    },
+                    "remind" if parse_duration(&value).is_err() => {
+                        println!("Error: Invalid reminder '{}'. Task not modified.", value);
+                        return Ok(());
+                    },
+                    "recur" if parse_duration(&value).is_err() => {
+                        println!("Error: Invalid recurrence '{}'. Task not modified.", value);
+                        return Ok(());
+                    },
+                    "project" => task.project = Some(value.clone()),
+                    "priority" => task.priority = parse_priority_str(&value),
+                    "description" => task.description = Some(value.clone()),
+                    "estimate" => task.estimate = Some(value.clone()),
+                    "url" => task.url = Some(value.clone()),
+                    "remind" => task.remind_before = Some(value.clone()),
+                    "recur" => task.recurrence = Some(value.clone()),
+                    _ => continue,
+                }
+                let shown = match full_key.as_str() {
+                    "due" => task.due.map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default(),
+                    "priority" => format!("{:?}", task.priority),
+                    _ => value,
+                };
+                changes.push(format!("{}: {}", full_key, shown));
+            }
+
+            for tag in &parsed.tags {
+                if tag.is_empty() {
+                    if !task.tags.is_empty() {
+                        task.clear_field("tag");
+                        changes.push("tag: (cleared)".to_string());
+                    }
+                } else if task.add_tag(tag) {
+                    changes.push(format!("tag: +{}", tag));
+                }
+            }
+            for dep in &parsed.deps {
+                if dep.is_empty() {
+                    if !task.depends_on.is_empty() {
+                        task.clear_field("dep");
+                        changes.push("dep: (cleared)".to_string());
+                    }
+                    continue;
+                }
+                let dep_id = match service.resolve_dependency(&task.id, dep) {
+                    Ok(dep_id) => dep_id,
+                    Err(e) => {
+                        println!("Error: Invalid dependency '{}': {}. Task not modified.", dep, e);
+                        return Ok(());
+                    },
+                };
+                if task.add_dependency(dep_id) {
+                    changes.push(format!("dep: +{}", service.get_task(&dep_id)?.name));
+                }
+            }
+
+            if changes.is_empty() {
+                println!("Nothing to change for: {}", task.name);
+                return Ok(());
+            }
+            service.update_task(&task)?;
+            println!("Task modified: {} (ID: {})", task.name, task.id);
+            for change in changes {
+                println!("  {}", change);
+            }
+        },
+        Some(Commands::Start { id }) => {
+            let id = resolve_task_id_where(&service.repo, &id, |t| matches!(t.state, TaskState::Pending { .. }))?;
+            let task = service.get_task(&id)?;
+            if task.is_tracking() {
+                println!("Already tracking: {}", task.name);
+                return Ok(());
+            }
+            // Only one timer may run at a time.
+            for stopped in service.stop_all_tracking()? {
+                println!("Stopped tracking: {}", stopped.name);
+            }
+            service.start_task(&id)?;
+            println!("Started tracking: {}", task.name);
+        },
+        Some(Commands::Stop { id }) => {
+            let id = resolve_task_id_where(&service.repo, &id, |t| matches!(t.state, TaskState::Pending { .. }))?;
+            let task = service.get_task(&id)?;
+            if !task.is_tracking() {
+                println!("Not tracking: {}", task.name);
+                return Ok(());
+            }
+            service.stop_task(&id)?;
+
+            let task = service.get_task(&id)?;
+            let elapsed = task.time_logs().last()
+                .and_then(|log| log.end.map(|end| end - log.start))
+                .unwrap_or_default();
+            println!(
+                "Stopped tracking: {} ({})",
+                task.name,
+                format_duration(elapsed.num_seconds() as f64 / 3600.0)
+            );
+        },
+        Some(Commands::Done { id, args }) => {
+            let id = resolve_task_id_where(&service.repo, &id, |t| matches!(t.state, TaskState::Pending { .. }))?;
+            let task = service.get_task(&id)?;
+
+            let parsed = parse_args(&args);
+            if !parsed.name.is_empty() {
+                println!("Error: unexpected argument '{}' (only actual:<effort> is accepted).", parsed.name);
+                return Ok(());
+            }
+            let mut actual = None;
+            for (key, value) in parsed.metadata {
+                if key != "actual" {
+                    println!("Error: unknown key '{}' (only actual:<effort> is accepted).", key);
+                    return Ok(());
+                }
+                if Estimate::parse(&value).is_none() {
+                    println!("Error: invalid actual effort '{}'.", value);
+                    return Ok(());
+                }
+                actual = Some(value);
+            }
+
+            match actual {
+                Some(effort) => {
+                    let log_effort = !config.stats.effort_on_completion_day;
+                    service.complete_task_with_effort(&task.id, effort, log_effort)?;
+                },
+                None => service.complete_task(&task.id)?,
+            }
+            println!("Completed task: {}", task.name);
+            if let Some(next) = task.next_occurrence(chrono::Utc::now()) {
+                if let Some(due) = next.due {
+                    println!("Next occurrence due {}", due.with_timezone(&chrono::Local).format("%Y-%m-%d"));
+                }
+            }
+        },
+        Some(Commands::Delete { id, force }) => {
+            let id = resolve_task_id_where(&service.repo, &id, |t| t.deleted_at().is_none())?;
+            let task = service.get_task(&id)?;
+
+            if !confirm::confirm(&format!("delete '{}'", task.name), assume_yes || force)? {
+                println!("Aborted.");
+                return Ok(());
+            }
+            service.delete_task(&task.id)?;
+            println!("Deleted task: {} (restore it with `trash --restore <id>`)", task.name);
+        },
+        Some(Commands::SuggestEstimate { id, pattern, apply }) => {
+            let id = resolve_task_id(&service.repo, &id)?;
+            let task = service.get_task(&id)?;
+
+            let Some(suggestion) = service.suggest_estimate(&task.id, pattern.as_deref())? else {
+                println!(
+                    "No history: fewer than {} similar completed tasks to base an estimate on.",
+                    MIN_ESTIMATE_SAMPLES
+                );
+                return Ok(());
+            };
+            println!(
+                "Suggested estimate for '{}': {} (average of {} completed task(s): {})",
+                task.name, suggestion.estimate, suggestion.samples,
+                config.display.time_display.format(suggestion.average_hours)
+            );
+
+            if apply {
+                if let Some(current) = &task.estimate {
+                    println!("Not applied: the task already has an estimate ({}).", current);
+                } else {
+                    let mut task = task;
+                    task.estimate = Some(suggestion.estimate);
+                    service.update_task(&task)?;
+                    println!("Estimate saved.");
+                }
+            }
+        },
+        Some(Commands::ScoreDump { now }) => {
+            let now = now
+                .map(|n| n.parse::<chrono::DateTime<chrono::Utc>>().or_else(|_| parse_human_date(&n)))
+                .transpose()?;
+            let mut tasks: Vec<Task> = service.repo.list()?.into_iter()
+                .filter(|t| matches!(t.state, TaskState::Pending { .. }))
+                .collect();
+            tasks.sort_by_key(|t| t.id);
+            for task in tasks {
+                let name = task.name.replace(['\t', '\n'], " ");
+                println!("{}\t{}\t{}", task.id, name, calculate_urgency(&task, now));
+            }
+        },
+        Some(Commands::Daemon { interval, desktop }) => {
+            daemon::run(&service, interval, desktop)?;
+        },
+        Some(Commands::Export { format, include_deleted }) => {
+            export::export_tasks(service.repo.list()?, format, include_deleted, std::io::stdout().lock())?;
+        },
+        Some(Commands::Import { path, strategy }) => {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| anyhow!("Cannot read {}: {}", path.display(), e))?;
+            let tasks: Vec<Task> = serde_json::from_str(&content)
+                .map_err(|e| anyhow!("{} is not a task export: {}", path.display(), e))?;
+            if strategy == MergeStrategy::Overwrite {
+                let existing: HashSet<uuid::Uuid> = service.repo.list()?.iter().map(|t| t.id).collect();
+                let replaced: HashSet<uuid::Uuid> = tasks.iter().map(|t| t.id).filter(|id| existing.contains(id)).collect();
+                if !replaced.is_empty()
+                    && !confirm::confirm(&format!("overwrite {} existing task(s) with the ones in {}", replaced.len(), path.display()), assume_yes)?
+                {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+            }
+            let report = service.import_tasks(tasks, strategy)?;
+            println!("Imported from {}: {} added, {} updated, {} skipped", path.display(), report.added, report.updated, report.skipped);
+        },
+        Some(Commands::Backup { file, restore }) => {
+            let backup_service = BackupService::new(None)?;
+            if restore {
+                let content = std::fs::read_to_string(&file)?;
+                let backup = Backup::from_json(&content)?;
+                let taken = backup.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+                let action = format!(
+                    "replace all current data with the backup from {} ({} task(s), {} file(s))",
+                    taken, backup.task_count(), backup.files.len()
+                );
+                if !confirm::confirm(&action, assume_yes)? {
+                    println!("Aborted.");
+                    return Ok(());
+                }
+                backup_service.restore(&backup)?;
+                println!("Restored {} file(s) from {}", backup.files.len(), file.display());
+            } else {
+                let backup = backup_service.create()?;
+                std::fs::write(&file, backup.to_json()?)?;
+                println!("Backed up {} file(s) to {}", backup.files.len(), file.display());
+            }
+        },
+        Some(Commands::Schema { json }) => {
+            if json {
+                println!("{}", serde_json::to_string_pretty(METADATA_KEYS)?);
             } else {
-                println!("{:<8} {:<8} {:<10} {:<12} {:<10} {:<20}", "ID", "Score", "Priority", "Due", "Project", "Description");
-                println!("{:-<8} {:-<8} {:-<10} {:-<12} {:-<10} {:-<20}", "", "", "", "", "", "");
-                
-                for task in tasks {
-                    let id_str = task.id.to_string();
-                    let short_id = if id_str.len() > 8 { &id_str[..8] } else { &id_str }; 
-                    let pri = format!("{:?}", task.priority);
-                    let due = task.due.map(|d: chrono::DateTime<chrono::Utc>| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".to_string());
-                    let project = task.project.clone().unwrap_or_else(|| "-".to_string());
-                    // TaskDto now has the score directly
-                    let score = task.score;
-                    
-                    println!("{:<8} {:<8.1} {:<10} {:<12} {:<10} {}", 
-                        short_id,
-                        score, 
-                        pri, 
-                        due, 
-                        project, 
-                        task.name
-                    );
-                }
-            }
-        },
-        Some(Commands::History) => {
-             let history_usecase = HistoryUseCase::new(&service.repo, &daily_log_service, &stats_repo); 
-             history::show_history(&history_usecase)?;
-        },
-        Some(Commands::Stats) => {
-            stats::run(&service.repo, &daily_log_service, &stats_repo)?;
-        },
-        Some(Commands::Tui) => {
-            tui::run()?;
+                println!("{:<12} {:<28} Format", "Key", "Example");
+                println!("{:-<12} {:-<28} {:-<20}", "", "", "");
+                for key in METADATA_KEYS {
+                    println!("{:<12} {:<28} {}", key.key, key.example, key.format);
+                }
+            }
+        },
+        Some(Commands::Profiles) => {
+            let active = paths::active_profile();
+            for name in paths::list_profiles()? {
+                let marker = if name == active { "*" } else { " " };
+                println!("{} {}", marker, name);
+            }
+        },
+        Some(Commands::BackfillLogs { days, hours }) => {
+            if hours < 0.0 {
+                println!("Error: --hours must not be negative.");
+                return Ok(());
+            }
+            backfill::backfill(&daily_log_service, &config, days, hours)?;
+        },
+        Some(Commands::Stats { by_project: true, .. }) => {
+            let project_stats = ProjectStatsUseCase::new(&service.repo).project_stats()?;
+            stats::print_project_stats(&project_stats, config.display.time_display);
+        },
+        Some(Commands::Stats { export_csv: Some(path), .. }) => {
+            let histories = HistoryUseCase::new(&service.repo, &daily_log_service, &stats_repo)
+                .with_in_progress_estimates(config.stats.in_progress_estimates)
+                .get_weekly_history()?;
+            let file = std::fs::File::create(&path)
+                .map_err(|e| anyhow!("Cannot write {}: {}", path.display(), e))?;
+            let days = export::export_history_csv(&histories, std::io::BufWriter::new(file))?;
+            println!("Wrote {} day(s) to {}.", days, path.display());
+        },
+        Some(Commands::Stats { .. }) => {
+            stats::run(&service.repo, &daily_log_service, &stats_repo, &config.plan, config.display.time_display, config.stats.in_progress_estimates)?;
+        },
+        Some(Commands::Next { format }) => {
+            let tasks = service.get_sorted_tasks(SortStrategy::Urgency)?;
+            let pending: Vec<&TaskDto> = tasks.iter().filter(|t| t.status == "Pending").collect();
+            match pending.iter().find(|t| !t.blocked) {
+                Some(task) => {
+                    let short_id = &task.id.to_string()[..8];
+                    match format {
+                        NextFormat::Plain => println!("{} ({}, score {:.1})", task.name, short_id, task.score),
+                        NextFormat::Line => println!("[{}] {}", short_id, task.name),
+                    }
+                }
+                None if pending.is_empty() => println!("Nothing to do"),
+                // Every pending task waits on another one
+                None => match prerequisites_to_finish(&tasks, &pending).as_slice() {
+                    [] => println!("Everything is blocked by a dependency loop; remove one with `modify <id> dep:`"),
+                    first => {
+                        let names: Vec<String> = first.iter().map(|t| format!("'{}' ({})", t.name, &t.id.to_string()[..8])).collect();
+                        println!("Everything is blocked; finish first: {}", names.join(", "));
+                    }
+                },
+            }
+        },
+        Some(Commands::Search { query, regex }) => {
+            let matcher = search::Matcher::new(&query, regex)?;
+            let mut tasks = service.get_sorted_tasks(SortStrategy::Urgency)?;
+            tasks.retain(|t| matcher.matches(t));
+            if tasks.is_empty() {
+                println!("No tasks match '{}'.", query);
+                return Ok(());
+            }
+            let colored = std::io::stdout().is_terminal();
+            print_task_table_with(&tasks, |task| {
+                let tags = &columns::name_with_tags(task)[task.name.len()..];
+                let mut cell = format!("{}{}", matcher.highlight(&task.name, colored), tags);
+                // The table has no description column, so a match there goes on its own line
+                if let Some(description) = task.description.as_deref().filter(|_| matcher.find(&task.name).is_none()) {
+                    cell.push_str(&format!("\n{:62}{}", "", matcher.highlight(description, colored)));
+                }
+                cell
+            });
+        },
+        Some(Commands::Tui { board }) => {
+            backfill::prompt_backfill(&daily_log_service, &config)?;
+            tui::run(board)?;
         },
         None => {
-            tui::run()?;
+            backfill::prompt_backfill(&daily_log_service, &config)?;
+            tui::run(false)?;
+        }
+    }
+    Ok(())
+}
+
+fn print_list(
+    service: &TaskService<FileTaskRepository>,
+    daily_log_service: &DailyLogService<FileDailyLogRepository>,
+    config: &Config,
+    view: &ViewConfig,
+    limit: Option<usize>,
+) -> Result<()> {
+    let status = view.status.as_deref().map(StatusFilter::parse).transpose()?;
+    let mut tasks = match status {
+        // Deleted tasks are left out of the sorted list; they only come from the trash
+        Some(StatusFilter::Deleted) => service.deleted_tasks()?.into_iter().map(|e| e.task).collect(),
+        _ => service.get_sorted_tasks(view.sort)?,
+    };
+    let now = chrono::Utc::now();
+
+    if let Some(filter) = status {
+        tasks.retain(|t| filter.matches(t));
+    }
+
+    if let Some(due) = &view.due {
+        let filter = DueFilter::parse(due)?;
+        tasks.retain(|t| filter.matches(t, now));
+    }
+    if let Some(p) = &view.project {
+        tasks.retain(|t| t.project.as_deref().is_some_and(|tp| tp.eq_ignore_ascii_case(p)));
+    }
+    if view.columns.contains(&ListColumn::Fit) && !view.fit {
+        DailyPlanUseCase::new(daily_log_service, config.plan.clone()).apply_daily_plan(&mut tasks)?;
+    }
+
+    if view.fit {
+        let mut tasks = tasks;
+        let usecase = DailyPlanUseCase::new(daily_log_service, config.plan.clone());
+        let stats = usecase.apply_daily_plan(&mut tasks)?;
+        let remaining = stats.remaining_active_capacity;
+        let time_display = config.display.time_display;
+        let mut fitting: Vec<TaskDto> = tasks.iter()
+            .filter(|t| t.fit == Some(true))
+            .cloned()
+            .collect();
+
+        if fitting.is_empty() {
+            let smallest = tasks.iter()
+                .filter(|t| t.status == "Pending" && !t.is_tracking && t.remaining_estimate > 0.0)
+                .min_by(|a, b| a.remaining_estimate.total_cmp(&b.remaining_estimate));
+            match smallest {
+                Some(t) => println!(
+                    "Nothing fits today: smallest task '{}' needs {}, {} remaining.",
+                    t.name, time_display.format(t.remaining_estimate), time_display.format(remaining)
+                ),
+                None => println!("Nothing fits today: no pending task has an estimate."),
+            }
+        } else {
+            let hidden = truncate_to(&mut fitting, limit);
+            print_view_table(&fitting, &view.columns, now);
+            print_more(hidden);
+            println!();
+            println!("capacity: {} remaining", time_display.format(remaining));
+            for budget in &stats.project_budgets {
+                println!(
+                    "  {}: {} of {} budget remaining",
+                    budget.project, time_display.format(budget.remaining_hours), time_display.format(budget.budget_hours)
+                );
+            }
         }
+    } else if tasks.is_empty() {
+        println!("No tasks found.");
+    } else {
+        let hidden = truncate_to(&mut tasks, limit);
+        print_view_table(&tasks, &view.columns, now);
+        print_more(hidden);
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Keeps the first `limit` items and returns how many were dropped.
+fn truncate_to<T>(items: &mut Vec<T>, limit: Option<usize>) -> usize {
+    let Some(limit) = limit.filter(|&limit| limit < items.len()) else {
+        return 0;
+    };
+    let hidden = items.len() - limit;
+    items.truncate(limit);
+    hidden
+}
+
+/// The footer under a list cut short by `--limit`.
+fn print_more(hidden: usize) {
+    if hidden > 0 {
+        println!("… and {} more", hidden);
+    }
+}
+
+/// Names of the tasks with these IDs, for messages; unknown IDs show as their short form.
+fn task_names(service: &TaskService<FileTaskRepository>, ids: &[uuid::Uuid]) -> Vec<String> {
+    ids.iter()
+        .map(|id| service.get_task(id).map(|t| t.name).unwrap_or_else(|_| id.to_string()[..8].to_string()))
+        .collect()
+}
+
+/// The standard table, or just the view's columns when it names any.
+fn print_view_table(tasks: &[TaskDto], view_columns: &[ListColumn], now: chrono::DateTime<chrono::Utc>) {
+    if view_columns.is_empty() {
+        return print_task_table(tasks);
+    }
+
+    let header: Vec<String> = view_columns.iter()
+        .map(|&c| format!("{:<w$}", c.title(), w = columns::width(c)))
+        .collect();
+    println!("{}", header.join(" ").trim_end());
+    let rule: Vec<String> = view_columns.iter().map(|&c| "-".repeat(columns::width(c))).collect();
+    println!("{}", rule.join(" "));
+
+    let colored = std::io::stdout().is_terminal();
+    for task in tasks {
+        let cells: Vec<String> = view_columns.iter()
+            .map(|&c| {
+                let cell = format!("{:<w$}", columns::cell(task, c, now), w = columns::width(c));
+                match c {
+                    ListColumn::Due => overdue_red(cell, task, colored),
+                    ListColumn::Priority => priority_colored(cell, &task.priority, colored),
+                    _ => cell,
+                }
+            })
+            .collect();
+        println!("{}", cells.join(" ").trim_end());
+    }
+}
+
+fn print_task_table(tasks: &[TaskDto]) {
+    print_task_table_with(tasks, columns::name_with_tags);
+}
+
+/// The standard table with the description cell rendered by `name`.
+fn print_task_table_with(tasks: &[TaskDto], name: impl Fn(&TaskDto) -> String) {
+    let colored = std::io::stdout().is_terminal();
+    println!("{:<8} {:<8} {:<10} {:<12} {:<10} {:<8} {:<20}", "ID", "Score", "Priority", "Due", "Project", "Timer", "Description");
+    println!("{:-<8} {:-<8} {:-<10} {:-<12} {:-<10} {:-<8} {:-<20}", "", "", "", "", "", "", "");
+
+    for task in tasks {
+        let id_str = task.id.to_string();
+        let short_id = if id_str.len() > 8 { &id_str[..8] } else { &id_str }; 
+        let pri = format!("{:?}", task.priority);
+        let due = task.due.map(|d: chrono::DateTime<chrono::Utc>| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".to_string());
+        let project = task.project.clone().unwrap_or_else(|| "-".to_string());
+        // TaskDto now has the score directly
+        let score = task.score;
+        // Elapsed time of the running session; `!` flags one left running for over a day's work
+        let timer = match (task.is_tracking, task.has_forgotten_timer()) {
+            (false, _) => "-".to_string(),
+            (true, false) => format_duration(task.open_session_time as f64 / 3600.0),
+            (true, true) => format!("{}!", format_duration(task.open_session_time as f64 / 3600.0)),
+        };
+
+        println!("{:<8} {:<8.1} {} {} {:<10} {:<8} {}", 
+            short_id,
+            score, 
+            priority_colored(format!("{:<10}", pri), &task.priority, colored), 
+            overdue_red(format!("{:<12}", due), task, colored), 
+            project, 
+            timer,
+            name(task)
+        );
+    }
+}
+
+/// Paints an already padded cell red when the task is overdue and `colored` (stdout is a terminal).
+fn overdue_red(cell: String, task: &TaskDto, colored: bool) -> String {
+    use crossterm::style::Stylize;
+    if colored && task.overdue { cell.red().to_string() } else { cell }
+}
+
+/// Paints an already padded priority cell like the TUI does: high red, medium yellow, low green.
+fn priority_colored(cell: String, priority: &Priority, colored: bool) -> String {
+    use crossterm::style::Stylize;
+    if !colored {
+        return cell;
+    }
+    match priority {
+        Priority::High => cell.red().to_string(),
+        Priority::Medium => cell.yellow().to_string(),
+        Priority::Low => cell.green().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // `add`, the TUI add prompt and the TUI modify prompt must all expand keys
+    // against the shared core list rather than keeping their own copies.
+    #[test]
+    fn test_known_keys_come_from_core() {
+        let local_list = concat!("known_keys = ", "vec![");
+        for source in [include_str!("main.rs"), include_str!("tui/app.rs")] {
+            assert!(!source.contains(local_list));
+        }
+    }
+}
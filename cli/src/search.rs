@@ -0,0 +1,66 @@
+use std::ops::Range;
+
+use anyhow::{anyhow, Result};
+use crossterm::style::Stylize;
+use regex::{Regex, RegexBuilder};
+use todoism_core::TaskDto;
+
+/// What `search` looks for in task names and descriptions, ignoring case.
+pub struct Matcher {
+    regex: Regex,
+}
+
+impl Matcher {
+    /// `query` is taken literally unless `regex` is set.
+    pub fn new(query: &str, regex: bool) -> Result<Self> {
+        let pattern = if regex { query.to_string() } else { regex::escape(query) };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .build()
+            .map(|regex| Self { regex })
+            .map_err(|e| anyhow!("Invalid regex '{}': {}", query, e))
+    }
+
+    /// Byte range of the first match in `text`.
+    pub fn find(&self, text: &str) -> Option<Range<usize>> {
+        self.regex.find(text).map(|m| m.range())
+    }
+
+    pub fn matches(&self, task: &TaskDto) -> bool {
+        self.find(&task.name).is_some() || task.description.as_deref().is_some_and(|d| self.find(d).is_some())
+    }
+
+    /// `text` with its first match highlighted, or as it is when `colored` is off.
+    pub fn highlight(&self, text: &str, colored: bool) -> String {
+        match self.find(text) {
+            Some(range) if colored && !range.is_empty() => format!(
+                "{}{}{}",
+                &text[..range.start],
+                text[range.clone()].bold().yellow(),
+                &text[range.end..]
+            ),
+            _ => text.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matcher() {
+        let text = Matcher::new("REPORT", false).unwrap();
+        assert_eq!(text.find("Write report"), Some(6..12));
+        assert_eq!(text.find("nothing"), None);
+        // Literal unless --regex
+        assert_eq!(Matcher::new("a.b", false).unwrap().find("axb"), None);
+
+        let pattern = Matcher::new("rep(o|a)rt", true).unwrap();
+        assert_eq!(pattern.find("Write REPART"), Some(6..12));
+        assert!(Matcher::new("(unclosed", true).is_err());
+
+        assert_eq!(text.highlight("Write report", false), "Write report");
+        assert_ne!(text.highlight("Write report", true), "Write report");
+    }
+}
@@ -3,16 +3,19 @@ use anyhow::Result;
 use crossterm::{
     event::{self, Event, KeyCode, KeyEventKind},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{enable_raw_mode, EnterAlternateScreen},
 };
 use ratatui::{
     prelude::*,
-    widgets::{Bar, BarChart, BarGroup, Block, Borders, BorderType, Paragraph, Gauge, Padding, Tabs},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, BorderType, Paragraph, Gauge, Padding, Tabs, Wrap},
 };
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+use todoism_core::config::{PlanConfig, TimeDisplay};
 use todoism_core::{
     repository::{DailyLogRepository, TaskRepository, FileStatsRepository},
-    service::{daily_log_service::DailyLogService, dto::WeeklyHistory},
-    usecase::history::HistoryUseCase,
+    service::{daily_log_service::DailyLogService, dto::{DailyHistory, WeeklyHistory, YearSummary}, task_service::parse_est_hours},
+    usecase::history::{HistoryUseCase, summarize_year},
+    usecase::project_stats::{ProjectStats, ProjectStatsUseCase},
 };
 
 // --- THEME ---
@@ -25,6 +28,11 @@ struct Theme {
     mtg: Color,
 }
 
+const TAB_TITLES: [&str; 4] = [" Overview ", " Heatmap ", " Review ", " Backlog "];
+const HEATMAP_TAB: usize = 1;
+const REVIEW_TAB: usize = 2;
+const BACKLOG_TAB: usize = 3;
+
 const THEME: Theme = Theme {
     primary: Color::Cyan,  // Highlights
     muted: Color::DarkGray,
@@ -37,17 +45,29 @@ const THEME: Theme = Theme {
 pub struct StatsApp {
     pub histories: Vec<WeeklyHistory>,
     pub current_week_index: usize,
-    pub current_tab: usize, // 0: Overview, 1: Heatmap
+    pub current_tab: usize, // 0: Overview, 1: Heatmap, 2: Review, 3: Backlog
+    pub working_days: Vec<Weekday>,
+    pub time_display: TimeDisplay,
+    pub hours_per_day: f64, // What a "day" is in the day figures; the configured daily capacity
+    pub heatmap_year_offset: usize, // Newest years hidden above the heatmap; 0 shows the current year first
+    pub backlog: Vec<ProjectStats>, // Read from the tasks when the screen opens, not from history
+    pub selected_day: Option<usize>, // Overview day whose tasks the info panel lists; None shows the week summary
 }
 
 impl StatsApp {
-    pub fn new(histories: Vec<WeeklyHistory>) -> Self {
+    pub fn new(histories: Vec<WeeklyHistory>, plan: &PlanConfig, time_display: TimeDisplay) -> Self {
         // Start at 0 (Newest week) because histories are sorted Descending (Newest -> Oldest)
         let current_week_index = 0;
         Self {
             histories,
             current_week_index,
             current_tab: 0,
+            working_days: plan.working_days.clone(),
+            time_display,
+            hours_per_day: plan.daily_capacity_hours,
+            heatmap_year_offset: 0,
+            backlog: Vec::new(),
+            selected_day: None,
         }
     }
 
@@ -62,9 +82,71 @@ impl StatsApp {
             self.current_week_index -= 1;
         }
     }
+
+    pub fn newest_week(&mut self) {
+        self.current_week_index = 0;
+    }
+
+    pub fn oldest_week(&mut self) {
+        self.current_week_index = self.histories.len().saturating_sub(1);
+    }
     
+    // Down walks from the week summary into its days, Up walks back out.
+    // The index is kept when changing weeks, so Wednesday stays selected.
+    pub fn next_day(&mut self) {
+        let days = self.current_data().map_or(0, |h| h.days.len());
+        self.selected_day = match self.selected_day {
+            None if days > 0 => Some(0),
+            Some(i) if i + 1 < days => Some(i + 1),
+            other => other,
+        };
+    }
+
+    pub fn previous_day(&mut self) {
+        self.selected_day = match self.selected_day {
+            Some(0) | None => None,
+            Some(i) => Some(i - 1),
+        };
+    }
+
+    /// The selected day of the current week, if that week has it.
+    pub fn current_day(&self) -> Option<&DailyHistory> {
+        self.current_data()?.days.get(self.selected_day?)
+    }
+
     pub fn next_tab(&mut self) {
-        self.current_tab = (self.current_tab + 1) % 2;
+        self.current_tab = (self.current_tab + 1) % TAB_TITLES.len();
+    }
+
+    // Histories are sorted newest first, so older years sit at higher indices.
+    pub fn older_year(&mut self) {
+        if let Some(year) = self.current_data().map(|h| h.year) {
+            if let Some(i) = self.histories.iter().position(|h| h.year < year) {
+                self.current_week_index = i;
+            }
+        }
+    }
+
+    pub fn newer_year(&mut self) {
+        if let Some(year) = self.current_data().map(|h| h.year) {
+            if let Some(newer) = self.histories.iter().rev().find(|h| h.year > year).map(|h| h.year) {
+                if let Some(i) = self.histories.iter().position(|h| h.year == newer) {
+                    self.current_week_index = i;
+                }
+            }
+        }
+    }
+
+    // Scrolls the heatmap one year at a time; the oldest year always stays on screen.
+    pub fn scroll_heatmap_older(&mut self) {
+        let years = heatmap_years(&self.histories).len();
+        if self.heatmap_year_offset + 1 < years {
+            self.heatmap_year_offset += 1;
+        }
+    }
+
+    pub fn scroll_heatmap_newer(&mut self) {
+        self.heatmap_year_offset = self.heatmap_year_offset.saturating_sub(1);
     }
 
     pub fn current_data(&self) -> Option<&WeeklyHistory> {
@@ -72,21 +154,53 @@ impl StatsApp {
     }
 }
 
-pub fn run<R, L>(task_repo: &R, daily_log_service: &DailyLogService<L>, stats_repo: &FileStatsRepository) -> Result<()>
+/// `stats --by-project`: a plain table, most actual hours first, with a total row.
+pub fn print_project_stats(stats: &[ProjectStats], time_display: TimeDisplay) {
+    if stats.is_empty() {
+        println!("No tasks found.");
+        return;
+    }
+
+    println!("{:<20} {:>6} {:>6} {:>10} {:>10}", "Project", "Tasks", "Done", "Estimated", "Actual");
+    println!("{:-<20} {:->6} {:->6} {:->10} {:->10}", "", "", "", "", "");
+    for entry in stats {
+        println!("{:<20} {:>6} {:>6} {:>10} {:>10}",
+            entry.project,
+            entry.tasks,
+            entry.completed,
+            time_display.format(entry.estimated_hours),
+            time_display.format(entry.actual_hours)
+        );
+    }
+    println!("{:-<20} {:->6} {:->6} {:->10} {:->10}", "", "", "", "", "");
+    println!("{:<20} {:>6} {:>6} {:>10} {:>10}",
+        "Total",
+        stats.iter().map(|s| s.tasks).sum::<usize>(),
+        stats.iter().map(|s| s.completed).sum::<usize>(),
+        time_display.format(stats.iter().map(|s| s.estimated_hours).sum()),
+        time_display.format(stats.iter().map(|s| s.actual_hours).sum())
+    );
+}
+
+pub fn run<R, L>(task_repo: &R, daily_log_service: &DailyLogService<L>, stats_repo: &FileStatsRepository, plan: &PlanConfig, time_display: TimeDisplay, in_progress_estimates: bool) -> Result<()>
 where
     R: TaskRepository,
     L: DailyLogRepository,
 {
     // Data setup
-    let usecase = HistoryUseCase::new(task_repo, daily_log_service, stats_repo);
+    let usecase = HistoryUseCase::new(task_repo, daily_log_service, stats_repo)
+        .with_in_progress_estimates(in_progress_estimates);
     let histories = usecase.get_weekly_history()?;
+    let backlog = ProjectStatsUseCase::new(task_repo).project_stats()?;
     
-    if histories.is_empty() {
+    // Queued work alone is still worth the Backlog tab
+    if histories.is_empty() && backlog.iter().all(|p| p.pending == 0) {
         println!("No history data available.");
         return Ok(());
     }
 
     // Terminal setup
+    crate::terminal::install_panic_hook();
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
@@ -94,7 +208,8 @@ where
     let mut terminal = Terminal::new(backend)?;
 
     // App setup
-    let mut app = StatsApp::new(histories);
+    let mut app = StatsApp::new(histories, plan, time_display);
+    app.backlog = backlog;
 
     // Main loop
     loop {
@@ -103,10 +218,21 @@ where
         if event::poll(Duration::from_millis(100))? {
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press {
+                    if crate::terminal::is_interrupt(&key) {
+                        break;
+                    }
                     match key.code {
                         KeyCode::Char('q') | KeyCode::Esc => break,
+                        KeyCode::Left | KeyCode::Char('h') if app.current_tab == REVIEW_TAB => app.older_year(),
+                        KeyCode::Right | KeyCode::Char('l') if app.current_tab == REVIEW_TAB => app.newer_year(),
+                        KeyCode::Down | KeyCode::Char('j') | KeyCode::PageDown if app.current_tab == HEATMAP_TAB => app.scroll_heatmap_older(),
+                        KeyCode::Down | KeyCode::Char('j') if app.current_tab == 0 => app.next_day(),
+                        KeyCode::Up | KeyCode::Char('k') if app.current_tab == 0 => app.previous_day(),
+                        KeyCode::Up | KeyCode::Char('k') | KeyCode::PageUp if app.current_tab == HEATMAP_TAB => app.scroll_heatmap_newer(),
                         KeyCode::Left | KeyCode::Char('h') => app.next_week(),
                         KeyCode::Right | KeyCode::Char('l') => app.previous_week(),
+                        KeyCode::Char('g') | KeyCode::Home => app.newest_week(),
+                        KeyCode::Char('G') | KeyCode::End => app.oldest_week(),
                         KeyCode::Tab => app.next_tab(),
                         _ => {}
                     }
@@ -116,9 +242,7 @@ where
     }
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    crate::terminal::restore()?;
 
     Ok(())
 }
@@ -153,8 +277,7 @@ fn ui(frame: &mut Frame, app: &StatsApp) {
     frame.render_widget(app_title, header_layout[0]);
 
     // Tabs
-    let titles = vec![" Overview ", " Heatmap "];
-    let tabs = Tabs::new(titles)
+    let tabs = Tabs::new(TAB_TITLES.to_vec())
         .block(Block::default().borders(Borders::BOTTOM).border_style(Style::default().fg(THEME.muted)))
         .highlight_style(Style::default().fg(THEME.text).add_modifier(Modifier::BOLD))
         .select(app.current_tab);
@@ -192,15 +315,25 @@ fn ui(frame: &mut Frame, app: &StatsApp) {
                     ])
                     .split(main_layout[1]);
 
-                draw_chart(frame, history, content_chunks[0]);
-                draw_info_panel(frame, history, content_chunks[2]);
+                draw_chart(frame, history, app.hours_per_day, app.selected_day, content_chunks[0]);
+                match app.current_day() {
+                    Some(day) => draw_day_panel(frame, day, app.time_display, app.hours_per_day, content_chunks[2]),
+                    None => draw_info_panel(frame, history, app.hours_per_day, content_chunks[2]),
+                }
             } else {
                 frame.render_widget(Paragraph::new("No data"), main_layout[1]);
             }
         },
-        1 => {
-            draw_heatmap(frame, &app.histories, main_layout[1]);
+        HEATMAP_TAB => {
+            draw_heatmap(frame, &app.histories, &app.working_days, app.heatmap_year_offset, main_layout[1]);
+        },
+        REVIEW_TAB => {
+            if let Some(history) = app.current_data() {
+                let summary = summarize_year(&app.histories, history.year);
+                draw_year_summary(frame, &summary, app.time_display, app.hours_per_day, main_layout[1]);
+            }
         },
+        BACKLOG_TAB => draw_backlog(frame, &app.backlog, app.time_display, main_layout[1]),
         _ => {}
     }
 
@@ -208,6 +341,31 @@ fn ui(frame: &mut Frame, app: &StatsApp) {
     let help_text = if app.current_tab == 0 {
         vec![
             Span::styled("NAV: ", Style::default().fg(THEME.muted)),
+            Span::styled("←/→ g/G ", Style::default().fg(THEME.text)),
+            Span::raw("  "),
+            Span::styled("DAY: ", Style::default().fg(THEME.muted)),
+            Span::styled("↑/↓ ", Style::default().fg(THEME.text)),
+            Span::raw("  "),
+            Span::styled("TAB: ", Style::default().fg(THEME.muted)),
+            Span::styled("Switch View ", Style::default().fg(THEME.text)),
+            Span::raw("  "),
+            Span::styled("QUIT: ", Style::default().fg(THEME.muted)),
+            Span::styled("q", Style::default().fg(THEME.text)),
+        ]
+    } else if app.current_tab == HEATMAP_TAB {
+        vec![
+            Span::styled("YEARS: ", Style::default().fg(THEME.muted)),
+            Span::styled("↑/↓ ", Style::default().fg(THEME.text)),
+            Span::raw("  "),
+            Span::styled("TAB: ", Style::default().fg(THEME.muted)),
+            Span::styled("Switch View ", Style::default().fg(THEME.text)),
+            Span::raw("  "),
+            Span::styled("QUIT: ", Style::default().fg(THEME.muted)),
+            Span::styled("q", Style::default().fg(THEME.text)),
+        ]
+    } else if app.current_tab == REVIEW_TAB {
+        vec![
+            Span::styled("YEAR: ", Style::default().fg(THEME.muted)),
             Span::styled("←/→ ", Style::default().fg(THEME.text)),
             Span::raw("  "),
             Span::styled("TAB: ", Style::default().fg(THEME.muted)),
@@ -230,7 +388,15 @@ fn ui(frame: &mut Frame, app: &StatsApp) {
     frame.render_widget(footer, main_layout[2]);
 }
 
-fn draw_heatmap(frame: &mut Frame, histories: &Vec<WeeklyHistory>, area: Rect) {
+// Distinct years in the histories, newest first (2026, 2025...)
+fn heatmap_years(histories: &[WeeklyHistory]) -> Vec<i32> {
+    let mut years: Vec<i32> = histories.iter().map(|h| h.year).collect();
+    years.sort_by(|a, b| b.cmp(a));
+    years.dedup();
+    years
+}
+
+fn draw_heatmap(frame: &mut Frame, histories: &Vec<WeeklyHistory>, working_days: &[Weekday], year_offset: usize, area: Rect) {
     // 1. Group by Year
     let mut years_map: std::collections::HashMap<i32, Vec<&WeeklyHistory>> = std::collections::HashMap::new();
     let mut max_hours = 1.0; // Baseline minimum to avoid div by zero
@@ -245,8 +411,7 @@ fn draw_heatmap(frame: &mut Frame, histories: &Vec<WeeklyHistory>, area: Rect) {
     }
     
     // 2. Sort Years Descending
-    let mut sorted_years: Vec<i32> = years_map.keys().cloned().collect();
-    sorted_years.sort_by(|a, b| b.cmp(a)); // 2026, 2025...
+    let sorted_years = heatmap_years(histories);
 
     if sorted_years.is_empty() { return; }
 
@@ -256,7 +421,8 @@ fn draw_heatmap(frame: &mut Frame, histories: &Vec<WeeklyHistory>, area: Rect) {
     
     // Check how many years fit
     let count = (total_height as usize / year_height).max(1);
-    let visible_years = sorted_years.iter().take(count);
+    let offset = year_offset.min(sorted_years.len() - 1);
+    let visible_years = sorted_years.iter().skip(offset).take(count);
     
     let constraints: Vec<Constraint> = visible_years.clone().map(|_| Constraint::Length(year_height as u16)).collect();
     
@@ -267,12 +433,12 @@ fn draw_heatmap(frame: &mut Frame, histories: &Vec<WeeklyHistory>, area: Rect) {
         
     for (i, &year) in visible_years.enumerate() {
         if let Some(year_data) = years_map.get(&year) {
-             draw_year_heatmap(frame, year, year_data, chunks[i], max_hours);
+             draw_year_heatmap(frame, year, year_data, working_days, chunks[i], max_hours);
         }
     }
 }
 
-fn draw_year_heatmap(frame: &mut Frame, year: i32, histories: &Vec<&WeeklyHistory>, area: Rect, max_hours: f64) {
+fn draw_year_heatmap(frame: &mut Frame, year: i32, histories: &Vec<&WeeklyHistory>, working_days: &[Weekday], area: Rect, max_hours: f64) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
@@ -325,28 +491,18 @@ fn draw_year_heatmap(frame: &mut Frame, year: i32, histories: &Vec<&WeeklyHistor
         HeatmapMode::Tiny => 1,
     };
     
-    let weeks_to_show = (available_width / cell_width).min(52).min(histories.len());
-    
-    let view_slice: Vec<&&WeeklyHistory> = histories.iter().take(weeks_to_show).rev().collect();
+    let weeks = heatmap_weeks(year, histories, available_width / cell_width, Local::now().date_naive());
     
     // --- Draw Month Labels ---
+    // A column is labelled when its Monday falls in a new month
     let mut month_spans = Vec::new();
-    let mut last_month = String::new();
+    let mut last_month = None;
     
-    for history in &view_slice {
-         let month_str = if let Some(day) = history.days.first() {
-             if day.date.len() >= 7 { &day.date[5..7] } else { "" }
-         } else { "" };
-         
-         let label_name = match month_str {
-             "01" => "Jan", "02" => "Feb", "03" => "Mar", "04" => "Apr",
-             "05" => "May", "06" => "Jun", "07" => "Jul", "08" => "Aug",
-             "09" => "Sep", "10" => "Oct", "11" => "Nov", "12" => "Dec",
-             _ => "",
-         };
-
-         if label_name != last_month && !label_name.is_empty() {
-             last_month = label_name.to_string();
+    for week in &weeks {
+         let month = week.monday.month();
+         if last_month != Some(month) {
+             last_month = Some(month);
+             let label_name = week.monday.format("%b").to_string();
              
              // Dynamic Label Truncation
              // Standard: "Jan" (3)
@@ -378,27 +534,26 @@ fn draw_year_heatmap(frame: &mut Frame, year: i32, histories: &Vec<&WeeklyHistor
 
     // --- Draw Grid ---
     let mut grid_lines: Vec<Line> = Vec::new();
-    let mut grid_data = Vec::new();
-    
-    for history in &view_slice {
-        let mut week_hours = vec![0.0; 7];
-        for day in &history.days {
-             let idx = match day.day_of_week.as_str() {
-                "Mon" => 0, "Tue" => 1, "Wed" => 2, "Thu" => 3, "Fri" => 4, "Sat" => 5, "Sun" => 6,
-                _ => 0,
-            };
-            if idx < 7 {
-                week_hours[idx] = day.stats.total_act_hours;
-            }
-        }
-        grid_data.push(week_hours);
-    }
+    let grid_data: Vec<[f64; 7]> = weeks.iter().map(|w| w.hours).collect();
 
     for row_idx in 0..7 {
         let mut spans = Vec::new();
-        for col_idx in 0..grid_data.len() {
-             let hours = grid_data[col_idx][row_idx];
+        // Rows run Mon..Sun
+        let working = Weekday::try_from(row_idx as u8).is_ok_and(|day| working_days.contains(&day));
+        for week_hours in &grid_data {
+             let hours = week_hours[row_idx];
              let color = get_heat_color(hours, max_hours);
+
+             // Idle days off are drawn as a dot instead of an empty cell; worked days off still show their heat
+             if !working && hours <= 0.1 {
+                 let dot = Span::styled("·", Style::default().fg(THEME.muted));
+                 match mode {
+                     HeatmapMode::Standard => spans.extend([Span::raw(" "), dot, Span::raw(" ")]),
+                     HeatmapMode::Compact => spans.extend([Span::raw(" "), dot]),
+                     HeatmapMode::Tiny => spans.push(dot),
+                 }
+                 continue;
+             }
              
              // Dynamic Cell Rendering
              match mode {
@@ -423,6 +578,41 @@ fn draw_year_heatmap(frame: &mut Frame, year: i32, histories: &Vec<&WeeklyHistor
     frame.render_widget(Paragraph::new(grid_lines), labels_vs_grid[1]);
 }
 
+/// One heatmap column: an ISO week of the year and its worked hours, Mon..Sun.
+#[derive(Debug, PartialEq)]
+struct HeatmapWeek {
+    week: u32,
+    monday: NaiveDate,
+    hours: [f64; 7],
+}
+
+/// Every ISO week of `year` in order, weeks without data left at zero, so a column's
+/// position is its real week. When fewer than all fit in `max_columns`, the columns
+/// end at the current week (for this year) or the last week (for past years).
+fn heatmap_weeks(year: i32, histories: &[&WeeklyHistory], max_columns: usize, today: NaiveDate) -> Vec<HeatmapWeek> {
+    let weeks_in_year = if NaiveDate::from_isoywd_opt(year, 53, Weekday::Mon).is_some() { 53 } else { 52 };
+    let mut last = weeks_in_year;
+    if max_columns < weeks_in_year as usize && today.iso_week().year() == year {
+        last = today.iso_week().week();
+    }
+    let first = (last + 1).saturating_sub(max_columns as u32).max(1);
+
+    (first..=last)
+        .filter_map(|week| {
+            let monday = NaiveDate::from_isoywd_opt(year, week, Weekday::Mon)?;
+            let mut hours = [0.0; 7];
+            for history in histories.iter().filter(|h| h.week == week) {
+                for day in &history.days {
+                    if let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") {
+                        hours[date.weekday().num_days_from_monday() as usize] += day.stats.total_act_hours;
+                    }
+                }
+            }
+            Some(HeatmapWeek { week, monday, hours })
+        })
+        .collect()
+}
+
 fn get_heat_color(hours: f64, max_hours: f64) -> Color {
     // Relative scaling with Linear Interpolation (Lerp)
     if hours <= 0.1 {
@@ -449,13 +639,16 @@ fn get_heat_color(hours: f64, max_hours: f64) -> Color {
     Color::Rgb(r as u8, g as u8, b as u8)
 }
 
-fn draw_chart(frame: &mut Frame, history: &WeeklyHistory, area: Rect) {
+fn draw_chart(frame: &mut Frame, history: &WeeklyHistory, hours_per_day: f64, selected_day: Option<usize>, area: Rect) {
     let mut bar_data = Vec::new();
 
-    for day in &history.days {
-        let act_val = day.stats.total_act_hours / 8.0;
-        let est_val = day.stats.total_est_hours / 8.0;
-        let mtg_val = day.stats.meeting_hours / 8.0;
+    for (i, day) in history.days.iter().enumerate() {
+        // The selected day's label is marked, e.g. "[Wed]"
+        let label = if selected_day == Some(i) { format!("[{}]", day.day_of_week) } else { day.day_of_week.clone() };
+
+        let act_val = day.stats.total_act_hours / hours_per_day;
+        let est_val = day.stats.total_est_hours / hours_per_day;
+        let mtg_val = day.stats.meeting_hours / hours_per_day;
 
         // Act (Green)
         bar_data.push((
@@ -466,7 +659,7 @@ fn draw_chart(frame: &mut Frame, history: &WeeklyHistory, area: Rect) {
         
         // Est (Cyan) - Label here
         bar_data.push((
-            day.day_of_week.clone(), 
+            label, 
             (est_val * 10.0) as u64, 
             THEME.est
         ));
@@ -506,7 +699,83 @@ fn draw_chart(frame: &mut Frame, history: &WeeklyHistory, area: Rect) {
     frame.render_widget(chart, area);
 }
 
-fn draw_info_panel(frame: &mut Frame, history: &WeeklyHistory, area: Rect) {
+// Remaining estimate of the pending tasks, one bar per project, biggest first
+fn draw_backlog(frame: &mut Frame, backlog: &[ProjectStats], time_display: TimeDisplay, area: Rect) {
+    let mut queued: Vec<&ProjectStats> = backlog.iter().filter(|p| p.pending > 0).collect();
+    queued.sort_by(|a, b| b.remaining_hours.total_cmp(&a.remaining_hours).then_with(|| a.project.cmp(&b.project)));
+
+    let total_hours: f64 = queued.iter().map(|p| p.remaining_hours).sum();
+    let total_tasks: usize = queued.iter().map(|p| p.pending).sum();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(THEME.muted))
+        .title(format!(" Backlog: {} over {} pending task(s) ", time_display.format(total_hours), total_tasks));
+
+    if queued.is_empty() {
+        frame.render_widget(Paragraph::new("No pending tasks").block(block), area);
+        return;
+    }
+
+    let bars: Vec<Bar> = queued.iter().map(|p| {
+        Bar::default()
+            .label(Line::from(p.project.as_str()))
+            .value((p.remaining_hours * 10.0).round() as u64)
+            .style(Style::default().fg(THEME.est))
+            .text_value(format!("{} ({})", time_display.format(p.remaining_hours), p.pending))
+    }).collect();
+
+    let chart = BarChart::default()
+        .block(block)
+        .direction(Direction::Horizontal)
+        .bar_width(1)
+        .bar_gap(1)
+        .data(BarGroup::default().bars(&bars));
+
+    frame.render_widget(chart, area);
+}
+
+// The selected day's totals and the tasks listed on it, with their estimate and actual time
+fn draw_day_panel(frame: &mut Frame, day: &DailyHistory, time_display: TimeDisplay, hours_per_day: f64, area: Rect) {
+    let stats = &day.stats;
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Actual:   ", Style::default().fg(THEME.muted)),
+            Span::styled(format!("{:.1}d", stats.total_act_hours / hours_per_day), Style::default().fg(THEME.act).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            Span::styled("Estimate: ", Style::default().fg(THEME.muted)),
+            Span::styled(format!("{:.1}d", stats.total_est_hours / hours_per_day), Style::default().fg(THEME.est).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            Span::styled("Meeting:  ", Style::default().fg(THEME.muted)),
+            Span::styled(format!("{:.1}d", stats.meeting_hours / hours_per_day), Style::default().fg(THEME.mtg).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+    ];
+
+    if day.tasks.is_empty() {
+        lines.push(Line::from(Span::styled("No tasks", Style::default().fg(THEME.muted))));
+    }
+    for task in &day.tasks {
+        let name_style = if task.status == "Pending" { Style::default().fg(THEME.muted) } else { Style::default().fg(THEME.text) };
+        lines.push(Line::from(Span::styled(task.name.clone(), name_style)));
+        lines.push(Line::from(vec![
+            Span::styled("  est ", Style::default().fg(THEME.muted)),
+            Span::styled(time_display.format(parse_est_hours(&task.estimate)), Style::default().fg(THEME.est)),
+            Span::styled("  act ", Style::default().fg(THEME.muted)),
+            Span::styled(time_display.format(task.accumulated_time as f64 / 3600.0), Style::default().fg(THEME.act)),
+        ]));
+    }
+
+    let panel = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().borders(Borders::ALL).border_type(BorderType::Rounded).border_style(Style::default().fg(THEME.muted))
+            .title(format!(" {} {} ", day.day_of_week, day.date)));
+    frame.render_widget(panel, area);
+}
+
+fn draw_info_panel(frame: &mut Frame, history: &WeeklyHistory, hours_per_day: f64, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -517,22 +786,22 @@ fn draw_info_panel(frame: &mut Frame, history: &WeeklyHistory, area: Rect) {
 
     // 1. Overview Card
     let stats = &history.stats;
-    let total_work = (stats.total_act_hours + stats.meeting_hours) / 8.0;
+    let total_work = (stats.total_act_hours + stats.meeting_hours) / hours_per_day;
     
     let info_text = vec![
         Line::from(vec![Span::styled("Overview", Style::default().add_modifier(Modifier::BOLD))]),
         Line::from(""),
         Line::from(vec![
             Span::styled("Actual:   ", Style::default().fg(THEME.muted)),
-            Span::styled(format!("{:.1}d", stats.total_act_hours / 8.0), Style::default().fg(THEME.act).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{:.1}d", stats.total_act_hours / hours_per_day), Style::default().fg(THEME.act).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(vec![
             Span::styled("Estimate: ", Style::default().fg(THEME.muted)),
-            Span::styled(format!("{:.1}d", stats.total_est_hours / 8.0), Style::default().fg(THEME.est).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{:.1}d", stats.total_est_hours / hours_per_day), Style::default().fg(THEME.est).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(vec![
             Span::styled("Meeting:  ", Style::default().fg(THEME.muted)),
-            Span::styled(format!("{:.1}d", stats.meeting_hours / 8.0), Style::default().fg(THEME.mtg).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("{:.1}d", stats.meeting_hours / hours_per_day), Style::default().fg(THEME.mtg).add_modifier(Modifier::BOLD)),
         ]),
         Line::from(""),
         Line::from(vec![
@@ -546,8 +815,9 @@ fn draw_info_panel(frame: &mut Frame, history: &WeeklyHistory, area: Rect) {
     frame.render_widget(info_block, chunks[0]);
 
     // 2. Legend & Gauge
-    let est_d = stats.total_est_hours / 8.0;
-    let act_d = stats.total_act_hours / 8.0;
+    // In-progress estimates are only non-zero with `stats.in_progress_estimates` enabled
+    let est_d = (stats.total_est_hours + stats.in_progress_est_hours) / hours_per_day;
+    let act_d = stats.total_act_hours / hours_per_day;
     
     // Efficiency: (Est / Act) * 100 ? Or Accuracy: (1 - |Est-Act|/Est)?
     // Let's show "Plan vs Actual" ratio.
@@ -555,7 +825,11 @@ fn draw_info_panel(frame: &mut Frame, history: &WeeklyHistory, area: Rect) {
     let percent = ratio * 100.0;
     
     // Gauge
-    let label = format!("{:.0}% of Est", percent);
+    let label = if stats.in_progress_est_hours > 0.0 {
+        format!("{:.0}% of Est (incl. {:.1}d in progress)", percent, stats.in_progress_est_hours / hours_per_day)
+    } else {
+        format!("{:.0}% of Est", percent)
+    };
     let gauge = Gauge::default()
         .block(Block::default().title(" Plan Adherence ").borders(Borders::ALL).border_type(BorderType::Rounded).border_style(Style::default().fg(THEME.muted)))
         .gauge_style(Style::default().fg(if ratio > 1.1 { THEME.mtg } else { THEME.act }))
@@ -563,4 +837,140 @@ fn draw_info_panel(frame: &mut Frame, history: &WeeklyHistory, area: Rect) {
         .label(label);
         
     frame.render_widget(gauge, chunks[1]);
-}
\ No newline at end of file
+}
+
+fn draw_year_summary(frame: &mut Frame, summary: &YearSummary, time_display: TimeDisplay, hours_per_day: f64, area: Rect) {
+    let insufficient = || Span::styled("insufficient data", Style::default().fg(THEME.muted));
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(THEME.muted));
+
+    let busiest = match summary.busiest_week {
+        Some((week, hours)) => Span::styled(format!("Week {} ({:.1}d)", week, hours / hours_per_day), Style::default().fg(THEME.act).add_modifier(Modifier::BOLD)),
+        None => insufficient(),
+    };
+    let project = match &summary.top_project {
+        Some((name, hours)) => Span::styled(format!("{} ({:.1}d)", name, hours / hours_per_day), Style::default().fg(THEME.text).add_modifier(Modifier::BOLD)),
+        None => insufficient(),
+    };
+
+    let stats = &summary.stats;
+    let lines = vec![
+        Line::from(vec![Span::styled(format!("{} in Review", summary.year), Style::default().fg(THEME.primary).add_modifier(Modifier::BOLD))]),
+        Line::from(""),
+        Line::from(vec![
+            label("Actual:         "),
+            Span::styled(format!("{} ({:.1}d)", time_display.format(stats.total_act_hours), stats.total_act_hours / hours_per_day), Style::default().fg(THEME.act).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            label("Estimate:       "),
+            Span::styled(format!("{} ({:.1}d)", time_display.format(stats.total_est_hours), stats.total_est_hours / hours_per_day), Style::default().fg(THEME.est).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(vec![
+            label("Meeting:        "),
+            Span::styled(format!("{} ({:.1}d)", time_display.format(stats.meeting_hours), stats.meeting_hours / hours_per_day), Style::default().fg(THEME.mtg).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::from(""),
+        Line::from(vec![label("Active weeks:   "), Span::styled(summary.active_weeks.to_string(), Style::default().fg(THEME.text))]),
+        Line::from(vec![label("Busiest week:   "), busiest]),
+        Line::from(vec![label("Top project:    "), project]),
+        Line::from(vec![label("Completed:      "), Span::styled(summary.completed_count.to_string(), Style::default().fg(THEME.text))]),
+    ];
+
+    let block = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).border_type(BorderType::Rounded).border_style(Style::default().fg(THEME.muted)).title(" Year in Review ").padding(Padding::new(2, 2, 1, 0)));
+    frame.render_widget(block, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use todoism_core::service::dto::HistoryStats;
+
+    fn week(year: i32) -> WeeklyHistory {
+        WeeklyHistory {
+            year,
+            week: 1,
+            days: vec![],
+            stats: HistoryStats::default(),
+        }
+    }
+
+    #[test]
+    fn test_heatmap_offset_clamps_to_years() {
+        let histories = vec![week(2026), week(2026), week(2025), week(2024)];
+        let mut app = StatsApp::new(histories, &PlanConfig { working_days: vec![], ..Default::default() }, TimeDisplay::default());
+
+        app.scroll_heatmap_newer();
+        assert_eq!(app.heatmap_year_offset, 0);
+
+        for _ in 0..5 {
+            app.scroll_heatmap_older();
+        }
+        assert_eq!(app.heatmap_year_offset, 2); // 2024 is the last year on screen
+
+        app.scroll_heatmap_newer();
+        assert_eq!(app.heatmap_year_offset, 1);
+    }
+
+    #[test]
+    fn test_day_selection_walks_the_week() {
+        use todoism_core::service::dto::DailyHistory;
+
+        let day = |date: &str| DailyHistory { date: date.to_string(), day_of_week: String::new(), tasks: vec![], stats: HistoryStats::default() };
+        let histories = vec![
+            WeeklyHistory { year: 2025, week: 3, days: vec![day("2025-01-13")], stats: HistoryStats::default() },
+            WeeklyHistory { year: 2025, week: 2, days: vec![day("2025-01-06"), day("2025-01-07")], stats: HistoryStats::default() },
+        ];
+        let mut app = StatsApp::new(histories, &PlanConfig::default(), TimeDisplay::default());
+        assert!(app.current_day().is_none());
+
+        app.next_day();
+        app.next_day(); // Only one day this week
+        assert_eq!(app.current_day().unwrap().date, "2025-01-13");
+
+        app.next_week();
+        app.next_day();
+        assert_eq!(app.current_day().unwrap().date, "2025-01-07");
+        app.previous_week();
+        assert!(app.current_day().is_none()); // The newer week has no second day
+
+        app.previous_day();
+        app.previous_day();
+        assert_eq!(app.selected_day, None);
+    }
+
+    #[test]
+    fn test_heatmap_weeks_cover_the_calendar() {
+        use todoism_core::service::dto::DailyHistory;
+
+        let day = |date: &str, hours: f64| DailyHistory {
+            date: date.to_string(),
+            day_of_week: String::new(),
+            tasks: vec![],
+            stats: HistoryStats { total_act_hours: hours, ..Default::default() },
+        };
+        // Weeks 2 and 10 of 2025 only
+        let sparse = [
+            WeeklyHistory { year: 2025, week: 10, days: vec![day("2025-03-05", 3.0)], stats: HistoryStats::default() },
+            WeeklyHistory { year: 2025, week: 2, days: vec![day("2025-01-06", 1.0)], stats: HistoryStats::default() },
+        ];
+        let refs: Vec<&WeeklyHistory> = sparse.iter().collect();
+        let past = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+
+        let weeks = heatmap_weeks(2025, &refs, 60, past);
+        assert_eq!(weeks.len(), 52);
+        assert_eq!(weeks[0].monday, NaiveDate::from_ymd_opt(2024, 12, 30).unwrap());
+        assert_eq!(weeks[1].hours, [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(weeks[9].week, 10);
+        assert_eq!(weeks[9].hours[2], 3.0); // Wednesday
+        assert!(weeks.iter().filter(|w| w.week != 2 && w.week != 10).all(|w| w.hours == [0.0; 7]));
+
+        // 2026 has 53 ISO weeks
+        assert_eq!(heatmap_weeks(2026, &[], 60, past).len(), 53);
+
+        // Too narrow: this year's columns end at the current week, past years at their last
+        let narrow = heatmap_weeks(2025, &refs, 20, NaiveDate::from_ymd_opt(2025, 3, 12).unwrap());
+        assert_eq!((narrow[0].week, narrow.last().unwrap().week), (1, 11));
+        let narrow = heatmap_weeks(2025, &refs, 20, past);
+        assert_eq!((narrow[0].week, narrow.last().unwrap().week), (33, 52));
+    }
+}
@@ -0,0 +1,29 @@
+use std::io;
+use std::panic;
+
+use crossterm::{
+    event::{DisableMouseCapture, KeyCode, KeyEvent, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, LeaveAlternateScreen},
+};
+
+/// Puts the terminal back into its normal state. Safe to call more than once.
+pub fn restore() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, crossterm::cursor::Show)
+}
+
+/// Restores the terminal before the default hook prints the panic, so a crash
+/// mid-draw doesn't leave the shell in raw mode on the alternate screen.
+pub fn install_panic_hook() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = restore();
+        default_hook(info);
+    }));
+}
+
+/// Ctrl-C arrives as a key event in raw mode instead of a signal.
+pub fn is_interrupt(key: &KeyEvent) -> bool {
+    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL)
+}
@@ -0,0 +1,66 @@
+use todoism_core::config::TimeDisplay;
+use todoism_core::service::dto::TodaySummary;
+use tabled::{Table, Tabled};
+use tabled::settings::{Style, Color, Modify};
+use tabled::settings::object::Rows;
+use anyhow::Result;
+
+// Helper struct for Table Row
+#[derive(Tabled)]
+struct TodayRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Description")]
+    desc: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Est (d)")]
+    est: String,
+    #[tabled(rename = "Today (h)")]
+    today: String,
+}
+
+pub fn show_today(summary: &TodaySummary, json: bool, time_display: TimeDisplay) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(summary)?);
+        return Ok(());
+    }
+
+    let stats = &summary.stats;
+    println!("\n\x1b[1;36mToday {}\x1b[0m (Done: {}, Mtg: {}, Remaining: {} of {})",
+             summary.date,
+             time_display.format(stats.work_done_today),
+             time_display.format(stats.meeting_hours),
+             time_display.format(stats.remaining_active_capacity),
+             time_display.format(stats.total_capacity));
+    for budget in &stats.project_budgets {
+        println!(
+            "  {}: {} of {} budget remaining",
+            budget.project, time_display.format(budget.remaining_hours), time_display.format(budget.budget_hours)
+        );
+    }
+
+    if summary.tasks.is_empty() {
+        println!("No work tracked today.");
+        return Ok(());
+    }
+
+    let rows: Vec<TodayRow> = summary.tasks.iter().map(|task_dto| {
+        TodayRow {
+            id: task_dto.id.to_string()[..8].to_string(),
+            desc: task_dto.name.clone(),
+            status: task_dto.status.clone(),
+            est: task_dto.estimate.clone().unwrap_or_else(|| "-".to_string()),
+            today: format!("{:.2}", (task_dto.today_accumulated_time + task_dto.today_untracked_time) as f64 / 3600.0),
+        }
+    }).collect();
+
+    let mut table = Table::new(rows);
+    table
+        .with(Style::modern())
+        .with(Modify::new(Rows::first()).with(Color::FG_CYAN)); // Header color
+
+    println!("{}", table);
+
+    Ok(())
+}
@@ -1,11 +1,18 @@
 use ratatui::widgets::TableState;
-use todoism_core::{FileTaskRepository, FileDailyLogRepository, Task, TaskDto, parse_args, expand_key, parse_human_date, Priority};
-use todoism_core::{TaskService, DailyLogService, SortStrategy};
+use todoism_core::{FileTaskRepository, FileDailyLogRepository, Task, TaskDto, parse_args, expand_key, metadata_key_names, parse_human_date, parse_duration, Priority};
+use todoism_core::{TaskService, TaskRepository, DailyLogService, SortStrategy, Config, Estimate, ViewConfig};
+use todoism_core::service::task_service::{DueFilter, StatusFilter, prerequisites_to_finish};
+use todoism_core::model::estimate::HOURS_PER_DAY;
+use todoism_core::repository::FileStatsRepository;
+use todoism_core::service::dto::DeletedTaskEntry;
+use todoism_core::state::AppState;
 use todoism_core::usecase::daily_plan::{DailyPlanUseCase, DailyPlanStats};
-use std::collections::HashMap;
-use chrono::Local;
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, Local, Utc};
 use uuid::Uuid;
 
+use crate::tui::symbols::Symbols;
+
 pub enum InputMode {
     Normal,
     Adding,
@@ -14,15 +21,56 @@ pub enum InputMode {
     CompleteWithEffort,
 }
 
+#[derive(Clone, Copy, PartialEq)]
+pub enum View {
+    List,
+    Board,
+    Trash,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum BoardGroup {
+    Status,
+    Project,
+}
+
+/// How many actions `u` can take back.
+const UNDO_LIMIT: usize = 50;
+
+/// A task as it was before a delete, completion, reopen or modify.
+pub struct UndoEntry {
+    pub action: &'static str,
+    pub before: Task,
+    pub spawned: Option<Uuid>, // Next instance added by completing a recurring task
+}
+
+pub struct BoardColumn<'a> {
+    pub title: String,
+    pub tasks: Vec<&'a TaskDto>,
+}
+
 pub struct App {
     pub service: TaskService<FileTaskRepository>,
     pub daily_log_service: DailyLogService<FileDailyLogRepository>,
     pub tasks: Vec<TaskDto>,
+    pub completed_today: Vec<TaskDto>, // Only shown on the status board
+    pub trash: Vec<DeletedTaskEntry>, // Most recently deleted first
     pub state: TableState,
+    pub view: View,
+    pub board_group: BoardGroup,
+    pub board_column: usize,
+    pub board_row: usize,
     pub input: String,
     pub input_mode: InputMode,
     pub cursor_position: usize,
     pub task_id_for_prompt: Option<Uuid>,
+    pub symbols: &'static Symbols,
+    pub page_size: usize, // Visible task rows, updated on every draw
+    pub status_message: Option<String>, // Shown in the footer until the next key press
+    pub config: Config,
+    pub saved_view: Option<(String, ViewConfig)>, // Cycled with `v`; None shows every open task
+    pub sort: SortStrategy, // Cycled with `O`; choosing a saved view switches to its sort
+    pub undo_stack: Vec<UndoEntry>, // This session's actions only, newest last; not persisted
     
     // Capacity Stats
     pub daily_stats: DailyPlanStats,
@@ -30,8 +78,18 @@ pub struct App {
 
 impl App {
     pub fn new() -> App {
+        let mut config = Config::load(None).unwrap_or_default();
+        // A layout picked with Tab last time wins over the configured one
+        if let Some(layout) = AppState::load(None).ok().and_then(|state| state.detail_layout) {
+            config.tui.detail_layout = layout;
+        }
+
         let repo = FileTaskRepository::new(None).expect("Failed to initialize repository");
-        let service = TaskService::new(repo);
+        let mut service = TaskService::new(repo);
+        if config.stats.live_update {
+            let stats_repo = FileStatsRepository::new(None).expect("Failed to initialize stats repository");
+            service = service.with_live_stats(stats_repo);
+        }
         
         let log_repo = FileDailyLogRepository::new(None).expect("Failed to initialize log repository");
         let daily_log_service = DailyLogService::new(log_repo);
@@ -46,41 +104,68 @@ impl App {
              }
         }
         
-        // Fetch all tasks first
-        let mut all_tasks = service.get_sorted_tasks(SortStrategy::Urgency).unwrap_or_default();
-        
-        // Apply Daily Plan Logic (Mutates tasks to add fit info)
-        let usecase = DailyPlanUseCase::new(&daily_log_service);
-        let daily_stats = usecase.apply_daily_plan(&mut all_tasks).unwrap_or_default();
-
-        // Filter for display
-        let tasks: Vec<TaskDto> = all_tasks.into_iter()
-            .filter(|t| t.status != "Completed" && t.status != "Deleted")
-            .collect();
-
-        let mut state = TableState::default();
-        if !tasks.is_empty() {
-            state.select(Some(0));
-        }
-        App { 
+        let mut app = App { 
             service,
             daily_log_service,
-            tasks, 
-            state,
+            tasks: Vec::new(),
+            completed_today: Vec::new(),
+            trash: Vec::new(),
+            state: TableState::default(),
+            view: View::List,
+            board_group: BoardGroup::Status,
+            board_column: 0,
+            board_row: 0,
             input: String::new(),
             input_mode,
             cursor_position: 0,
             task_id_for_prompt: None,
-            daily_stats,
+            symbols: Symbols::for_set(config.symbols.set),
+            page_size: 1,
+            status_message: None,
+            config,
+            saved_view: None,
+            sort: SortStrategy::Urgency,
+            undo_stack: Vec::new(),
+            daily_stats: DailyPlanStats::default(),
+        };
+
+        app.reload_tasks();
+        app.select_first();
+        if app.config.tui.show_welcome_delta {
+            app.status_message = app.welcome_delta();
         }
+        app
+    }
+
+    /// "Since your last visit" line, and records this visit. None on the first visit.
+    fn welcome_delta(&self) -> Option<String> {
+        let now = Utc::now();
+        let mut state = AppState::load(None).unwrap_or_default();
+        let last_opened = state.last_opened.replace(now);
+        let _ = state.save(None);
+
+        let activity = self.service.activity_since(last_opened?, now).ok()?;
+        Some(format!(
+            "Since your last visit: {} task(s) completed, {} logged",
+            activity.completed,
+            self.config.display.time_display.format(activity.tracked_seconds as f64 / 3600.0)
+        ))
     }
 
     pub fn next(&mut self) {
-        if self.tasks.is_empty() { return; }
+        if self.view == View::Board {
+            let len = self.board_column_len();
+            if len > 0 {
+                self.board_row = (self.board_row + 1) % len;
+            }
+            return;
+        }
+        let len = self.list_len();
+        if len == 0 { return; }
         
         let i = match self.state.selected() {
             Some(i) => {
-                if i >= self.tasks.len() - 1 {
+                if i >= len - 1 {
                     0
                 } else {
                     i + 1
@@ -92,12 +177,20 @@ impl App {
     }
 
     pub fn previous(&mut self) {
-        if self.tasks.is_empty() { return; }
+        if self.view == View::Board {
+            let len = self.board_column_len();
+            if len > 0 {
+                self.board_row = (self.board_row + len - 1) % len;
+            }
+            return;
+        }
+        let len = self.list_len();
+        if len == 0 { return; }
 
         let i = match self.state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.tasks.len() - 1
+                    len - 1
                 } else {
                     i - 1
                 }
@@ -107,30 +200,114 @@ impl App {
         self.state.select(Some(i));
     }
 
+    pub fn page_down(&mut self) {
+        let len = self.list_len();
+        if self.view == View::Board || len == 0 { return; }
+
+        let last = len - 1;
+        let i = match self.state.selected() {
+            Some(i) if i >= last => 0,
+            Some(i) => (i + self.page_size.max(1)).min(last),
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    pub fn page_up(&mut self) {
+        let len = self.list_len();
+        if self.view == View::Board || len == 0 { return; }
+
+        let i = match self.state.selected() {
+            Some(0) => len - 1,
+            Some(i) => i.saturating_sub(self.page_size.max(1)),
+            None => 0,
+        };
+        self.state.select(Some(i));
+    }
+
+    pub fn select_first(&mut self) {
+        if self.view == View::Board {
+            self.board_row = 0;
+        } else if self.list_len() > 0 {
+            self.state.select(Some(0));
+        } else {
+            self.state.select(None);
+        }
+    }
+
+    pub fn select_last(&mut self) {
+        if self.view == View::Board {
+            self.board_row = self.board_column_len().saturating_sub(1);
+        } else if self.list_len() > 0 {
+            self.state.select(Some(self.list_len() - 1));
+        }
+    }
+
     pub fn toggle_status(&mut self) {
-        if let Some(i) = self.state.selected() {
-            if let Some(task) = self.tasks.get(i) {
-                // Since only Pending tasks are shown, we are completing it.
-                // If we ever show Completed tasks, we should check task.status here.
-                
-                self.input_mode = InputMode::CompleteWithEffort;
-                self.task_id_for_prompt = Some(task.id);
-                
-                if let Some(est) = &task.estimate {
-                    self.input = est.clone();
-                    self.cursor_position = self.input.len();
-                } else {
-                    self.input.clear();
-                    self.cursor_position = 0;
-                }
+        if self.view == View::Trash { return; }
+        if let Some(task) = self.selected_task().cloned() {
+            // Completed tasks only appear on the status board; toggling reopens them.
+            if task.status == "Completed" {
+                self.record_undo("reopen", &task.id);
+                let _ = self.service.toggle_status(&task.id);
+                self.reload_tasks();
+                return;
+            }
+
+            self.input_mode = InputMode::CompleteWithEffort;
+            self.task_id_for_prompt = Some(task.id);
+            
+            if let Some(est) = &task.estimate {
+                // Effort is entered in plain days; ranges and hour estimates pre-fill their midpoint
+                self.input = match Estimate::parse(est) {
+                    Some(parsed) if est.trim().parse::<f64>().is_err() => {
+                        format!("{}", parsed.midpoint_hours() / HOURS_PER_DAY)
+                    },
+                    _ => est.clone(),
+                };
+                self.cursor_position = self.input.len();
+            } else {
+                self.input.clear();
+                self.cursor_position = 0;
             }
         }
     }
 
+    /// `s`: stops the selected task's timer, or starts it after stopping any other one.
+    pub fn toggle_tracking(&mut self) {
+        if self.view == View::Trash { return; }
+        let Some(task) = self.selected_task().filter(|t| t.status == "Pending").cloned() else { return; };
+
+        let result = if task.is_tracking {
+            self.service.stop_task(&task.id).map(|()| format!("Stopped tracking '{}'", task.name))
+        } else {
+            // Only one timer may run at a time
+            self.service.stop_all_tracking()
+                .and_then(|_| self.service.start_task(&task.id))
+                .map(|()| format!("Started tracking '{}'", task.name))
+        };
+        self.status_message = Some(result.unwrap_or_else(|e| format!("Tracking failed: {}", e)));
+
+        // The score may have moved the task; keep it selected
+        self.reload_tasks();
+        self.select_task(task.id);
+    }
+
     pub fn delete_task(&mut self) {
+        if self.view == View::Trash { return; }
+        if self.view == View::Board {
+            if let Some(id) = self.selected_task().map(|t| t.id) {
+                self.record_undo("delete", &id);
+                let _ = self.service.delete_task(&id);
+                self.reload_tasks();
+            }
+            return;
+        }
+
         if let Some(i) = self.state.selected() {
-            if let Some(task) = self.tasks.get(i) {
-                let _ = self.service.delete_task(&task.id);
+            if let Some(id) = self.tasks.get(i).map(|t| t.id) {
+                self.record_undo("delete", &id);
+                let _ = self.service.delete_task(&id);
             }
             // Instead of manually removing, just reload to be safe and consistent with sorting
             self.reload_tasks();
@@ -147,15 +324,295 @@ impl App {
     }
 
     fn reload_tasks(&mut self) {
-        if let Ok(mut all_tasks) = self.service.get_sorted_tasks(SortStrategy::Urgency) {
-             let usecase = DailyPlanUseCase::new(&self.daily_log_service);
+        if let Ok(trash) = self.service.deleted_tasks() {
+            self.trash = trash;
+        }
+        if let Ok(mut all_tasks) = self.service.get_sorted_tasks(self.sort) {
+             let usecase = DailyPlanUseCase::new(&self.daily_log_service, self.config.plan.clone());
              if let Ok(stats) = usecase.apply_daily_plan(&mut all_tasks) {
                  self.daily_stats = stats;
              }
              
-             self.tasks = all_tasks.into_iter()
-                .filter(|t| t.status != "Completed" && t.status != "Deleted")
+             let today = Local::now().date_naive();
+             let (completed, open): (Vec<TaskDto>, Vec<TaskDto>) = all_tasks.into_iter()
+                .filter(|t| t.status != "Deleted")
+                .partition(|t| t.status == "Completed");
+             self.completed_today = completed.iter()
+                .filter(|t| t.completed_at.is_some_and(|c| DateTime::<Local>::from(c).date_naive() == today))
+                .cloned()
                 .collect();
+             self.tasks = match &self.saved_view {
+                 // Like `list --status`: completed tasks come from the full list, deleted ones from the trash
+                 Some((_, view)) => match view.status.as_deref().and_then(|s| StatusFilter::parse(s).ok()) {
+                     Some(StatusFilter::Completed) => filter_by_view(completed, view),
+                     Some(StatusFilter::Deleted) => {
+                         filter_by_view(self.trash.iter().map(|e| e.task.clone()).collect(), view)
+                     },
+                     _ => filter_by_view(open, view),
+                 },
+                 None => open,
+             };
+        }
+        self.clamp_board_selection();
+    }
+
+    /// Steps through the saved views, then back to the plain list.
+    pub fn cycle_saved_view(&mut self) {
+        let names = self.config.view_names();
+        let next = match &self.saved_view {
+            None => names.first(),
+            Some((current, _)) => names.iter()
+                .position(|n| n == current)
+                .and_then(|i| names.get(i + 1)),
+        };
+        self.saved_view = next.and_then(|name| {
+            self.config.view(name).ok().map(|view| (name.clone(), view))
+        });
+        self.sort = self.saved_view.as_ref().map_or(SortStrategy::Urgency, |(_, view)| view.sort);
+        self.status_message = Some(match &self.saved_view {
+            Some((name, _)) => format!("View: {}", name),
+            None => "View: all tasks".to_string(),
+        });
+        self.reload_tasks();
+        self.select_first();
+    }
+
+    /// Steps through urgency, priority and due date ordering, keeping the selected task selected.
+    pub fn cycle_sort(&mut self) {
+        let selected = self.selected_task().map(|t| t.id);
+        self.sort = match self.sort {
+            SortStrategy::Urgency => SortStrategy::Priority,
+            SortStrategy::Priority => SortStrategy::DueDate,
+            SortStrategy::DueDate => SortStrategy::Urgency,
+        };
+        self.status_message = Some(format!("Sort: {}", sort_label(self.sort)));
+        self.reload_tasks();
+        match selected {
+            Some(id) => self.select_task(id),
+            None => self.select_first(),
+        }
+    }
+
+    /// Moves the selection to the task with `id` if the current view shows it.
+    fn select_task(&mut self, id: Uuid) {
+        match self.view {
+            View::List => {
+                if let Some(index) = self.tasks.iter().position(|t| t.id == id) {
+                    self.state.select(Some(index));
+                }
+            }
+            View::Board => {
+                let found = self.board_columns().iter().enumerate().find_map(|(c, column)| {
+                    column.tasks.iter().position(|t| t.id == id).map(|row| (c, row))
+                });
+                if let Some((column, row)) = found {
+                    self.board_column = column;
+                    self.board_row = row;
+                }
+            }
+            View::Trash => {}
+        }
+    }
+
+    /// The task actions apply to: the highlighted row in the list, or the highlighted card on the board.
+    pub fn selected_task(&self) -> Option<&TaskDto> {
+        match self.view {
+            View::List => self.state.selected().and_then(|i| self.tasks.get(i)),
+            View::Trash => self.state.selected().and_then(|i| self.trash.get(i)).map(|e| &e.task),
+            View::Board => self.board_columns()
+                .get(self.board_column)
+                .and_then(|column| column.tasks.get(self.board_row).copied()),
+        }
+    }
+
+    pub fn toggle_view(&mut self) {
+        let from_trash = self.view == View::Trash;
+        self.view = match self.view {
+            View::List => View::Board,
+            View::Board | View::Trash => View::List,
+        };
+        self.clamp_board_selection();
+        if from_trash {
+            self.select_first();
+        }
+    }
+
+    /// Switches between the trash and the task list.
+    pub fn toggle_trash(&mut self) {
+        self.view = match self.view {
+            View::Trash => View::List,
+            View::List | View::Board => View::Trash,
+        };
+        self.select_first();
+    }
+
+    /// Opens the selected task's URL in the default browser.
+    pub fn open_url(&mut self) {
+        let Some(task) = self.selected_task() else { return; };
+        let Some(url) = task.url.clone() else {
+            self.status_message = Some(format!("'{}' has no URL", task.name));
+            return;
+        };
+        self.status_message = Some(match open_in_browser(&url) {
+            Ok(()) => format!("Opened {}", url),
+            Err(e) => format!("Could not open {}: {}", url, e),
+        });
+    }
+
+    /// Pushes task `id` as it is now onto the undo stack; call before `action` changes it.
+    fn record_undo(&mut self, action: &'static str, id: &Uuid) {
+        if let Ok(before) = self.service.get_task(id) {
+            self.push_undo(UndoEntry { action, before, spawned: None });
+        }
+    }
+
+    fn push_undo(&mut self, entry: UndoEntry) {
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(entry);
+    }
+
+    fn task_ids(&self) -> Vec<Uuid> {
+        self.service.repo.list().map(|tasks| tasks.iter().map(|t| t.id).collect()).unwrap_or_default()
+    }
+
+    /// `u`: in the trash restores the selected task, elsewhere takes back the last
+    /// delete, completion, reopen or modify made in this session.
+    pub fn undo(&mut self) {
+        if self.view == View::Trash {
+            return self.undelete_task();
+        }
+        let Some(entry) = self.undo_stack.pop() else {
+            self.status_message = Some("Nothing to undo".to_string());
+            return;
+        };
+
+        let result = self.service.revert_task(&entry.before).and_then(|()| match entry.spawned {
+            Some(id) => self.service.repo.delete(&id),
+            None => Ok(()),
+        });
+        self.status_message = Some(match result {
+            Ok(()) => format!("Undid {} of '{}'", entry.action, entry.before.name),
+            Err(e) => format!("Undo failed: {}", e),
+        });
+        self.reload_tasks();
+    }
+
+    /// Restores the highlighted task in the trash view.
+    fn undelete_task(&mut self) {
+        let Some(i) = self.state.selected() else { return; };
+        let Some(id) = self.trash.get(i).map(|e| e.task.id) else { return; };
+
+        match self.service.restore_task(&id) {
+            Ok(task) => self.status_message = Some(format!("Restored '{}'", task.name)),
+            Err(e) => self.status_message = Some(format!("Restore failed: {}", e)),
+        }
+        self.reload_tasks();
+        if self.trash.is_empty() {
+            self.state.select(None);
+        } else if i >= self.trash.len() {
+            self.state.select(Some(self.trash.len() - 1));
+        }
+    }
+
+    /// Rows in the table shown by the current list-style view.
+    fn list_len(&self) -> usize {
+        match self.view {
+            View::Trash => self.trash.len(),
+            _ => self.tasks.len(),
+        }
+    }
+
+    pub fn toggle_board_group(&mut self) {
+        if self.view != View::Board { return; }
+        self.board_group = match self.board_group {
+            BoardGroup::Status => BoardGroup::Project,
+            BoardGroup::Project => BoardGroup::Status,
+        };
+        self.board_column = 0;
+        self.board_row = 0;
+    }
+
+    pub fn next_column(&mut self) {
+        let count = self.board_columns().len();
+        if self.view != View::Board || count == 0 { return; }
+        self.board_column = (self.board_column + 1) % count;
+        self.clamp_board_selection();
+    }
+
+    pub fn previous_column(&mut self) {
+        let count = self.board_columns().len();
+        if self.view != View::Board || count == 0 { return; }
+        self.board_column = (self.board_column + count - 1) % count;
+        self.clamp_board_selection();
+    }
+
+    /// Columns of the board. Status columns are fixed; project columns follow the
+    /// open tasks, with tasks without a project last. Each column keeps urgency order.
+    pub fn board_columns(&self) -> Vec<BoardColumn<'_>> {
+        match self.board_group {
+            BoardGroup::Status => {
+                let (tracking, pending): (Vec<&TaskDto>, Vec<&TaskDto>) = self.tasks.iter()
+                    .partition(|t| t.is_tracking);
+                vec![
+                    BoardColumn { title: "Pending".to_string(), tasks: pending },
+                    BoardColumn { title: "Tracking".to_string(), tasks: tracking },
+                    BoardColumn { title: "Done today".to_string(), tasks: self.completed_today.iter().collect() },
+                ]
+            },
+            BoardGroup::Project => {
+                let mut by_project: BTreeMap<&str, Vec<&TaskDto>> = BTreeMap::new();
+                let mut no_project = Vec::new();
+                for task in &self.tasks {
+                    match task.project.as_deref() {
+                        Some(p) => by_project.entry(p).or_default().push(task),
+                        None => no_project.push(task),
+                    }
+                }
+                let mut columns: Vec<BoardColumn> = by_project.into_iter()
+                    .map(|(project, tasks)| BoardColumn { title: project.to_string(), tasks })
+                    .collect();
+                if !no_project.is_empty() || columns.is_empty() {
+                    columns.push(BoardColumn { title: "(none)".to_string(), tasks: no_project });
+                }
+                columns
+            },
+        }
+    }
+
+    fn board_column_len(&self) -> usize {
+        self.board_columns().get(self.board_column).map_or(0, |c| c.tasks.len())
+    }
+
+    fn clamp_board_selection(&mut self) {
+        let count = self.board_columns().len();
+        if self.board_column >= count {
+            self.board_column = count.saturating_sub(1);
+        }
+        let len = self.board_column_len();
+        if self.board_row >= len {
+            self.board_row = len.saturating_sub(1);
+        }
+    }
+
+    /// Cycles side-by-side, list-only and detail-only layouts and remembers the choice.
+    pub fn cycle_detail_layout(&mut self) {
+        self.config.tui.detail_layout = self.config.tui.detail_layout.next();
+        let mut state = AppState::load(None).unwrap_or_default();
+        state.detail_layout = Some(self.config.tui.detail_layout);
+        if let Err(e) = state.save(None) {
+            self.status_message = Some(format!("Layout not saved: {}", e));
+        }
+    }
+
+    pub fn clear_status(&mut self) {
+        self.status_message = None;
+    }
+
+    fn report_ignored_keys(&mut self, errors: Vec<String>) {
+        if !errors.is_empty() {
+            self.status_message = Some(format!("Ignored {}", errors.join(", ")));
         }
     }
 
@@ -166,13 +623,23 @@ impl App {
     }
 
     pub fn enter_modify_mode(&mut self) {
-        if self.state.selected().is_some() {
+        if self.view != View::Trash && self.selected_task().is_some() {
             self.input_mode = InputMode::Modifying;
             self.input.clear();
             self.cursor_position = 0;
         }
     }
 
+    /// Re-opens the check-in prompt with today's logged hours to correct them.
+    /// Escaping keeps the current log.
+    pub fn edit_meeting_hours(&mut self) {
+        let today = Local::now().date_naive();
+        let current = self.daily_log_service.get_log(today).ok().flatten().map_or(0.0, |log| log.total_hours());
+        self.input = current.to_string();
+        self.cursor_position = self.input.chars().count();
+        self.input_mode = InputMode::MeetingHoursPrompt;
+    }
+
     pub fn exit_input_mode(&mut self) {
         self.input_mode = InputMode::Normal;
     }
@@ -228,30 +695,74 @@ impl App {
         
         if parsed.name.is_empty() { return; }
 
-        let known_keys = vec!["due", "project", "priority", "description", "estimate"];
+        let known_keys = metadata_key_names();
         let mut normalized_metadata = HashMap::new();
+        let mut key_errors = Vec::new();
         
         for (key, value) in parsed.metadata {
-            if let Ok(full_key) = expand_key(&key, &known_keys) {
-                normalized_metadata.insert(full_key, value);
+            match expand_key(&key, &known_keys) {
+                Ok(full_key) => {
+                    normalized_metadata.insert(full_key, value);
+                },
+                Err(e) => key_errors.push(e.to_string()),
             }
         }
+        self.report_ignored_keys(key_errors);
 
-        let due = normalized_metadata.get("due").and_then(|d| parse_human_date(d).ok());
+        // Like `add` without --force: a bad due date or estimate creates nothing
+        let due = match normalized_metadata.get("due").map(|d| (d, parse_human_date(d))) {
+            Some((_, Ok(dt))) => Some(dt),
+            Some((d, Err(_))) => {
+                self.status_message = Some(format!("Invalid due date '{}', task not added", d));
+                return;
+            },
+            None => None,
+        };
+        if let Some(est) = normalized_metadata.get("estimate") {
+            if Estimate::parse(est).is_none() {
+                self.status_message = Some(format!("Invalid estimate '{}', task not added", est));
+                return;
+            }
+        }
+        if let Some(recur) = normalized_metadata.get("recur") {
+            if parse_duration(recur).is_err() {
+                self.status_message = Some(format!("Invalid recurrence '{}', task not added", recur));
+                return;
+            }
+        }
         let project = normalized_metadata.get("project").cloned();
         let priority = normalized_metadata.get("priority")
              .map(|p| parse_priority_str(p))
              .unwrap_or_default();
         let description = normalized_metadata.get("description").cloned();
         let estimate = normalized_metadata.get("estimate").cloned();
+        let url = normalized_metadata.get("url").cloned();
+        let remind_before = normalized_metadata.get("remind").cloned();
 
         let mut new_task = Task::new(parsed.name, due);
         new_task.project = project;
         new_task.priority = priority;
         new_task.description = description;
         new_task.estimate = estimate;
+        new_task.url = url;
+        new_task.remind_before = remind_before;
+        new_task.recurrence = normalized_metadata.get("recur").cloned();
+        for tag in &parsed.tags {
+            new_task.add_tag(tag);
+        }
+        for dep in parsed.deps.iter().filter(|d| !d.is_empty()) {
+            match self.service.resolve_dependency(&new_task.id, dep) {
+                Ok(dep_id) => {
+                    new_task.add_dependency(dep_id);
+                },
+                Err(e) => {
+                    self.status_message = Some(format!("Invalid dependency '{}': {}, task not added", dep, e));
+                    return;
+                },
+            }
+        }
 
-        if let Ok(_) = self.service.create_task(new_task) {
+        if self.service.create_task(new_task).is_ok() {
              self.reload_tasks();
              if !self.tasks.is_empty() {
                  self.state.select(Some(0));
@@ -260,38 +771,83 @@ impl App {
     }
 
     fn submit_modify(&mut self) {
-        if let Some(i) = self.state.selected() {
+        if let Some(id) = self.selected_task().map(|t| t.id) {
              let args: Vec<String> = self.input.split_whitespace().map(|s| s.to_string()).collect();
              let parsed = parse_args(&args);
              
-             let known_keys = vec!["due", "project", "priority", "description", "estimate"];
+             let known_keys = metadata_key_names();
+             let mut key_errors = Vec::new();
              
-             if let Some(task_dto) = self.tasks.get(i) {
-                 // Fetch the full entity to modify
-                 if let Ok(mut task) = self.service.get_task(&task_dto.id) {
-                     if !parsed.name.is_empty() {
-                         task.name = parsed.name;
-                     }
-                     
-                     for (key, value) in parsed.metadata {
-                        if let Ok(full_key) = expand_key(&key, &known_keys) {
-                            match full_key.as_str() {
-                                "due" => {
-                                    if let Ok(d) = parse_human_date(&value) {
-                                        task.due = Some(d);
-                                    }
-                                },
-                                "project" => task.project = Some(value),
-                                "priority" => task.priority = parse_priority_str(&value),
-                                "description" => task.description = Some(value),
-                                "estimate" => task.estimate = Some(value),
-                                _ => {}
-                            }
+             // Fetch the full entity to modify
+             if let Ok(mut task) = self.service.get_task(&id) {
+                 let before = task.clone();
+                 if !parsed.name.is_empty() {
+                     task.name = parsed.name;
+                 }
+                 
+                 for (key, value) in parsed.metadata {
+                    let full_key = match expand_key(&key, &known_keys) {
+                        Ok(full_key) => full_key,
+                        Err(e) => {
+                            key_errors.push(e.to_string());
+                            continue;
                         }
+                    };
+                    if value.is_empty() {
+                        task.clear_field(&full_key);
+                        continue;
+                    }
+                    // Like `modify`: one bad value leaves the whole task unchanged
+                    let invalid = match full_key.as_str() {
+                        "due" if parse_human_date(&value).is_err() => Some("due date"),
+                        "estimate" if Estimate::parse(&value).is_none() => Some("estimate"),
+                        "remind" if parse_duration(&value).is_err() => Some("reminder"),
+                        "recur" if parse_duration(&value).is_err() => Some("recurrence"),
+                        _ => None,
+                    };
+                    if let Some(what) = invalid {
+                        self.status_message = Some(format!("Invalid {} '{}', task not modified", what, value));
+                        return;
+                    }
+                    match full_key.as_str() {
+                        "due" => task.due = parse_human_date(&value).ok(),
+                        "project" => task.project = Some(value),
+                        "priority" => task.priority = parse_priority_str(&value),
+                        "description" => task.description = Some(value),
+                        "estimate" => task.estimate = Some(value),
+                        "url" => task.url = Some(value),
+                        "remind" => task.remind_before = Some(value),
+                        "recur" => task.recurrence = Some(value),
+                        _ => {}
+                    }
+                 }
+                 for tag in &parsed.tags {
+                     if tag.is_empty() {
+                         task.clear_field("tag");
+                     } else {
+                         task.add_tag(tag);
+                     }
+                 }
+                 for dep in &parsed.deps {
+                     if dep.is_empty() {
+                         task.clear_field("dep");
+                         continue;
                      }
-                     let _ = self.service.update_task(&task);
+                     match self.service.resolve_dependency(&task.id, dep) {
+                         Ok(dep_id) => {
+                             task.add_dependency(dep_id);
+                         },
+                         Err(e) => {
+                             self.status_message = Some(format!("Invalid dependency '{}': {}, task not modified", dep, e));
+                             return;
+                         },
+                     }
+                 }
+                 if task != before && self.service.update_task(&task).is_ok() {
+                     self.push_undo(UndoEntry { action: "modify", before, spawned: None });
                  }
              }
+             self.report_ignored_keys(key_errors);
              self.reload_tasks();
         }
     }
@@ -299,20 +855,85 @@ impl App {
     fn submit_complete_with_effort(&mut self) {
         if let Some(id) = self.task_id_for_prompt {
             let effort = self.input.trim().to_string();
-            // Even if empty, we might want to allow it? 
-            // The prompt defaults to estimate. If user clears it, maybe it means 0?
-            // Let's pass whatever string they gave.
-            let _ = self.service.complete_task_with_effort(&id, effort);
             self.task_id_for_prompt = None;
+            if Estimate::parse(&effort).is_none() {
+                self.status_message = Some(format!("Invalid effort '{}', task not completed", effort));
+                return;
+            }
+            let log_effort = !self.config.stats.effort_on_completion_day;
+            if let Ok(before) = self.service.get_task(&id) {
+                // A recurring task gets its next instance; undoing the completion removes it again
+                let recurring = before.recurrence.is_some();
+                let ids_before = if recurring { self.task_ids() } else { Vec::new() };
+                match self.service.complete_task_with_effort(&id, effort, log_effort) {
+                    Ok(()) => {
+                        let spawned = if recurring {
+                            self.task_ids().into_iter().find(|id| !ids_before.contains(id))
+                        } else {
+                            None
+                        };
+                        self.push_undo(UndoEntry { action: "completion", before, spawned });
+                    },
+                    Err(e) => {
+                        self.status_message = Some(format!("Task not completed: {}", e));
+                        return;
+                    },
+                }
+            }
             self.reload_tasks();
+
+            if self.config.tui.auto_start_next {
+                self.start_next_fit_task();
+            }
+        }
+    }
+
+    // Tasks are already in urgency order, so the first fitting one is the next to do.
+    fn start_next_fit_task(&mut self) {
+        if self.tasks.is_empty() { return; }
+
+        if let Some(tracking) = self.tasks.iter().find(|t| t.is_tracking) {
+            self.status_message = Some(format!("'{}' is still tracking; not starting another", tracking.name));
+            return;
+        }
+
+        match self.tasks.iter().find(|t| t.fit == Some(true)).map(|t| (t.id, t.name.clone())) {
+            Some((id, name)) => {
+                let _ = self.service.start_task(&id);
+                self.reload_tasks();
+                let index = self.tasks.iter().position(|t| t.id == id).unwrap_or(0);
+                self.state.select(Some(index));
+                self.status_message = Some(format!("Started tracking '{}'", name));
+            },
+            None => {
+                // Blocked tasks that would fit once their prerequisites are done
+                let remaining = self.daily_stats.remaining_active_capacity;
+                let waiting: Vec<&TaskDto> = self.tasks.iter()
+                    .filter(|t| t.blocked && t.remaining_estimate > 0.0 && t.remaining_estimate <= remaining)
+                    .collect();
+                let first = prerequisites_to_finish(&self.tasks, &waiting);
+                // Never land on a task that can't be started
+                let index = first.first()
+                    .and_then(|p| self.tasks.iter().position(|t| t.id == p.id))
+                    .or_else(|| self.tasks.iter().position(|t| t.status == "Pending" && !t.blocked))
+                    .unwrap_or(0);
+                self.state.select(Some(index));
+                self.status_message = Some(if first.is_empty() {
+                    format!("Nothing fits today; selected '{}'", self.tasks[index].name)
+                } else {
+                    let names: Vec<String> = first.iter().map(|t| format!("'{}'", t.name)).collect();
+                    format!("Nothing unblocked fits today; finish {} first", names.join(", "))
+                });
+            }
         }
     }
 
     fn submit_meeting_hours(&mut self) {
         if let Ok(hours) = self.input.trim().parse::<f64>() {
             let today = Local::now().date_naive();
-            let _ = self.daily_log_service.add_log(today, hours);
+            let _ = self.daily_log_service.set_log(today, hours);
             self.input_mode = InputMode::Normal;
+            self.reload_tasks();
         } else {
              // Invalid input, maybe clear or keep for correction. 
              // For now, let's just clear and stay in mode or maybe provide visual feedback (not implemented in this step).
@@ -320,8 +941,9 @@ impl App {
              // If input is empty/invalid, we could default to 0.0 or force them to type correct number.
              if self.input.trim() == "0" || self.input.trim().is_empty() {
                   let today = Local::now().date_naive();
-                 let _ = self.daily_log_service.add_log(today, 0.0);
+                 let _ = self.daily_log_service.set_log(today, 0.0);
                  self.input_mode = InputMode::Normal;
+                 self.reload_tasks();
              }
         }
     }
@@ -335,3 +957,49 @@ fn parse_priority_str(s: &str) -> Priority {
         _ => Priority::Medium,
     }
 }
+
+// Hands the URL to the platform's opener. Output is discarded so it can't draw over the TUI.
+fn open_in_browser(url: &str) -> std::io::Result<()> {
+    use std::process::{Command, Stdio};
+
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(["/C", "start", ""]);
+        c
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+// Applies a saved view's status/due/project/fit filters; the plan has already set `fit`.
+fn filter_by_view(tasks: Vec<TaskDto>, view: &ViewConfig) -> Vec<TaskDto> {
+    let now = Utc::now();
+    let status = view.status.as_deref().and_then(|s| StatusFilter::parse(s).ok());
+    let due = view.due.as_deref().and_then(|d| DueFilter::parse(d).ok());
+    tasks.into_iter()
+        .filter(|t| status.is_none_or(|f| f.matches(t)))
+        .filter(|t| due.as_ref().is_none_or(|f| f.matches(t, now)))
+        .filter(|t| view.project.as_ref().is_none_or(|p| {
+            t.project.as_deref().is_some_and(|tp| tp.eq_ignore_ascii_case(p))
+        }))
+        .filter(|t| !view.fit || t.fit == Some(true))
+        .collect()
+}
+
+/// How the footer and list title name a sort order.
+pub fn sort_label(sort: SortStrategy) -> &'static str {
+    match sort {
+        SortStrategy::Urgency => "urgency",
+        SortStrategy::Priority => "priority",
+        SortStrategy::DueDate => "due date",
+    }
+}
@@ -1,12 +1,13 @@
 pub mod app;
+pub mod symbols;
 pub mod ui;
 
 use std::io;
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, EnableMouseCapture, Event, KeyCode},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{enable_raw_mode, EnterAlternateScreen},
 };
 use ratatui::{
     backend::{Backend, CrosstermBackend},
@@ -17,7 +18,11 @@ use crate::tui::app::{App, InputMode};
 
 
 
-pub fn run() -> Result<()> {
+pub fn run(board: bool) -> Result<()> {
+
+    crate::terminal::install_panic_hook();
+
+
 
     // Setup terminal
 
@@ -37,25 +42,19 @@ pub fn run() -> Result<()> {
 
     let mut app = App::new();
 
-    let res = run_app(&mut terminal, &mut app);
-
+    if board {
 
+        app.toggle_view();
 
-    // Restore terminal
-
-    disable_raw_mode()?;
-
-    execute!(
+    }
 
-        terminal.backend_mut(),
+    let res = run_app(&mut terminal, &mut app);
 
-        LeaveAlternateScreen,
 
-        DisableMouseCapture
 
-    )?;
+    // Restore terminal
 
-    terminal.show_cursor()?;
+    crate::terminal::restore()?;
 
 
 
@@ -79,7 +78,7 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
 
         terminal.draw(|f| ui::draw(f, app))
 
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e.to_string()))?;
+            .map_err(|e| io::Error::other(e.to_string()))?;
 
 
 
@@ -91,6 +90,20 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
 
 
 
+                        // Ctrl-C quits from any mode
+
+                        if crate::terminal::is_interrupt(&key) {
+
+                            return Ok(());
+
+                        }
+
+
+
+                        app.clear_status();
+
+
+
                         match app.input_mode {
 
 
@@ -119,6 +132,10 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
 
 
 
+                                                                                                            KeyCode::Char('s') => app.toggle_tracking(),
+
+
+
                                                                                                             
 
 
@@ -135,6 +152,66 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
 
 
 
+                                                                                                            KeyCode::Char('b') => app.toggle_view(),
+
+
+
+                                                                                                            KeyCode::Char('v') => app.cycle_saved_view(),
+
+
+
+                                                                                                            KeyCode::Char('B') => app.toggle_board_group(),
+
+
+
+                                                                                                            KeyCode::Char('t') => app.toggle_trash(),
+
+
+
+                                                                                                            KeyCode::Tab => app.cycle_detail_layout(),
+
+
+
+                                                                                                            KeyCode::Char('u') => app.undo(),
+
+
+
+                                                                                                            KeyCode::Char('o') => app.open_url(),
+
+
+
+                                                                                                            KeyCode::Char('O') => app.cycle_sort(),
+
+
+
+                                                                                                            KeyCode::Right | KeyCode::Char('l') => app.next_column(),
+
+
+
+                                                                                                            KeyCode::Left | KeyCode::Char('h') => app.previous_column(),
+
+
+
+                                                                                                            KeyCode::Char('M') => app.edit_meeting_hours(),
+
+
+
+                                                                                                            KeyCode::PageDown => app.page_down(),
+
+
+
+                                                                                                            KeyCode::PageUp => app.page_up(),
+
+
+
+                                                                                                            KeyCode::Char('g') | KeyCode::Home => app.select_first(),
+
+
+
+                                                                                                            KeyCode::Char('G') | KeyCode::End => app.select_last(),
+
+
+
                                                                                                             _ => {}
 
 
@@ -0,0 +1,86 @@
+use todoism_core::config::SymbolSet;
+use todoism_core::{Priority, TaskDto};
+
+// Single source of truth for every icon the TUI renders.
+pub struct Symbols {
+    pub tracking: &'static str,
+    pub forgotten: &'static str, // Tracking for longer than a working day
+    pub completed: &'static str,
+    pub pending: &'static str,
+    pub blocked: &'static str, // Pending, but a dependency isn't done yet
+    pub deleted: &'static str,
+    pub unknown: &'static str,
+    pub high: &'static str,
+    pub medium: &'static str,
+    pub low: &'static str,
+    pub highlight: &'static str,
+    pub progress_done: &'static str,
+    pub progress_todo: &'static str,
+}
+
+const UNICODE: Symbols = Symbols {
+    tracking: "▶",
+    forgotten: "⏱!",
+    completed: "✔",
+    pending: "☐",
+    blocked: "🔒",
+    deleted: "✖",
+    unknown: "?",
+    high: "H",
+    medium: "M",
+    low: "L",
+    highlight: ">> ",
+    progress_done: "▓",
+    progress_todo: "░",
+};
+
+const ASCII: Symbols = Symbols {
+    tracking: ">",
+    forgotten: ">!",
+    completed: "[x]",
+    pending: "[ ]",
+    blocked: "[B]",
+    deleted: "[-]",
+    unknown: "[?]",
+    high: "H",
+    medium: "M",
+    low: "L",
+    highlight: ">> ",
+    progress_done: "#",
+    progress_todo: ".",
+};
+
+impl Symbols {
+    pub fn for_set(set: SymbolSet) -> &'static Symbols {
+        match set {
+            SymbolSet::Unicode => &UNICODE,
+            SymbolSet::Ascii => &ASCII,
+        }
+    }
+
+    pub fn status(&self, task: &TaskDto) -> &'static str {
+        if task.has_forgotten_timer() {
+            return self.forgotten;
+        }
+        if task.is_tracking {
+            return self.tracking;
+        }
+        if task.blocked && task.status == "Pending" {
+            return self.blocked;
+        }
+        match task.status.as_str() {
+            "Completed" => self.completed,
+            "Pending" => self.pending,
+            "Deleted" => self.deleted,
+            _ => self.unknown,
+        }
+    }
+
+    pub fn priority(&self, priority: &Priority) -> &'static str {
+        match priority {
+            Priority::High => self.high,
+            Priority::Medium => self.medium,
+            Priority::Low => self.low,
+        }
+    }
+}
@@ -2,17 +2,33 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, BorderType, Paragraph, Row, Table, Wrap, Clear, Gauge},
+    widgets::{Block, Borders, BorderType, Paragraph, Row, Cell, Table, Wrap, Clear, Gauge, List, ListItem, ListState},
     Frame,
 };
-use todoism_core::Priority;
+use chrono::{Local, Utc};
+use todoism_core::{format_duration, ListColumn, Priority, TaskDto};
+use crate::columns;
+use todoism_core::service::task_service::parse_est_hours;
 use unicode_width::UnicodeWidthStr;
 
-use crate::tui::app::{App, InputMode};
+use todoism_core::config::DetailLayout;
+use crate::tui::app::{sort_label, App, InputMode, View};
+use crate::tui::symbols::Symbols;
+
+// Below this the fixed-height header/capacity/footer rows leave no room for content.
+const MIN_WIDTH: u16 = 60;
+const MIN_HEIGHT: u16 = 15;
+const PROGRESS_CELLS: usize = 5;
 
 pub fn draw(f: &mut Frame, app: &mut App) {
     let size = f.area();
 
+    // Checked on every frame, so the full layout comes back as soon as the terminal grows.
+    if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+        draw_too_small(f, size);
+        return;
+    }
+
     // Header and Main Content Split
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -35,26 +51,49 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     // Capacity Bar
     draw_capacity_bar(f, app, main_chunks[1]);
 
-    // Split Content into Left (List) and Right (Detail)
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(60),
-            Constraint::Percentage(40),
-        ])
-        .split(main_chunks[2]);
-
-    draw_task_list(f, app, content_chunks[0]);
-    draw_detail_view(f, app, content_chunks[1]);
+    match app.config.tui.detail_layout {
+        DetailLayout::Side => {
+            // Split Content into Left (List) and Right (Detail)
+            let content_chunks = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Percentage(60),
+                    Constraint::Percentage(40),
+                ])
+                .split(main_chunks[2]);
+            draw_main_view(f, app, content_chunks[0]);
+            draw_detail_view(f, app, content_chunks[1]);
+        },
+        DetailLayout::Hidden => {
+            let content_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Min(1),
+                    Constraint::Length(1),
+                ])
+                .split(main_chunks[2]);
+            draw_main_view(f, app, content_chunks[0]);
+            draw_detail_line(f, app, content_chunks[1]);
+        },
+        DetailLayout::Full => draw_detail_view(f, app, main_chunks[2]),
+    }
 
     // Footer or Input (adjust index to 3)
     let footer_chunk = main_chunks[3];
     
     match app.input_mode {
         InputMode::Normal => {
-            let footer = Paragraph::new("j/k: Navigate | Space: Toggle | d: Delete | a: Add | m: Mod | q: Quit")
-                .style(Style::default().fg(Color::DarkGray))
-                .alignment(Alignment::Center);
+            let footer = match &app.status_message {
+                Some(msg) => Paragraph::new(msg.as_str())
+                    .style(Style::default().fg(Color::Yellow)),
+                None => Paragraph::new(match app.view {
+                    View::List => "j/k: Navigate | PgUp/PgDn/g/G: Jump | Space: Toggle | s: Start/Stop | d: Delete | u: Undo | a: Add | m: Mod | M: Meetings | o: Open URL | O: Sort | v: Views | b: Board | t: Trash | Tab: Layout | q: Quit",
+                    View::Board => "h/l: Column | j/k: Navigate | B: Group | Space: Toggle | s: Start/Stop | d: Delete | u: Undo | a: Add | m: Mod | o: Open URL | O: Sort | b: List | t: Trash | Tab: Layout | q: Quit",
+                    View::Trash => "j/k: Navigate | PgUp/PgDn/g/G: Jump | u: Restore | t/b: Back to list | Tab: Layout | q: Quit",
+                })
+                    .style(Style::default().fg(Color::DarkGray)),
+            }
+            .alignment(Alignment::Center);
             f.render_widget(footer, footer_chunk);
         },
         InputMode::Adding => {
@@ -152,6 +191,14 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     }
 }
 
+fn draw_main_view(f: &mut Frame, app: &mut App, area: Rect) {
+    match app.view {
+        View::List => draw_task_list(f, app, area),
+        View::Board => draw_board(f, app, area),
+        View::Trash => draw_trash(f, app, area),
+    }
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -172,6 +219,19 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+fn draw_too_small(f: &mut Frame, area: Rect) {
+    let message = format!(
+        "Terminal too small (min {}x{}, now {}x{})",
+        MIN_WIDTH, MIN_HEIGHT, area.width, area.height
+    );
+    let paragraph = Paragraph::new(message)
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    let y = area.height / 2;
+    f.render_widget(paragraph, Rect::new(area.x, area.y + y, area.width, area.height - y));
+}
+
 fn draw_capacity_bar(f: &mut Frame, app: &App, area: Rect) {
     let capacity_total = app.daily_stats.total_capacity;
     let unavailable = app.daily_stats.meeting_hours;
@@ -185,9 +245,13 @@ fn draw_capacity_bar(f: &mut Frame, app: &App, area: Rect) {
     // [########.......]  Consumed / Effective Total
     // Or cleaner: "Capacity: 2.5h remaining (8h - 1h mtg - 4.5h done)"
     
+    let time_display = app.config.display.time_display;
     let label = format!(
-        "Capacity: {:.1}h rem. (Total 8h - {:.1}h mtg - {:.1}h done)", 
-        effective_remaining, unavailable, consumed
+        "Capacity: {} rem. (Total {} - {} mtg - {} done)",
+        time_display.format(effective_remaining),
+        time_display.format(capacity_total),
+        time_display.format(unavailable),
+        time_display.format(consumed)
     );
         
     
@@ -198,8 +262,17 @@ fn draw_capacity_bar(f: &mut Frame, app: &App, area: Rect) {
         1.0 // Over capacity or 0 capacity
     };
 
+    // Project budgets ride along in the title to keep the bar one row tall
+    let mut title = " Daily Capacity ".to_string();
+    for budget in &app.daily_stats.project_budgets {
+        title.push_str(&format!(
+            "| {} {}/{} ",
+            budget.project, time_display.format(budget.remaining_hours), time_display.format(budget.budget_hours)
+        ));
+    }
+
     let gauge = Gauge::default()
-        .block(Block::default().borders(Borders::ALL).title(" Daily Capacity "))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .gauge_style(Style::default().fg(if ratio > 0.9 { Color::Red } else { Color::Green }))
         .ratio(ratio)
         .label(label);
@@ -208,16 +281,27 @@ fn draw_capacity_bar(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn draw_task_list(f: &mut Frame, app: &mut App, area: Rect) {
+    // Rows left after the borders and the header row
+    app.page_size = (area.height.saturating_sub(3) as usize).max(1);
+
+    if let Some((_, view)) = &app.saved_view {
+        if !view.columns.is_empty() {
+            let columns = view.columns.clone();
+            return draw_view_table(f, app, &columns, area);
+        }
+    }
+
+    let symbols = app.symbols;
     let rows: Vec<Row> = app.tasks.iter().map(|task| {
-        let (status_icon, status_style) = if task.is_tracking {
-             ("▶", Style::default().fg(Color::Green))
+        let status_icon = symbols.status(task);
+        let status_style = if task.has_forgotten_timer() {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        } else if task.is_tracking {
+            Style::default().fg(Color::Green)
+        } else if task.blocked {
+            Style::default().fg(Color::DarkGray)
         } else {
-            match task.status.as_str() {
-                "Completed" => ("✔", Style::default()),
-                "Pending" => ("☐", Style::default()),
-                "Deleted" => ("✖", Style::default()),
-                _ => ("?", Style::default()),
-            }
+            Style::default()
         };
         
         let priority_style = match task.priority {
@@ -226,11 +310,7 @@ fn draw_task_list(f: &mut Frame, app: &mut App, area: Rect) {
             Priority::Low => Style::default().fg(Color::Green),
         };
 
-        let pri_str = match task.priority {
-            Priority::High => "H",
-            Priority::Medium => "M",
-            Priority::Low => "L",
-        };
+        let pri_str = symbols.priority(&task.priority);
 
         let due_str = task.due.map(|d| d.format("%m-%d").to_string()).unwrap_or_else(|| "-".to_string());
         let proj_str = task.project.clone().unwrap_or_else(|| "".to_string());
@@ -256,8 +336,9 @@ fn draw_task_list(f: &mut Frame, app: &mut App, area: Rect) {
             Span::styled(format!("{:.1}", score), Style::default().fg(Color::DarkGray)),
             Span::styled(fit_str, fit_style),
             Span::styled(pri_str, priority_style),
-            Span::raw(due_str),
+            Span::styled(due_str, if task.overdue { Style::default().fg(Color::Red) } else { Style::default() }),
             Span::raw(est_str),
+            progress_bar(task, symbols),
             Span::raw(proj_str),
             Span::styled(task.name.clone(), Style::default().add_modifier(Modifier::BOLD)),
         ])
@@ -266,80 +347,274 @@ fn draw_task_list(f: &mut Frame, app: &mut App, area: Rect) {
     let table = Table::new(
         rows,
         [
-            Constraint::Length(3),  // Status
+            Constraint::Length(3),  // Status (fits the widest ascii icon)
             Constraint::Length(5),  // Score
             Constraint::Length(4),  // Fit column
             Constraint::Length(3),  // Priority
             Constraint::Length(6),  // Due
             Constraint::Length(5),  // Est
+            Constraint::Length(PROGRESS_CELLS as u16), // Progress
             Constraint::Length(10), // Project
             Constraint::Min(10),    // Name
         ]
     )
-    .header(Row::new(vec!["St", "Score", "Fit", "Pr", "Due", "Est", "Project", "Task"]).style(Style::default().fg(Color::Yellow)))
-    .block(Block::default().title(" Tasks ").borders(Borders::ALL).border_type(BorderType::Rounded))
+    .header(Row::new(vec!["St", "Score", "Fit", "Pr", "Due", "Est", "Prog", "Project", "Task"]).style(Style::default().fg(Color::Yellow)))
+    .block(Block::default().title(list_title(app)).borders(Borders::ALL).border_type(BorderType::Rounded))
     .row_highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
-    .highlight_symbol(">> ");
+    .highlight_symbol(symbols.highlight);
 
     f.render_stateful_widget(table, area, &mut app.state);
 }
 
-fn draw_detail_view(f: &mut Frame, app: &App, area: Rect) {
-    if let Some(selected_index) = app.state.selected() {
-        if let Some(task) = app.tasks.get(selected_index) {
-            let mut detail_text = vec![
-                Line::from(vec![
-                    Span::styled("Title: ", Style::default().fg(Color::Blue)),
-                    Span::styled(&task.name, Style::default().add_modifier(Modifier::BOLD)),
-                ]),
-                Line::from(""),
-                Line::from(vec![
-                    Span::styled("ID: ", Style::default().fg(Color::DarkGray)),
-                    Span::raw(task.id.to_string()),
-                ]),
-                Line::from(vec![
-                    Span::styled("Status: ", Style::default().fg(Color::Blue)),
-                    Span::raw(&task.status),
-                ]),
-                Line::from(vec![
-                    Span::styled("Priority: ", Style::default().fg(Color::Blue)),
-                    Span::raw(format!("{:?}", task.priority)),
-                ]),
-                Line::from(vec![
-                    Span::styled("Score: ", Style::default().fg(Color::Blue)),
-                    Span::raw(format!("{:.2}", task.score)),
-                ]),
-                Line::from(vec![
-                    Span::styled("Due: ", Style::default().fg(Color::Blue)),
-                    Span::raw(task.due.map(|d| d.to_string()).unwrap_or_else(|| "None".to_string())),
-                ]),
-                Line::from(vec![
-                    Span::styled("Project: ", Style::default().fg(Color::Blue)),
-                    Span::raw(task.project.as_deref().unwrap_or("None")),
-                ]),
-                Line::from(vec![
-                    Span::styled("Estimate: ", Style::default().fg(Color::Blue)),
-                    Span::raw(task.estimate.as_deref().unwrap_or("None")),
-                ]),
-                Line::from(vec![
-                    Span::styled("Description: ", Style::default().fg(Color::Blue)),
-                    Span::raw(task.description.as_deref().unwrap_or("None")),
-                ]),
-                Line::from(vec![
-                    Span::styled("Time Logged: ", Style::default().fg(Color::Blue)),
-                    Span::raw(format!("{}s {}", task.accumulated_time, if task.is_tracking { "(Tracking)" } else { "" })),
-                ]),
-                Line::from(""),
-            ];
-
-            let detail_block = Paragraph::new(detail_text)
-                .block(Block::default().title(" Detail ").borders(Borders::ALL).border_type(BorderType::Rounded))
-                .wrap(Wrap { trim: true });
-            
-            f.render_widget(detail_block, area);
+fn list_title(app: &App) -> String {
+    match &app.saved_view {
+        Some((name, _)) => format!(" Tasks: {} (by {}) ", name, sort_label(app.sort)),
+        None => format!(" Tasks (by {}) ", sort_label(app.sort)),
+    }
+}
+
+// A saved view that picks its own columns: plain cells, same text as `list --view`.
+fn draw_view_table(f: &mut Frame, app: &mut App, view_columns: &[ListColumn], area: Rect) {
+    let now = Utc::now();
+    let rows: Vec<Row> = app.tasks.iter().map(|task| {
+        Row::new(view_columns.iter().map(|&c| {
+            let cell = Cell::from(columns::cell(task, c, now));
+            if c == ListColumn::Due && task.overdue { cell.style(Style::default().fg(Color::Red)) } else { cell }
+        }).collect::<Vec<_>>())
+    }).collect();
+    let widths: Vec<Constraint> = view_columns.iter().map(|&c| match c {
+        ListColumn::Name => Constraint::Min(10),
+        _ => Constraint::Length(columns::width(c) as u16),
+    }).collect();
+
+    let table = Table::new(rows, widths)
+        .header(Row::new(view_columns.iter().map(|c| c.title()).collect::<Vec<_>>()).style(Style::default().fg(Color::Yellow)))
+        .block(Block::default().title(list_title(app)).borders(Borders::ALL).border_type(BorderType::Rounded))
+        .row_highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+        .highlight_symbol(app.symbols.highlight);
+
+    f.render_stateful_widget(table, area, &mut app.state);
+}
+
+fn draw_trash(f: &mut Frame, app: &mut App, area: Rect) {
+    app.page_size = (area.height.saturating_sub(3) as usize).max(1);
+
+    let rows: Vec<Row> = app.trash.iter().map(|entry| {
+        let deleted = entry.deleted_at.with_timezone(&Local).format("%m-%d %H:%M").to_string();
+        let proj_str = entry.task.project.clone().unwrap_or_default();
+        Row::new(vec![
+            Span::styled(deleted, Style::default().fg(Color::DarkGray)),
+            Span::raw(proj_str),
+            Span::raw(entry.task.name.clone()),
+        ])
+    }).collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(11), // Deleted at
+            Constraint::Length(10), // Project
+            Constraint::Min(10),    // Name
+        ]
+    )
+    .header(Row::new(vec!["Deleted", "Project", "Task"]).style(Style::default().fg(Color::Yellow)))
+    .block(Block::default().title(format!(" Trash ({}) ", app.trash.len())).borders(Borders::ALL).border_type(BorderType::Rounded))
+    .row_highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+    .highlight_symbol(app.symbols.highlight);
+
+    f.render_stateful_widget(table, area, &mut app.state);
+}
+
+fn draw_board(f: &mut Frame, app: &App, area: Rect) {
+    let symbols = app.symbols;
+    let columns = app.board_columns();
+    let column_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(columns.iter().map(|_| Constraint::Ratio(1, columns.len() as u32)))
+        .split(area);
+
+    for (index, (column, chunk)) in columns.iter().zip(column_chunks.iter()).enumerate() {
+        let active = index == app.board_column;
+        let items: Vec<ListItem> = column.tasks.iter().map(|task| {
+            let status_style = if task.is_tracking {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default()
+            };
+            let priority_style = match task.priority {
+                Priority::High => Style::default().fg(Color::Red),
+                Priority::Medium => Style::default().fg(Color::Yellow),
+                Priority::Low => Style::default().fg(Color::Green),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(symbols.status(task), status_style),
+                Span::raw(" "),
+                Span::styled(symbols.priority(&task.priority), priority_style),
+                Span::raw(" "),
+                Span::raw(task.name.clone()),
+            ]))
+        }).collect();
+
+        let border_style = if active {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        let list = List::new(items)
+            .block(Block::default()
+                .title(format!(" {} ({}) ", column.title, column.tasks.len()))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(border_style))
+            .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD))
+            .highlight_symbol(symbols.highlight);
+
+        // Only the active column shows a cursor
+        let mut state = ListState::default();
+        if active && !column.tasks.is_empty() {
+            state.select(Some(app.board_row));
         }
+        f.render_stateful_widget(list, *chunk, &mut state);
+    }
+}
+
+// Tracked time against the estimate, capped at full and red once over.
+// Tasks without an estimate or without tracked time show nothing.
+fn progress_bar(task: &TaskDto, symbols: &Symbols) -> Span<'static> {
+    let est_hours = parse_est_hours(&task.estimate);
+    if est_hours <= 0.0 || task.accumulated_time == 0 {
+        return Span::raw("");
+    }
+
+    let ratio = (task.accumulated_time as f64 / 3600.0) / est_hours;
+    let done = ((ratio.min(1.0) * PROGRESS_CELLS as f64).round() as usize).max(1);
+    let bar = format!(
+        "{}{}",
+        symbols.progress_done.repeat(done),
+        symbols.progress_todo.repeat(PROGRESS_CELLS - done)
+    );
+    let color = if ratio > 1.0 { Color::Red } else { Color::Green };
+    Span::styled(bar, Style::default().fg(color))
+}
+
+// Archived or deleted dependencies aren't in the list any more; those show their short ID
+fn dependency_names(app: &App, task: &TaskDto) -> String {
+    if task.depends_on.is_empty() {
+        return "None".to_string();
+    }
+    let names: Vec<String> = task.depends_on.iter()
+        .map(|id| app.tasks.iter().find(|t| t.id == *id).map_or_else(|| id.to_string()[..8].to_string(), |t| t.name.clone()))
+        .collect();
+    let blocked = if task.blocked { " (blocked)" } else { "" };
+    format!("{}{}", names.join(", "), blocked)
+}
+
+fn draw_detail_view(f: &mut Frame, app: &App, area: Rect) {
+    if let Some(task) = app.selected_task() {
+        let detail_text = vec![
+            Line::from(vec![
+                Span::styled("Title: ", Style::default().fg(Color::Blue)),
+                Span::styled(&task.name, Style::default().add_modifier(Modifier::BOLD)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("ID: ", Style::default().fg(Color::DarkGray)),
+                Span::raw(task.id.to_string()),
+            ]),
+            Line::from(vec![
+                Span::styled("Status: ", Style::default().fg(Color::Blue)),
+                Span::raw(&task.status),
+            ]),
+            Line::from(vec![
+                Span::styled("Priority: ", Style::default().fg(Color::Blue)),
+                Span::raw(format!("{:?}", task.priority)),
+            ]),
+            Line::from(vec![
+                Span::styled("Score: ", Style::default().fg(Color::Blue)),
+                Span::raw(format!("{:.2}", task.score)),
+            ]),
+            Line::from(vec![
+                Span::styled("Due: ", Style::default().fg(Color::Blue)),
+                Span::raw(task.due.map(|d| d.to_string()).unwrap_or_else(|| "None".to_string())),
+            ]),
+            Line::from(vec![
+                Span::styled("Project: ", Style::default().fg(Color::Blue)),
+                Span::raw(task.project.as_deref().unwrap_or("None")),
+            ]),
+            Line::from(vec![
+                Span::styled("Tags: ", Style::default().fg(Color::Blue)),
+                Span::raw(if task.tags.is_empty() { "None".to_string() } else { task.tags.join(", ") }),
+            ]),
+            Line::from(vec![
+                Span::styled("Depends on: ", Style::default().fg(Color::Blue)),
+                Span::raw(dependency_names(app, task)),
+            ]),
+            Line::from(vec![
+                Span::styled("Recurs: ", Style::default().fg(Color::Blue)),
+                Span::raw(task.recurrence.as_deref().map_or("None".to_string(), |r| format!("every {}", r))),
+            ]),
+            Line::from(vec![
+                Span::styled("Estimate: ", Style::default().fg(Color::Blue)),
+                Span::raw(task.estimate.as_deref().unwrap_or("None")),
+            ]),
+            Line::from(vec![
+                Span::styled("Description: ", Style::default().fg(Color::Blue)),
+                Span::raw(task.description.as_deref().unwrap_or("None")),
+            ]),
+            Line::from(vec![
+                Span::styled("URL: ", Style::default().fg(Color::Blue)),
+                Span::raw(task.url.as_deref().unwrap_or("None")),
+            ]),
+            Line::from(vec![
+                Span::styled("Time Logged: ", Style::default().fg(Color::Blue)),
+                Span::raw(format!("{}s {}", task.accumulated_time, if task.is_tracking { "(Tracking)" } else { "" })),
+            ]),
+            Line::from(vec![
+                Span::styled("Running: ", Style::default().fg(Color::Blue)),
+                if task.is_tracking {
+                    let elapsed = format_duration(task.open_session_time as f64 / 3600.0);
+                    if task.has_forgotten_timer() {
+                        Span::styled(format!("{} (forgotten?)", elapsed), Style::default().fg(Color::Red))
+                    } else {
+                        Span::raw(elapsed)
+                    }
+                } else {
+                    Span::raw("-")
+                },
+            ]),
+            Line::from(""),
+        ];
+
+        let detail_block = Paragraph::new(detail_text)
+            .block(Block::default().title(" Detail ").borders(Borders::ALL).border_type(BorderType::Rounded))
+            .wrap(Wrap { trim: true });
+        
+        f.render_widget(detail_block, area);
     } else {
          let detail_block = Block::default().title(" Detail ").borders(Borders::ALL).border_type(BorderType::Rounded);
          f.render_widget(detail_block, area);
     }
 }
+
+// One-line summary of the selected task for the list-only layout.
+fn draw_detail_line(f: &mut Frame, app: &App, area: Rect) {
+    let Some(task) = app.selected_task() else {
+        return;
+    };
+    let due = task.due.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_else(|| "-".to_string());
+    let line = Line::from(vec![
+        Span::styled(task.name.clone(), Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(
+            format!(
+                "  {} | {:?} | due {} | est {} | {}",
+                task.status,
+                task.priority,
+                due,
+                task.estimate.as_deref().unwrap_or("-"),
+                task.project.as_deref().unwrap_or("-"),
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(line), area);
+}
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+/// Source of the current time. Services take one so that time-dependent logic
+/// (urgency, today's capacity, relative dates) can be tested against a fixed instant.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    fn local_now(&self) -> DateTime<Local> {
+        self.now().with_timezone(&Local)
+    }
+
+    /// The local calendar day of `now`.
+    fn today(&self) -> NaiveDate {
+        self.local_now().date_naive()
+    }
+}
+
+/// The real wall clock; the default everywhere.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Always returns the same instant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
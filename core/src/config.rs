@@ -0,0 +1,403 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use chrono::Weekday;
+use serde::{Deserialize, Serialize};
+
+use crate::model::task::Priority;
+use crate::paths;
+use crate::service::task_service::{DueFilter, SortStrategy, StatusFilter};
+use crate::time::format_duration;
+use crate::usecase::daily_plan::DAILY_CAPACITY_HOURS;
+
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+// User settings read from `config.toml` in the active profile's data directory.
+// Every field has a serde default so a missing file or a partial file behaves like today.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Config {
+    pub symbols: SymbolsConfig,
+    pub plan: PlanConfig,
+    pub tui: TuiConfig,
+    pub stats: StatsConfig,
+    pub scoring: ScoringConfig,
+    pub display: DisplayConfig,
+    pub history: HistoryConfig,
+    /// Named `list --view` presets; `triage` and `planning` exist unless overridden here.
+    pub views: BTreeMap<String, ViewConfig>,
+}
+
+/// A saved list layout: which columns, in which order, with which filters.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ViewConfig {
+    /// Columns left to right; empty keeps the standard columns.
+    pub columns: Vec<ListColumn>,
+    pub sort: SortStrategy,
+    /// Due filter, same values as `list --due` (today, week, overdue, a date)
+    pub due: Option<String>,
+    pub project: Option<String>,
+    /// Status filter, same values as `list --status` (pending, completed, deleted)
+    pub status: Option<String>,
+    /// Only tasks that fit today's remaining capacity
+    pub fit: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ListColumn {
+    Id,
+    Score,
+    Priority,
+    Due,
+    Project,
+    Timer,
+    Estimate,
+    Age,
+    Fit,
+    Tags,
+    Name,
+}
+
+impl ListColumn {
+    pub fn title(self) -> &'static str {
+        match self {
+            ListColumn::Id => "ID",
+            ListColumn::Score => "Score",
+            ListColumn::Priority => "Priority",
+            ListColumn::Due => "Due",
+            ListColumn::Project => "Project",
+            ListColumn::Timer => "Timer",
+            ListColumn::Estimate => "Est",
+            ListColumn::Age => "Age",
+            ListColumn::Fit => "Fit",
+            ListColumn::Tags => "Tags",
+            ListColumn::Name => "Description",
+        }
+    }
+}
+
+/// Views available without any configuration.
+pub fn builtin_views() -> Vec<(&'static str, ViewConfig)> {
+    use ListColumn::*;
+    vec![
+        ("triage", ViewConfig {
+            columns: vec![Id, Priority, Age, Score, Project, Name],
+            sort: SortStrategy::Priority,
+            ..Default::default()
+        }),
+        ("planning", ViewConfig {
+            columns: vec![Id, Estimate, Fit, Due, Project, Name],
+            sort: SortStrategy::Urgency,
+            ..Default::default()
+        }),
+    ]
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Order of the completed tasks within a day of `history`.
+    pub completion_order: CompletionOrder,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompletionOrder {
+    /// Earliest completion first, the way the day went
+    #[default]
+    Ascending,
+    /// Latest completion first
+    Descending,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// How hour amounts are shown in capacity labels and stats.
+    pub time_display: TimeDisplay,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeDisplay {
+    /// Decimal hours, e.g. `1.5h`
+    #[default]
+    Hours,
+    /// Hours and minutes, e.g. `1h30m`
+    Hm,
+}
+
+impl TimeDisplay {
+    pub fn format(self, hours: f64) -> String {
+        match self {
+            TimeDisplay::Hours => format!("{:.1}h", hours),
+            TimeDisplay::Hm => format_duration(hours),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ScoringConfig {
+    /// Weight of the due date in the urgency score.
+    pub coefficient_due: f64,
+    /// Weight of the priority.
+    pub coefficient_priority: f64,
+    /// Weight of the task's age; also the most age can add.
+    pub coefficient_age: f64,
+    /// Weight of a short estimate (quick wins first).
+    pub coefficient_estimate: f64,
+    /// Days a task must stay pending before the starvation boost applies; 0 disables it.
+    pub starvation_days: u32,
+    /// Urgency added to starving tasks; 0 disables it.
+    pub starvation_boost: f64,
+    /// Only tasks of this priority can starve.
+    pub starvation_priority: Priority,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            coefficient_due: 12.0,
+            coefficient_priority: 6.0,
+            coefficient_age: 2.0,
+            coefficient_estimate: 5.0,
+            starvation_days: 0,
+            starvation_boost: 0.0,
+            starvation_priority: Priority::High,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct StatsConfig {
+    /// Add completed tasks to the monthly stats right away rather than when they are archived.
+    pub live_update: bool,
+    /// Credit manual effort entered on completion to the completion day only, instead of
+    /// logging it as a time range that ends at completion.
+    pub effort_on_completion_day: bool,
+    /// Credit the estimates of tasks still in progress in history and the Plan Adherence gauge.
+    /// Each day a pending task is worked on is credited the hours tracked that day, until its
+    /// estimate is used up; once it's completed its full estimate moves to the completion day.
+    pub in_progress_estimates: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct TuiConfig {
+    /// After completing a task, start tracking the most urgent task that fits today.
+    pub auto_start_next: bool,
+    /// Detail panel layout until one is picked with Tab; the pick is kept in `state.json`.
+    pub detail_layout: DetailLayout,
+    /// On startup, summarize what was completed and logged since the TUI was last opened.
+    pub show_welcome_delta: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DetailLayout {
+    /// Task list and detail panel side by side
+    #[default]
+    Side,
+    /// Full-width list with a one-line detail footer
+    Hidden,
+    /// Detail panel only
+    Full,
+}
+
+impl DetailLayout {
+    pub fn next(self) -> Self {
+        match self {
+            DetailLayout::Side => DetailLayout::Hidden,
+            DetailLayout::Hidden => DetailLayout::Full,
+            DetailLayout::Full => DetailLayout::Side,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PlanConfig {
+    /// Hours available on a working day, e.g. `6` for a part-time day. Also how many
+    /// hours make up a "day" in stats and history.
+    pub daily_capacity_hours: f64,
+    /// Slack allowed when checking whether a task fits the remaining capacity.
+    pub fit_tolerance_hours: f64,
+    /// Count the manual effort of tasks completed today without time logs as consumed capacity.
+    pub count_untracked_completions: bool,
+    /// Days with capacity and an expected meeting log, e.g. `["Mon", "Tue", "Wed", "Thu", "Fri"]`.
+    pub working_days: Vec<Weekday>,
+    /// Share of the day's capacity (after meetings) reserved per project, e.g. `Work = 0.5`.
+    /// Projects not listed only draw on the global capacity.
+    pub project_budgets: BTreeMap<String, f64>,
+}
+
+impl Default for PlanConfig {
+    fn default() -> Self {
+        Self {
+            daily_capacity_hours: DAILY_CAPACITY_HOURS,
+            fit_tolerance_hours: 0.0,
+            count_untracked_completions: true,
+            working_days: vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri],
+            project_budgets: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct SymbolsConfig {
+    pub set: SymbolSet,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolSet {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+impl Config {
+    pub fn load(base_dir: Option<PathBuf>) -> Result<Self> {
+        let path = match base_dir {
+            Some(dir) => dir,
+            None => paths::data_dir()?,
+        }
+        .join(CONFIG_FILE_NAME);
+
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        Self::parse(&content)
+            .map_err(|e| anyhow!("Invalid config {}: {}", path.display(), e))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: Config = toml::from_str(content)?;
+        let capacity = config.plan.daily_capacity_hours;
+        if !(capacity > 0.0 && capacity <= 24.0) {
+            return Err(anyhow!("plan.daily_capacity_hours must be between 0 and 24, got {}", capacity));
+        }
+        for (name, view) in &config.views {
+            if let Some(due) = &view.due {
+                DueFilter::parse(due).map_err(|e| anyhow!("view '{}': invalid due filter: {}", name, e))?;
+            }
+            if let Some(status) = &view.status {
+                StatusFilter::parse(status).map_err(|e| anyhow!("view '{}': {}", name, e))?;
+            }
+        }
+        Ok(config)
+    }
+
+    /// The view called `name`: a configured one, else a built-in one.
+    pub fn view(&self, name: &str) -> Result<ViewConfig> {
+        if let Some(view) = self.views.get(name) {
+            return Ok(view.clone());
+        }
+        builtin_views().into_iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, view)| view)
+            .ok_or_else(|| anyhow!("Unknown view '{}' (available: {})", name, self.view_names().join(", ")))
+    }
+
+    /// Built-in views first, then the configured ones, without duplicates.
+    pub fn view_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = builtin_views().into_iter().map(|(name, _)| name.to_string()).collect();
+        for name in self.views.keys() {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_symbols_config() {
+        assert_eq!(Config::parse("").unwrap().symbols.set, SymbolSet::Unicode);
+
+        let config = Config::parse("[symbols]\nset = \"ascii\"\n").unwrap();
+        assert_eq!(config.symbols.set, SymbolSet::Ascii);
+
+        assert!(Config::parse("[symbols]\nset = \"emoji\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_plan_config() {
+        assert_eq!(Config::parse("").unwrap().plan.fit_tolerance_hours, 0.0);
+
+        let config = Config::parse("[plan]\nfit_tolerance_hours = 0.25\n").unwrap();
+        assert_eq!(config.plan.fit_tolerance_hours, 0.25);
+        assert_eq!(config.plan.working_days.len(), 5);
+
+        let config = Config::parse("[plan]\nworking_days = [\"Sun\", \"Monday\"]\n").unwrap();
+        assert_eq!(config.plan.working_days, vec![Weekday::Sun, Weekday::Mon]);
+
+        assert_eq!(Config::parse("").unwrap().plan.daily_capacity_hours, 8.0);
+        assert_eq!(Config::parse("[plan]\ndaily_capacity_hours = 6\n").unwrap().plan.daily_capacity_hours, 6.0);
+        assert!(Config::parse("[plan]\ndaily_capacity_hours = 0\n").is_err());
+        assert!(Config::parse("[plan]\ndaily_capacity_hours = 25\n").is_err());
+    }
+
+    #[test]
+    fn test_time_display() {
+        assert_eq!(Config::parse("").unwrap().display.time_display, TimeDisplay::Hours);
+        let config = Config::parse("[display]\ntime_display = \"hm\"\n").unwrap();
+        assert_eq!(config.display.time_display.format(1.5), "1h30m");
+        assert_eq!(TimeDisplay::Hours.format(0.25), "0.2h");
+    }
+
+    #[test]
+    fn test_parse_project_budgets() {
+        let config = Config::parse("[plan.project_budgets]\nWork = 0.5\n\"Side Project\" = 0.25\n").unwrap();
+        assert_eq!(config.plan.project_budgets.get("Work"), Some(&0.5));
+        assert_eq!(config.plan.project_budgets.get("Side Project"), Some(&0.25));
+    }
+
+    #[test]
+    fn test_parse_views() {
+        let config = Config::parse(
+            "[views.focus]\ncolumns = [\"id\", \"due\", \"name\"]\nsort = \"due\"\ndue = \"week\"\nproject = \"Work\"\n"
+        ).unwrap();
+        let focus = config.view("focus").unwrap();
+        assert_eq!(focus.columns, [ListColumn::Id, ListColumn::Due, ListColumn::Name]);
+        assert_eq!(focus.sort, SortStrategy::DueDate);
+        assert_eq!(focus.project.as_deref(), Some("Work"));
+        assert_eq!(config.view_names(), ["triage", "planning", "focus"]);
+        assert_eq!(config.view("triage").unwrap().sort, SortStrategy::Priority);
+        assert!(config.view("nope").is_err());
+
+        // Typos are rejected when the config loads
+        assert!(Config::parse("[views.x]\ncolumns = [\"nmae\"]\n").is_err());
+        assert!(Config::parse("[views.x]\nsrot = \"due\"\n").is_err());
+        assert!(Config::parse("[views.x]\ndue = \"someday\"\n").is_err());
+        assert!(Config::parse("[views.x]\nstatus = \"open\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_scoring_config() {
+        assert_eq!(Config::parse("").unwrap().scoring, ScoringConfig::default());
+
+        let config = Config::parse("[scoring]\nstarvation_days = 14\nstarvation_boost = 5.0\nstarvation_priority = \"Medium\"\n").unwrap();
+        assert_eq!(config.scoring.starvation_days, 14);
+        assert_eq!(config.scoring.starvation_boost, 5.0);
+        assert_eq!(config.scoring.starvation_priority, Priority::Medium);
+        assert_eq!(config.scoring.coefficient_due, 12.0);
+
+        let config = Config::parse("[scoring]\ncoefficient_due = 20\n").unwrap();
+        assert_eq!(config.scoring.coefficient_due, 20.0);
+        assert_eq!(config.scoring.coefficient_priority, 6.0);
+    }
+}
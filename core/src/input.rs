@@ -1,20 +1,133 @@
 use std::collections::HashMap;
 use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+/// A `key:value` metadata key accepted by `add` and the TUI prompts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct MetadataKey {
+    pub key: &'static str,
+    pub format: &'static str,
+    pub example: &'static str,
+}
+
+/// Every metadata key, in the order `schema` prints them. Keys may be abbreviated
+/// to any unique prefix (see `expand_key`), except those in `FULL_KEY_ONLY`.
+pub const METADATA_KEYS: &[MetadataKey] = &[
+    MetadataKey {
+        key: "due",
+        format: "today, tomorrow, eow, eom, +Nd/+Nw/+Nm, weekday (fri: soonest incl. today, 1:fri: after today, 2:fri), YYYY-MM-DD; optionally followed by a time (fri 9am, tomorrow 14:00)",
+        example: "due:fri",
+    },
+    MetadataKey {
+        key: "project",
+        format: "free text; quote values with spaces",
+        example: "project:Work",
+    },
+    MetadataKey {
+        key: "priority",
+        format: "H/M/L or high/medium/low",
+        example: "priority:H",
+    },
+    MetadataKey {
+        key: "description",
+        format: "free text; quote values with spaces",
+        example: "description:\"see ticket 42\"",
+    },
+    MetadataKey {
+        key: "estimate",
+        format: "days (0.5), hours (4h), or a range (2-3d)",
+        example: "estimate:4h",
+    },
+    MetadataKey {
+        key: "url",
+        format: "link to a ticket or doc, opened with `o` in the TUI",
+        example: "url:https://example.com/issues/42",
+    },
+    MetadataKey {
+        key: "tag",
+        format: "a label; repeat the key for several (tag:home tag:errand)",
+        example: "tag:home",
+    },
+    MetadataKey {
+        key: "dep",
+        format: "ID prefix of a task that must be finished first; repeat for several (key not abbreviable)",
+        example: "dep:a1b2c3d4",
+    },
+    MetadataKey {
+        key: "recur",
+        format: "repeat interval, e.g. 1d, 2w; completing adds the next instance (key not abbreviable)",
+        example: "recur:1w",
+    },
+    MetadataKey {
+        key: "remind",
+        format: "time before the due date, e.g. 30m, 1h30m, 1d (needs `todoism daemon`)",
+        example: "remind:1h",
+    },
+];
+
+/// Names of `METADATA_KEYS`, ready for `expand_key`.
+pub fn metadata_key_names() -> Vec<&'static str> {
+    METADATA_KEYS.iter().map(|k| k.key).collect()
+}
 
 #[derive(Debug, PartialEq)]
 pub struct ParsedInput {
     pub name: String,
     pub metadata: HashMap<String, String>,
+    /// Values of every `tag:` (or an abbreviation of it), in order. Like `deps`, the key
+    /// may repeat; an empty entry comes from a bare `tag:`.
+    pub tags: Vec<String>,
+    /// Values of every `dep:`, in order: ID prefixes still to be resolved by the caller.
+    pub deps: Vec<String>,
 }
 
+/// Splits arguments into name words and `key:value` metadata.
+///
+/// Only the first colon separates key and value, so values keep any later colons
+/// (`url:https://example.com`, `time:9:30`). A token is metadata only when its key
+/// looks like a word (starts with a letter); `9:30`, `:bad` or ` :x` stay in the name.
+///
+/// A value starting with `"` runs until the argument ending with the closing quote, so
+/// `project:"My Big Project"` split into words (as the TUI does) is one value again.
+/// An unclosed quote takes the rest of the arguments.
+///
+/// A key with nothing after the colon (`due:`, `project:""`) is kept with an empty
+/// value, so callers can tell "clear this field" from the key not being given.
 pub fn parse_args(args: &[String]) -> ParsedInput {
     let mut name_parts = Vec::new();
     let mut metadata = HashMap::new();
+    let mut tags = Vec::new();
+    let mut deps = Vec::new();
+    let known_keys = metadata_key_names();
 
-    for arg in args {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
         if let Some((key, value)) = arg.split_once(':') {
-            if !key.is_empty() {
-                metadata.insert(key.to_string(), value.to_string());
+            if is_metadata_key(key) {
+                let value = match value.strip_prefix('"') {
+                    Some(quoted) => {
+                        let mut value = quoted.to_string();
+                        let mut closed = value.ends_with('"');
+                        while !closed {
+                            let Some(next) = iter.next() else { break; };
+                            value.push(' ');
+                            value.push_str(next);
+                            closed = next.ends_with('"');
+                        }
+                        if closed {
+                            value.pop();
+                        }
+                        value
+                    },
+                    None => value.to_string(),
+                };
+                match expand_key(key, &known_keys).as_deref() {
+                    Ok("tag") => tags.push(value),
+                    Ok("dep") => deps.push(value),
+                    _ => {
+                        metadata.insert(key.to_string(), value);
+                    },
+                }
                 continue;
             }
         }
@@ -24,9 +137,25 @@ pub fn parse_args(args: &[String]) -> ParsedInput {
     ParsedInput {
         name: name_parts.join(" "),
         metadata,
+        tags,
+        deps,
     }
 }
 
+fn is_metadata_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() => {
+            chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        },
+        _ => false,
+    }
+}
+
+/// Keys added after their prefixes were already in use (`de:` for description, `re:`
+/// for remind). They are only recognised written in full, so those abbreviations still work.
+pub const FULL_KEY_ONLY: &[&str] = &["dep", "recur"];
+
 pub fn expand_key(key: &str, candidates: &[&str]) -> Result<String> {
     // 1. Exact match
     if candidates.contains(&key) {
@@ -36,7 +165,7 @@ pub fn expand_key(key: &str, candidates: &[&str]) -> Result<String> {
     // 2. Prefix match
     let matches: Vec<&str> = candidates
         .iter()
-        .filter(|&&c| c.starts_with(key))
+        .filter(|&&c| c.starts_with(key) && !FULL_KEY_ONLY.contains(&c))
         .cloned()
         .collect();
 
@@ -65,6 +194,84 @@ mod tests {
         assert_eq!(parsed.metadata.get("project"), Some(&"Groceries".to_string()));
     }
 
+    #[test]
+    fn test_parse_values_keep_later_colons() {
+        let args = vec!["Read".to_string(), "url:https://x".to_string(), "time:9:30".to_string()];
+        let parsed = parse_args(&args);
+        assert_eq!(parsed.name, "Read");
+        assert_eq!(parsed.metadata.get("url"), Some(&"https://x".to_string()));
+        assert_eq!(parsed.metadata.get("time"), Some(&"9:30".to_string()));
+    }
+
+    fn words(input: &str) -> Vec<String> {
+        input.split_whitespace().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_quoted_project() {
+        let parsed = parse_args(&words(r#"Plan launch project:"My Big Project" pri:H"#));
+        assert_eq!(parsed.name, "Plan launch");
+        assert_eq!(parsed.metadata.get("project"), Some(&"My Big Project".to_string()));
+        assert_eq!(parsed.metadata.get("pri"), Some(&"H".to_string()));
+
+        // A single quoted word just loses its quotes
+        let parsed = parse_args(&words(r#"Plan project:"Solo""#));
+        assert_eq!(parsed.metadata.get("project"), Some(&"Solo".to_string()));
+    }
+
+    #[test]
+    fn test_parse_quoted_description() {
+        let parsed = parse_args(&words(r#"Fix bug description:"crashes on empty input" est:2h"#));
+        assert_eq!(parsed.name, "Fix bug");
+        assert_eq!(parsed.metadata.get("description"), Some(&"crashes on empty input".to_string()));
+        assert_eq!(parsed.metadata.get("est"), Some(&"2h".to_string()));
+
+        // Unclosed quotes run to the end
+        let parsed = parse_args(&words(r#"Fix description:"no closing quote"#));
+        assert_eq!(parsed.metadata.get("description"), Some(&"no closing quote".to_string()));
+    }
+
+    #[test]
+    fn test_parse_repeated_tags_accumulate() {
+        let parsed = parse_args(&words("Buy milk tag:home ta:errand pri:L tag:home"));
+        assert_eq!(parsed.name, "Buy milk");
+        assert_eq!(parsed.tags, ["home", "errand", "home"]);
+        assert_eq!(parsed.metadata.get("tag"), None);
+        assert_eq!(parsed.metadata.get("pri"), Some(&"L".to_string()));
+
+        let parsed = parse_args(&words("Deploy dep:a1b2 dep:c3d4"));
+        assert_eq!(parsed.name, "Deploy");
+        assert_eq!(parsed.deps, ["a1b2", "c3d4"]);
+        assert!(parsed.metadata.is_empty());
+    }
+
+    #[test]
+    fn test_parse_empty_values_are_kept() {
+        let parsed = parse_args(&words(r#"due: project:"" pri:L"#));
+        assert_eq!(parsed.name, "");
+        assert_eq!(parsed.metadata.get("due"), Some(&String::new()));
+        assert_eq!(parsed.metadata.get("project"), Some(&String::new()));
+        assert_eq!(parsed.metadata.get("description"), None);
+    }
+
+    #[test]
+    fn test_parse_non_key_colons_stay_in_name() {
+        let args = vec!["Call".to_string(), "at".to_string(), "9:30".to_string(), ":bad".to_string(), " :x".to_string()];
+        let parsed = parse_args(&args);
+        assert_eq!(parsed.name, "Call at 9:30 :bad  :x");
+        assert!(parsed.metadata.is_empty());
+    }
+
+    #[test]
+    fn test_metadata_keys_expand_unambiguously() {
+        let names = metadata_key_names();
+        for key in METADATA_KEYS {
+            assert_eq!(expand_key(key.key, &names).unwrap(), key.key);
+            let (example_key, _) = key.example.split_once(':').unwrap();
+            assert_eq!(example_key, key.key);
+        }
+    }
+
     #[test]
     fn test_expand_key() {
         let candidates = vec!["due", "project", "priority"];
@@ -83,4 +290,18 @@ mod tests {
         // Unknown
         assert!(expand_key("x", &candidates).is_err());
     }
+
+    #[test]
+    fn test_full_key_only_keys_keep_older_abbreviations() {
+        let names = metadata_key_names();
+        assert_eq!(expand_key("de", &names).unwrap(), "description");
+        assert_eq!(expand_key("re", &names).unwrap(), "remind");
+        assert_eq!(expand_key("dep", &names).unwrap(), "dep");
+        assert_eq!(expand_key("recur", &names).unwrap(), "recur");
+        assert!(expand_key("rec", &names).is_err());
+
+        // Still ambiguous between the abbreviable keys
+        let err = expand_key("d", &names).unwrap_err().to_string();
+        assert!(err.contains("Ambiguous") && err.contains("due") && err.contains("description"));
+    }
 }
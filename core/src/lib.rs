@@ -1,17 +1,30 @@
+pub mod clock;
+pub mod config;
 pub mod model;
+pub mod paths;
 pub mod repository;
 pub mod input;
 pub mod time;
 pub mod service;
+pub mod state;
 pub mod usecase;
+#[cfg(test)]
+mod test_util;
 
 pub use model::task::{Task, Priority, TaskState};
-pub use repository::{TaskRepository, FileTaskRepository, FileDailyLogRepository};
-pub use input::{parse_args, expand_key, ParsedInput};
-pub use time::{parse_human_date, parse_duration};
-pub use service::task_service::{TaskService, SortStrategy, calculate_score, sort_tasks};
+pub use model::estimate::Estimate;
+pub use repository::{TaskRepository, FileTaskRepository, MemoryTaskRepository, FileDailyLogRepository};
+#[cfg(feature = "sqlite")]
+pub use repository::SqliteTaskRepository;
+pub use input::{parse_args, expand_key, metadata_key_names, ParsedInput, MetadataKey, METADATA_KEYS};
+pub use time::{parse_human_date, parse_human_date_with, parse_duration, start_of_day, format_duration};
+pub use service::task_service::{TaskService, SortStrategy, ScoringStrategy, calculate_score, calculate_urgency, sort_tasks, sort_tasks_by};
 pub use service::daily_log_service::DailyLogService;
 pub use service::dto::TaskDto;
+pub use service::backup_service::{Backup, BackupService};
+pub use service::resolve::{resolve_task_id, resolve_task_id_where, ResolveError};
+pub use config::{Config, ViewConfig, ListColumn};
+pub use clock::{Clock, SystemClock, FixedClock};
 
 pub fn greet() -> String {
     "Hello from Todoism Core!".to_string()
@@ -13,12 +13,15 @@ pub struct DailyLog {
     pub meetings: Vec<Meeting>,
 }
 
+/// The meeting entry the daily check-in records, covering all of the day's meetings.
+pub const ALL_MEETINGS: &str = "all";
+
 impl DailyLog {
     pub fn new(date: NaiveDate, hours: f64) -> Self {
         Self {
             date,
             meetings: vec![Meeting {
-                name: "all".to_string(),
+                name: ALL_MEETINGS.to_string(),
                 hours,
             }],
         }
@@ -0,0 +1,86 @@
+/// Hours in one estimated day; plain estimates are written in days.
+pub const HOURS_PER_DAY: f64 = 8.0;
+
+/// A parsed `estimate` string. A single value has equal bounds; a range such as
+/// `2-4h` keeps both. Values without a unit are days (`0.5` = 4h), `h` means hours
+/// and `d` days.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub low_hours: f64,
+    pub high_hours: f64,
+}
+
+impl Estimate {
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let (body, hours_per_unit) = if let Some(body) = input.strip_suffix(['h', 'H']) {
+            (body, 1.0)
+        } else if let Some(body) = input.strip_suffix(['d', 'D']) {
+            (body, HOURS_PER_DAY)
+        } else {
+            (input, HOURS_PER_DAY)
+        };
+
+        let (low, high) = match body.split_once('-') {
+            Some((low, high)) => (parse_bound(low)?, parse_bound(high)?),
+            None => {
+                let value = parse_bound(body)?;
+                (value, value)
+            }
+        };
+        if low > high {
+            return None;
+        }
+
+        Some(Self {
+            low_hours: low * hours_per_unit,
+            high_hours: high * hours_per_unit,
+        })
+    }
+
+    pub fn is_range(&self) -> bool {
+        self.low_hours != self.high_hours
+    }
+
+    /// What capacity and fit calculations plan with.
+    pub fn midpoint_hours(&self) -> f64 {
+        (self.low_hours + self.high_hours) / 2.0
+    }
+}
+
+fn parse_bound(s: &str) -> Option<f64> {
+    s.trim().parse::<f64>().ok().filter(|v| v.is_finite() && *v >= 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_values() {
+        assert_eq!(Estimate::parse("0.5"), Some(Estimate { low_hours: 4.0, high_hours: 4.0 }));
+        assert_eq!(Estimate::parse("1d").unwrap().midpoint_hours(), 8.0);
+        assert_eq!(Estimate::parse("3h").unwrap().midpoint_hours(), 3.0);
+        assert!(!Estimate::parse("3h").unwrap().is_range());
+    }
+
+    #[test]
+    fn test_parse_ranges() {
+        let est = Estimate::parse("2-4h").unwrap();
+        assert_eq!(est, Estimate { low_hours: 2.0, high_hours: 4.0 });
+        assert!(est.is_range());
+        assert_eq!(est.midpoint_hours(), 3.0);
+
+        // Unitless ranges are days
+        assert_eq!(Estimate::parse("0.5-1").unwrap().midpoint_hours(), 6.0);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid() {
+        assert_eq!(Estimate::parse(""), None);
+        assert_eq!(Estimate::parse("4-2h"), None);
+        assert_eq!(Estimate::parse("-2h"), None);
+        assert_eq!(Estimate::parse("two"), None);
+        assert_eq!(Estimate::parse("2-"), None);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod daily_log;
+pub mod estimate;
 pub mod task;
 pub mod stats;
 
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct DailyStats {
@@ -8,11 +9,24 @@ pub struct DailyStats {
     pub mtg: f64,
 }
 
+/// What one completed task contributed to a month, kept so it is counted once and can be reverted.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TaskCredit {
+    pub date: String, // "YYYY-MM-DD"; `est` and `act` count on this day
+    pub est: f64,
+    pub act: f64,
+    /// Tracked hours by the day they were worked ("YYYY-MM-DD"), on top of `act`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub logged: BTreeMap<String, f64>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MonthlyStats {
     pub year: i32,
     pub month: u32,
     pub days: HashMap<String, DailyStats>, // Key: "YYYY-MM-DD"
+    #[serde(default)]
+    pub credited: HashMap<Uuid, TaskCredit>, // Tasks already included in `days`
 }
 
 impl MonthlyStats {
@@ -21,6 +35,7 @@ impl MonthlyStats {
             year,
             month,
             days: HashMap::new(),
+            credited: HashMap::new(),
         }
     }
 
@@ -30,4 +45,58 @@ impl MonthlyStats {
         entry.act += act;
         entry.mtg += mtg;
     }
+
+    /// Adds a task's hours unless that task is already counted, which makes crediting
+    /// on completion and again at archive time safe. Returns whether anything was added.
+    pub fn credit(&mut self, id: Uuid, credit: TaskCredit) -> bool {
+        if self.credited.contains_key(&id) {
+            return false;
+        }
+        self.add(credit.date.clone(), credit.est, credit.act, 0.0);
+        for (date, hours) in &credit.logged {
+            self.add(date.clone(), 0.0, *hours, 0.0);
+        }
+        self.credited.insert(id, credit);
+        true
+    }
+
+    /// Takes a credited task's hours back out, e.g. when it is reopened.
+    pub fn uncredit(&mut self, id: &Uuid) -> bool {
+        let Some(credit) = self.credited.remove(id) else {
+            return false;
+        };
+        if let Some(entry) = self.days.get_mut(&credit.date) {
+            entry.est -= credit.est;
+            entry.act -= credit.act;
+        }
+        for (date, hours) in &credit.logged {
+            if let Some(entry) = self.days.get_mut(date) {
+                entry.act -= hours;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credit_counts_each_task_once() {
+        let mut stats = MonthlyStats::new(2025, 1);
+        let id = Uuid::new_v4();
+        let logged = BTreeMap::from([("2025-01-07".to_string(), 1.5)]);
+        let credit = TaskCredit { date: "2025-01-06".to_string(), est: 4.0, act: 6.0, logged };
+
+        assert!(stats.credit(id, credit.clone()));
+        assert!(!stats.credit(id, credit));
+        assert_eq!(stats.days["2025-01-06"].act, 6.0);
+        assert_eq!(stats.days["2025-01-07"].act, 1.5);
+
+        assert!(stats.uncredit(&id));
+        assert!(!stats.uncredit(&id));
+        assert_eq!(stats.days["2025-01-06"].act, 0.0);
+        assert_eq!(stats.days["2025-01-07"].act, 0.0);
+    }
 }
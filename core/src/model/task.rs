@@ -1,20 +1,19 @@
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use uuid::Uuid;
+use std::collections::HashSet;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+use crate::model::estimate::Estimate;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub enum Priority {
     Low,
+    #[default]
     Medium,
     High,
 }
 
-impl Default for Priority {
-    fn default() -> Self {
-        Priority::Medium
-    }
-}
-
 // Old Status enum is replaced by TaskState logic, 
 // but we might keep a simple enum for sorting/filtering if needed, 
 // or just rely on matching TaskState. 
@@ -36,7 +35,9 @@ pub enum TaskState {
         #[serde(default)]
         actual: Option<String>, 
     },
-    Deleted,
+    Deleted {
+        deleted_at: DateTime<Utc>,
+    },
 }
 
 impl Default for TaskState {
@@ -45,7 +46,8 @@ impl Default for TaskState {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(from = "TaskRecord")]
 pub struct Task {
     pub id: Uuid,
     pub name: String,
@@ -57,15 +59,101 @@ pub struct Task {
     pub description: Option<String>,
     pub project: Option<String>,
     pub estimate: Option<String>,
+    /// Link to a ticket or doc the task refers to
+    pub url: Option<String>,
+    /// How long before `due` the daemon reminds about the task, e.g. `30m` or `1d`
+    pub remind_before: Option<String>,
+    /// Free-form labels, in the order they were added; unlike `project` a task can have many
+    pub tags: Vec<String>,
+    /// Repeat interval such as `1d` or `1w`; completing the task adds the next instance
+    pub recurrence: Option<String>,
+    /// Tasks that must be finished first; the task is blocked while any of them is pending
+    pub depends_on: Vec<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
+/// On-disk shape of a `Task`. Older files stored `Deleted` as a unit variant without
+/// a timestamp; those get `created_at` as their `deleted_at`.
+#[derive(Deserialize)]
+struct TaskRecord {
+    id: Uuid,
+    name: String,
+    priority: Priority,
+    state: StoredState,
+    due: Option<DateTime<Utc>>,
+    description: Option<String>,
+    project: Option<String>,
+    estimate: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    remind_before: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    recurrence: Option<String>,
+    #[serde(default)]
+    depends_on: Vec<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredState {
+    Current(TaskState),
+    Legacy(LegacyState),
+}
+
+#[derive(Deserialize)]
+enum LegacyState {
+    Deleted,
+}
+
+impl From<TaskRecord> for Task {
+    fn from(record: TaskRecord) -> Self {
+        let state = match record.state {
+            StoredState::Current(state) => state,
+            StoredState::Legacy(LegacyState::Deleted) => TaskState::Deleted { deleted_at: record.created_at },
+        };
+        Self {
+            id: record.id,
+            name: record.name,
+            priority: record.priority,
+            state,
+            due: record.due,
+            description: record.description,
+            project: record.project,
+            estimate: record.estimate,
+            url: record.url,
+            remind_before: record.remind_before,
+            tags: record.tags,
+            recurrence: record.recurrence,
+            depends_on: record.depends_on,
+            created_at: record.created_at,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct TimeLog {
     pub start: DateTime<Utc>,
     pub end: Option<DateTime<Utc>>,
 }
 
+impl TimeLog {
+    /// Portion of this log inside [from, to). Open logs are treated as running until `now`.
+    pub fn overlap(&self, from: DateTime<Utc>, to: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+        let end = self.end.unwrap_or(now);
+        let clipped_start = self.start.max(from);
+        let clipped_end = end.min(to);
+        if clipped_end > clipped_start {
+            clipped_end - clipped_start
+        } else {
+            Duration::zero()
+        }
+    }
+}
+
 impl Task {
     pub fn new(name: String, due: Option<DateTime<Utc>>) -> Self {
         Self {
@@ -77,6 +165,11 @@ impl Task {
             description: None,
             project: None,
             estimate: None,
+            url: None,
+            remind_before: None,
+            tags: Vec::new(),
+            recurrence: None,
+            depends_on: Vec::new(),
             created_at: Utc::now(),
         }
     }
@@ -136,6 +229,31 @@ impl Task {
         };
     }
     
+    /// Completes with a manual effort, read like an estimate (`2h`, `1d`, bare days). If no time was tracked, the effort is also
+    /// recorded as a time log ending at completion, so history spreads it over the days it
+    /// covers instead of crediting it all to the completion day.
+    pub fn complete_with_logged_effort(&mut self, effort: String, at: DateTime<Utc>) -> Result<()> {
+        // Read like an estimate: `2h`, `1d` or bare days such as `0.5`
+        let hours = Estimate::parse(&effort).map(|est| est.midpoint_hours()).filter(|h| *h > 0.0);
+        let start = match hours {
+            Some(hours) => Some(
+                Duration::try_seconds((hours * 3600.0).round() as i64)
+                    .and_then(|effort| at.checked_sub_signed(effort))
+                    .ok_or_else(|| anyhow!("Effort '{}' is too large to log", effort))?,
+            ),
+            None => None,
+        };
+        self.complete(Some(effort));
+
+        if let TaskState::Completed { completed_at, time_logs, .. } = &mut self.state {
+            *completed_at = at;
+            if let (Some(start), true) = (start, time_logs.is_empty()) {
+                time_logs.push(TimeLog { start, end: Some(at) });
+            }
+        }
+        Ok(())
+    }
+
     // Helper to revert completion or un-delete (simplistic implementation)
     pub fn reopen(&mut self) {
          if !matches!(self.state, TaskState::Pending { .. }) {
@@ -148,7 +266,169 @@ impl Task {
     }
 
     pub fn delete(&mut self) {
-        self.state = TaskState::Deleted;
+        self.state = TaskState::Deleted { deleted_at: Utc::now() };
+    }
+
+    /// Brings a deleted task back as pending. Returns false if it wasn't deleted.
+    pub fn restore(&mut self) -> bool {
+        if !matches!(self.state, TaskState::Deleted { .. }) {
+            return false;
+        }
+        self.state = TaskState::default();
+        true
+    }
+
+    /// Whole days since the task was created, as of `now`.
+    pub fn age_days(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.created_at).num_days()
+    }
+
+    /// Still pending with its due date before `now`.
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.state, TaskState::Pending { .. }) && self.due.is_some_and(|due| due < now)
+    }
+
+    /// When the reminder for this task is due: `remind_before` ahead of `due`.
+    /// None without a due date or with an unparsable `remind_before`.
+    pub fn reminder_at(&self) -> Option<DateTime<Utc>> {
+        let before = crate::time::parse_duration(self.remind_before.as_deref()?).ok()?;
+        self.due?.checked_sub_signed(before)
+    }
+
+    /// Unsets the field behind a full metadata key (`modify` with `key:` and no value).
+    /// Priority goes back to the default. Returns false for keys that aren't task fields.
+    pub fn clear_field(&mut self, key: &str) -> bool {
+        match key {
+            "due" => self.due = None,
+            "project" => self.project = None,
+            "priority" => self.priority = Priority::default(),
+            "description" => self.description = None,
+            "estimate" => self.estimate = None,
+            "url" => self.url = None,
+            "remind" => self.remind_before = None,
+            "tag" => self.tags.clear(),
+            "recur" => self.recurrence = None,
+            "dep" => self.depends_on.clear(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// The instance that follows this one when it recurs: a fresh pending copy due one
+    /// interval after this one's due date (or after `now` without one), moved on by whole
+    /// intervals until it lies in the future. None when not recurring, unparsable, or
+    /// when the interval takes the due date past what a date can hold.
+    pub fn next_occurrence(&self, now: DateTime<Utc>) -> Option<Task> {
+        let interval = crate::time::parse_duration(self.recurrence.as_deref()?).ok()?;
+        let mut due = self.due.unwrap_or(now).checked_add_signed(interval)?;
+        while due <= now {
+            due = due.checked_add_signed(interval)?;
+        }
+
+        let mut next = Task::new(self.name.clone(), Some(due));
+        next.priority = self.priority.clone();
+        next.description = self.description.clone();
+        next.project = self.project.clone();
+        next.estimate = self.estimate.clone();
+        next.url = self.url.clone();
+        next.remind_before = self.remind_before.clone();
+        next.tags = self.tags.clone();
+        next.recurrence = self.recurrence.clone();
+        next.depends_on = self.depends_on.clone();
+        Some(next)
+    }
+
+    /// Adds a tag unless the task already has it (case-insensitively). Returns whether it was added.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+        if tag.is_empty() || self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            return false;
+        }
+        self.tags.push(tag.to_string());
+        true
+    }
+
+    /// Makes this task depend on `id` unless it already does. Returns whether it was added.
+    pub fn add_dependency(&mut self, id: Uuid) -> bool {
+        if id == self.id || self.depends_on.contains(&id) {
+            return false;
+        }
+        self.depends_on.push(id);
+        true
+    }
+
+    /// Whether a dependency is among `pending` (the IDs of the pending tasks).
+    pub fn is_blocked(&self, pending: &HashSet<Uuid>) -> bool {
+        self.depends_on.iter().any(|id| pending.contains(id))
+    }
+
+    pub fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        match self.state {
+            TaskState::Deleted { deleted_at } => Some(deleted_at),
+            _ => None,
+        }
+    }
+
+    pub fn time_logs(&self) -> &[TimeLog] {
+        match &self.state {
+            TaskState::Pending { time_logs } => time_logs,
+            TaskState::Completed { time_logs, .. } => time_logs,
+            TaskState::Deleted { .. } => &[],
+        }
+    }
+
+    /// Sorts logs by start and merges overlapping sessions into one spanning log so
+    /// no time is counted twice. Touching or separate sessions stay separate.
+    /// An open log runs until stopped, so it absorbs every later log; on a completed
+    /// task it is closed at `completed_at`. Returns whether anything changed.
+    pub fn normalize_time_logs(&mut self) -> bool {
+        let (time_logs, closed_at) = match &mut self.state {
+            TaskState::Pending { time_logs } => (time_logs, None),
+            TaskState::Completed { time_logs, completed_at, .. } => (time_logs, Some(*completed_at)),
+            TaskState::Deleted { .. } => return false,
+        };
+
+        let mut sorted = time_logs.clone();
+        sorted.sort_by_key(|log| log.start);
+
+        let mut merged: Vec<TimeLog> = Vec::with_capacity(sorted.len());
+        for log in sorted {
+            if let Some(last) = merged.last_mut() {
+                match last.end {
+                    None => continue,
+                    Some(last_end) if log.start < last_end => {
+                        last.end = log.end.map(|end| end.max(last_end));
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+            merged.push(log);
+        }
+
+        if let (Some(completed_at), Some(last)) = (closed_at, merged.last_mut()) {
+            if last.end.is_none() {
+                last.end = Some(completed_at.max(last.start));
+            }
+        }
+
+        let changed = merged != *time_logs;
+        *time_logs = merged;
+        changed
+    }
+
+    /// When the task was last worked on: the latest log end, or `now` while tracking.
+    pub fn last_activity(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.time_logs().iter()
+            .map(|log| log.end.unwrap_or(now))
+            .max()
+    }
+
+    // Only tracked logs count here; a manual `actual` has no position in time.
+    pub fn time_spent_between(&self, from: DateTime<Utc>, to: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+        self.time_logs().iter()
+            .map(|log| log.overlap(from, to, now))
+            .fold(Duration::zero(), |acc, d| acc + d)
     }
 }
 
@@ -157,6 +437,123 @@ impl Task {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_next_occurrence_lands_in_the_future() {
+        let now = Utc::now();
+        let mut task = Task::new("Water plants".to_string(), Some(now - Duration::days(3) - Duration::hours(1)));
+        assert!(task.next_occurrence(now).is_none());
+
+        task.recurrence = Some("1d".to_string());
+        let next = task.next_occurrence(now).unwrap();
+        assert_eq!(next.due, Some(now + Duration::days(1) - Duration::hours(1)));
+        assert_ne!(next.id, task.id);
+        assert_eq!(next.recurrence.as_deref(), Some("1d"));
+
+        // Without a due date the interval counts from now
+        task.due = None;
+        assert_eq!(task.next_occurrence(now).unwrap().due, Some(now + Duration::days(1)));
+
+        // An interval that runs past the last representable date doesn't recur
+        task.recurrence = Some("99999999w".to_string());
+        assert!(task.next_occurrence(now).is_none());
+    }
+
+    #[test]
+    fn test_clear_field() {
+        let mut task = Task::new("Report".to_string(), Some(Utc::now()));
+        task.project = Some("Work".to_string());
+        task.priority = Priority::High;
+
+        assert!(task.clear_field("due"));
+        assert!(task.clear_field("project"));
+        assert!(task.clear_field("priority"));
+        assert_eq!(task.due, None);
+        assert_eq!(task.project, None);
+        assert_eq!(task.priority, Priority::default());
+        assert!(!task.clear_field("name"));
+
+        assert!(task.add_tag("home"));
+        assert!(!task.add_tag("Home"));
+        assert!(task.add_tag("errand"));
+        assert_eq!(task.tags, ["home", "errand"]);
+        assert!(task.clear_field("tag"));
+        assert!(task.tags.is_empty());
+    }
+
+    #[test]
+    fn test_age_days() {
+        let now = Utc::now();
+        let mut task = Task::new("Old".to_string(), None);
+        task.created_at = now - Duration::days(5);
+        assert_eq!(task.age_days(now), 5);
+
+        task.created_at = now - Duration::days(5) + Duration::seconds(1);
+        assert_eq!(task.age_days(now), 4);
+
+        task.created_at = now;
+        assert_eq!(task.age_days(now), 0);
+    }
+
+    #[test]
+    fn test_load_legacy_deleted_state() {
+        let json = r#"{
+            "id": "6f1c2b1e-8a4e-4c47-9d3a-2f0e5b7c9a11",
+            "name": "Old",
+            "priority": "Low",
+            "state": "Deleted",
+            "due": null,
+            "description": null,
+            "project": null,
+            "estimate": null,
+            "created_at": "2024-03-01T09:00:00Z"
+        }"#;
+        let task: Task = serde_json::from_str(json).unwrap();
+        let created_at = "2024-03-01T09:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(task.state, TaskState::Deleted { deleted_at: created_at });
+        assert_eq!(task.url, None);
+        assert!(task.tags.is_empty());
+
+        // Re-saving writes the new shape, which loads back unchanged
+        let reloaded: Task = serde_json::from_str(&serde_json::to_string(&task).unwrap()).unwrap();
+        assert_eq!(reloaded.deleted_at(), Some(created_at));
+    }
+
+    #[test]
+    fn test_complete_with_logged_effort() {
+        let done_at = at(18);
+        let mut task = Task::new("Effort".to_string(), None);
+        task.complete_with_logged_effort("0.5".to_string(), done_at).unwrap();
+        assert_eq!(task.time_logs(), &[closed(14, 18)]);
+
+        // Units are read like an estimate: `2h` is two hours, not two days
+        let mut hours = Task::new("Hours".to_string(), None);
+        hours.complete_with_logged_effort("2h".to_string(), done_at).unwrap();
+        assert_eq!(hours.time_logs(), &[closed(16, 18)]);
+
+        // Tracked time wins; the effort is only kept as the manual actual
+        let mut tracked = Task::new("Tracked".to_string(), None);
+        tracked.state = TaskState::Pending { time_logs: vec![closed(9, 10)] };
+        tracked.complete_with_logged_effort("0.5".to_string(), done_at).unwrap();
+        assert_eq!(tracked.time_logs(), &[closed(9, 10)]);
+
+        // An effort reaching back past the earliest date is refused and the task stays pending
+        let mut huge = Task::new("Huge".to_string(), None);
+        assert!(huge.complete_with_logged_effort("1e9".to_string(), done_at).is_err());
+        assert!(matches!(huge.state, TaskState::Pending { .. }));
+    }
+
+    #[test]
+    fn test_delete_records_time() {
+        let mut task = Task::new("Gone".to_string(), None);
+        let before = Utc::now();
+        task.delete();
+        assert!(task.deleted_at().is_some_and(|at| at >= before));
+
+        assert!(task.restore());
+        assert_eq!(task.state, TaskState::default());
+        assert!(!task.restore());
+    }
+
     #[test]
     fn test_task_tracking_lifecycle() {
         let mut task = Task::new("Test Task".to_string(), None);
@@ -197,4 +594,112 @@ mod tests {
             panic!("Task should be Completed");
         }
     }
+
+    #[test]
+    fn test_time_spent_between_clips_at_boundaries() {
+        let from = DateTime::parse_from_rfc3339("2025-01-02T00:00:00Z").unwrap().with_timezone(&Utc);
+        let to = from + Duration::days(1);
+        let now = to + Duration::days(1);
+
+        let mut task = Task::new("Billing".to_string(), None);
+        task.state = TaskState::Pending {
+            time_logs: vec![
+                // Crosses the start boundary: only 1h counts
+                TimeLog { start: from - Duration::hours(1), end: Some(from + Duration::hours(1)) },
+                // Entirely inside: 2h
+                TimeLog { start: from + Duration::hours(10), end: Some(from + Duration::hours(12)) },
+                // Entirely outside
+                TimeLog { start: to + Duration::hours(1), end: Some(to + Duration::hours(2)) },
+                // Open log started 2h before the end: clipped at `to`
+                TimeLog { start: to - Duration::hours(2), end: None },
+            ],
+        };
+
+        assert_eq!(task.time_spent_between(from, to, now), Duration::hours(5));
+    }
+
+    #[test]
+    fn test_open_log_clipped_at_now() {
+        let from = DateTime::parse_from_rfc3339("2025-01-02T00:00:00Z").unwrap().with_timezone(&Utc);
+        let to = from + Duration::days(1);
+        let now = from + Duration::hours(3);
+
+        let log = TimeLog { start: from + Duration::hours(1), end: None };
+        assert_eq!(log.overlap(from, to, now), Duration::hours(2));
+    }
+
+    fn at(hour: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-01-02T00:00:00Z").unwrap().with_timezone(&Utc) + Duration::hours(hour)
+    }
+
+    fn closed(start: i64, end: i64) -> TimeLog {
+        TimeLog { start: at(start), end: Some(at(end)) }
+    }
+
+    fn total(task: &Task) -> Duration {
+        task.time_spent_between(at(0), at(48), at(48))
+    }
+
+    #[test]
+    fn test_normalize_merges_overlapping_logs() {
+        let mut task = Task::new("Overlap".to_string(), None);
+        task.state = TaskState::Pending {
+            // 9-12 and 10-11 (nested) and 11-13 overlap; 15-16 is a separate session
+            time_logs: vec![closed(15, 16), closed(10, 11), closed(9, 12), closed(11, 13)],
+        };
+        assert_eq!(total(&task), Duration::hours(7)); // double-counted
+
+        assert!(task.normalize_time_logs());
+        assert_eq!(task.time_logs(), &[closed(9, 13), closed(15, 16)]);
+        assert_eq!(total(&task), Duration::hours(5));
+        assert!(!task.normalize_time_logs());
+    }
+
+    #[test]
+    fn test_normalize_keeps_adjacent_logs() {
+        let mut task = Task::new("Adjacent".to_string(), None);
+        task.state = TaskState::Pending {
+            time_logs: vec![closed(10, 11), closed(9, 10)],
+        };
+
+        task.normalize_time_logs();
+        assert_eq!(task.time_logs(), &[closed(9, 10), closed(10, 11)]);
+        assert_eq!(total(&task), Duration::hours(2));
+    }
+
+    #[test]
+    fn test_normalize_open_logs() {
+        // A stray open log swallows the later session and stays the single trailing open log
+        let mut task = Task::new("Open".to_string(), None);
+        task.state = TaskState::Pending {
+            time_logs: vec![TimeLog { start: at(9), end: None }, closed(10, 11)],
+        };
+        task.normalize_time_logs();
+        assert_eq!(task.time_logs(), &[TimeLog { start: at(9), end: None }]);
+        assert!(task.is_tracking());
+
+        // On a completed task the open log is closed at completion
+        task.state = TaskState::Completed {
+            completed_at: at(12),
+            time_logs: vec![closed(8, 9), TimeLog { start: at(10), end: None }],
+            actual: None,
+        };
+        task.normalize_time_logs();
+        assert_eq!(task.time_logs(), &[closed(8, 9), closed(10, 12)]);
+    }
+
+    #[test]
+    fn test_last_activity() {
+        let mut task = Task::new("Idle".to_string(), None);
+        assert_eq!(task.last_activity(at(20)), None);
+
+        task.state = TaskState::Pending {
+            time_logs: vec![closed(9, 10), closed(12, 13), closed(10, 11)],
+        };
+        assert_eq!(task.last_activity(at(20)), Some(at(13)));
+
+        task.start_tracking();
+        let now = Utc::now();
+        assert_eq!(task.last_activity(now), Some(now));
+    }
 }
@@ -0,0 +1,101 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+
+pub const PROFILE_ENV: &str = "TODOISM_PROFILE";
+pub const DEFAULT_PROFILE: &str = "default";
+const PROFILES_DIR_NAME: &str = "profiles";
+
+static SELECTED_PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Pins the profile for the rest of the process (e.g. from `--profile`).
+/// Takes precedence over `TODOISM_PROFILE`.
+pub fn select_profile(name: &str) -> Result<()> {
+    validate_profile_name(name)?;
+    SELECTED_PROFILE
+        .set(name.to_string())
+        .map_err(|_| anyhow!("Profile already selected"))
+}
+
+pub fn active_profile() -> String {
+    SELECTED_PROFILE.get().cloned()
+        .or_else(|| env::var(PROFILE_ENV).ok().filter(|p| !p.trim().is_empty()))
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Root of all todoism data (`~/.todoism`), independent of the profile.
+pub fn root_dir() -> Result<PathBuf> {
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| anyhow!("Could not determine home directory"))?;
+    Ok(home_dir.join(".todoism"))
+}
+
+/// Data directory of the active profile. Every repository resolves its default location here.
+pub fn data_dir() -> Result<PathBuf> {
+    let profile = active_profile();
+    validate_profile_name(&profile)?;
+    Ok(profile_dir(&root_dir()?, &profile))
+}
+
+pub fn list_profiles() -> Result<Vec<String>> {
+    let mut profiles = vec![DEFAULT_PROFILE.to_string()];
+    let dir = root_dir()?.join(PROFILES_DIR_NAME);
+    if dir.exists() {
+        let mut named = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                if let Some(name) = entry.file_name().to_str() {
+                    named.push(name.to_string());
+                }
+            }
+        }
+        named.sort();
+        profiles.extend(named);
+    }
+    Ok(profiles)
+}
+
+// The default profile keeps using the root directly for backward compatibility.
+fn profile_dir(root: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        root.to_path_buf()
+    } else {
+        root.join(PROFILES_DIR_NAME).join(profile)
+    }
+}
+
+fn validate_profile_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\']);
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid profile name: '{}'", name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_dir() {
+        let root = Path::new("/home/u/.todoism");
+        assert_eq!(profile_dir(root, DEFAULT_PROFILE), PathBuf::from("/home/u/.todoism"));
+        assert_eq!(profile_dir(root, "work"), PathBuf::from("/home/u/.todoism/profiles/work"));
+    }
+
+    #[test]
+    fn test_validate_profile_name() {
+        assert!(validate_profile_name("work").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("..").is_err());
+        assert!(validate_profile_name("a/b").is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+/// Writes `value` as pretty JSON to `path` without ever leaving it half-written:
+/// the JSON goes to a temp file next to it, which is renamed over `path` only once
+/// it is complete. If serializing fails (or panics) the original file is untouched.
+pub(crate) fn write_json_atomic<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let temp = TempFile(temp_path(path)?);
+
+    let mut writer = BufWriter::new(File::create(&temp.0)?);
+    serde_json::to_writer_pretty(&mut writer, value)?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    drop(writer);
+
+    fs::rename(&temp.0, path)?;
+    std::mem::forget(temp);
+    Ok(())
+}
+
+// Same directory as the target so the rename never crosses filesystems
+fn temp_path(path: &Path) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow!("Invalid file path: {}", path.display()))?;
+    Ok(path.with_file_name(format!(".{}.{}.tmp", name, std::process::id())))
+}
+
+/// Removes the temp file when the write is abandoned, including by unwinding.
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serializer;
+    use crate::test_util::TempDir;
+
+    struct Exploding;
+
+    impl Serialize for Exploding {
+        fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            panic!("serialization blew up");
+        }
+    }
+
+    struct Failing;
+
+    impl Serialize for Failing {
+        fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("cannot serialize"))
+        }
+    }
+
+    #[test]
+    fn test_failed_write_keeps_original_file() {
+        let dir = TempDir::new();
+        let path = dir.join("tasks.json");
+        write_json_atomic(&path, &vec!["keep", "me"]).unwrap();
+        let original = fs::read_to_string(&path).unwrap();
+
+        let panicked = std::panic::catch_unwind(|| write_json_atomic(&path, &vec![Exploding]));
+        assert!(panicked.is_err());
+        assert!(write_json_atomic(&path, &Failing).is_err());
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+        // No temp files are left behind either
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    }
+}
@@ -1,10 +1,12 @@
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, Write};
-use std::path::PathBuf;
-use anyhow::{anyhow, Result};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
 use chrono::NaiveDate;
 use serde_json;
 use crate::model::daily_log::DailyLog;
+use crate::paths;
+use crate::repository::atomic::write_json_atomic;
 
 const DAILY_LOG_FILE_NAME: &str = "daily_logs.json";
 
@@ -15,7 +17,10 @@ const DAILY_LOG_FILE_NAME: &str = "daily_logs.json";
 
 pub trait DailyLogRepository {
     fn get(&self, date: NaiveDate) -> Result<Option<DailyLog>>;
+    fn list(&self) -> Result<Vec<DailyLog>>;
     fn upsert(&self, log: DailyLog) -> Result<()>;
+    /// Removes the log for `date`, returning whether one existed.
+    fn delete(&self, date: NaiveDate) -> Result<bool>;
 }
 
 pub struct FileDailyLogRepository {
@@ -26,25 +31,23 @@ impl FileDailyLogRepository {
     pub fn new(base_dir: Option<PathBuf>) -> Result<Self> {
         let mut path = match base_dir {
             Some(dir) => dir,
-            None => {
-                let home_dir = dirs::home_dir()
-                    .ok_or_else(|| anyhow!("Could not determine home directory"))?;
-                home_dir.join(".todoism")
-            }
+            None => paths::data_dir()?,
         };
         fs::create_dir_all(&path)?;
         path.push(DAILY_LOG_FILE_NAME);
 
         if !path.exists() {
-            File::create(&path)?;
-            let mut writer = BufWriter::new(File::create(&path)?);
-            serde_json::to_writer_pretty(&mut writer, &Vec::<DailyLog>::new())?;
-            writer.flush()?;
+            write_json_atomic(&path, &Vec::<DailyLog>::new())?;
         }
 
         Ok(FileDailyLogRepository { file_path: path })
     }
 
+    /// The JSON file backing this repository.
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
+    }
+
     fn read_logs(&self) -> Result<Vec<DailyLog>> {
         let file = File::open(&self.file_path)?;
         let reader = BufReader::new(file);
@@ -53,11 +56,7 @@ impl FileDailyLogRepository {
     }
 
     fn write_logs(&self, logs: &[DailyLog]) -> Result<()> {
-        let file = File::create(&self.file_path)?;
-        let mut writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(&mut writer, logs)?;
-        writer.flush()?;
-        Ok(())
+        write_json_atomic(&self.file_path, logs)
     }
 }
 
@@ -67,6 +66,10 @@ impl DailyLogRepository for FileDailyLogRepository {
         Ok(logs.into_iter().find(|l| l.date == date))
     }
 
+    fn list(&self) -> Result<Vec<DailyLog>> {
+        self.read_logs()
+    }
+
     fn upsert(&self, log: DailyLog) -> Result<()> {
         let mut logs = self.read_logs()?;
         if let Some(pos) = logs.iter().position(|l| l.date == log.date) {
@@ -77,4 +80,35 @@ impl DailyLogRepository for FileDailyLogRepository {
         self.write_logs(&logs)?;
         Ok(())
     }
+
+    fn delete(&self, date: NaiveDate) -> Result<bool> {
+        let mut logs = self.read_logs()?;
+        let initial_len = logs.len();
+        logs.retain(|l| l.date != date);
+
+        if logs.len() == initial_len {
+            return Ok(false);
+        }
+
+        self.write_logs(&logs)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_delete_reports_whether_log_existed() {
+        let dir = TempDir::new();
+        let repo = FileDailyLogRepository::new(Some(dir.to_path_buf())).unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+
+        repo.upsert(DailyLog::new(date, 2.0)).unwrap();
+        assert!(repo.delete(date).unwrap());
+        assert!(repo.get(date).unwrap().is_none());
+        assert!(!repo.delete(date).unwrap());
+    }
 }
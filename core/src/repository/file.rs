@@ -1,69 +1,85 @@
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, Write};
-use std::path::PathBuf;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Result};
 use serde_json;
 use uuid::Uuid;
 
 use crate::model::task::Task;
+use crate::paths;
+use crate::repository::atomic::write_json_atomic;
+use crate::repository::lock::{FileLock, LOCK_TIMEOUT};
 use crate::repository::traits::TaskRepository;
 
 const DEFAULT_FILE_NAME: &str = "tasks.json";
+const LOCK_FILE_NAME: &str = "tasks.lock";
 
+/// Tasks in one JSON file. Every read-modify-write holds `tasks.lock`, so a TUI and a
+/// CLI command running at the same time don't overwrite each other's changes; a writer
+/// that can't get the lock within `LOCK_TIMEOUT` fails instead of writing.
 #[derive(Clone)]
 pub struct FileTaskRepository {
     file_path: PathBuf,
+    lock_path: PathBuf,
 }
 
 impl FileTaskRepository {
     pub fn new(base_dir: Option<PathBuf>) -> Result<Self> {
         let mut path = match base_dir {
             Some(dir) => dir,
-            None => {
-                // Determine the default data directory (e.g., ~/.config/todoism or ~/.todoism)
-                // For now, let's use a simple .todoism in the home directory
-                let home_dir = dirs::home_dir()
-                    .ok_or_else(|| anyhow!("Could not determine home directory"))?;
-                home_dir.join(".todoism")
-            }
+            None => paths::data_dir()?,
         };
         fs::create_dir_all(&path)?; // Ensure the directory exists
         path.push(DEFAULT_FILE_NAME);
 
         // Ensure the file itself exists; create if it doesn't
         if !path.exists() {
-            File::create(&path)?;
             // Write an empty JSON array to initialize it
-            let mut writer = BufWriter::new(File::create(&path)?);
-            serde_json::to_writer_pretty(&mut writer, &Vec::<Task>::new())?;
-            writer.flush()?;
+            write_json_atomic(&path, &Vec::<Task>::new())?;
         }
 
-        Ok(FileTaskRepository { file_path: path })
+        let lock_path = path.with_file_name(LOCK_FILE_NAME);
+        Ok(FileTaskRepository { file_path: path, lock_path })
+    }
+
+    /// The JSON file backing this repository.
+    pub fn file_path(&self) -> &Path {
+        &self.file_path
     }
 
     fn read_tasks(&self) -> Result<Vec<Task>> {
         let file = File::open(&self.file_path)?;
         let reader = BufReader::new(file);
-        let tasks = serde_json::from_reader(reader)?;
+        let mut tasks: Vec<Task> = serde_json::from_reader(reader)?;
+        // Repair overlapping sessions on load; the fix is persisted by the next write.
+        for task in &mut tasks {
+            task.normalize_time_logs();
+        }
         Ok(tasks)
     }
 
     fn write_tasks(&self, tasks: &[Task]) -> Result<()> {
-        let file = File::create(&self.file_path)?;
-        let mut writer = BufWriter::new(file);
-        serde_json::to_writer_pretty(&mut writer, tasks)?;
-        writer.flush()?;
-        Ok(())
+        write_json_atomic(&self.file_path, tasks)
+    }
+
+    /// Reads the tasks under the lock, lets `change` edit them, and writes them back
+    /// before releasing it.
+    fn modify_tasks<R>(&self, change: impl FnOnce(&mut Vec<Task>) -> Result<R>) -> Result<R> {
+        let _lock = FileLock::acquire(&self.lock_path, LOCK_TIMEOUT)?;
+        let mut tasks = self.read_tasks()?;
+        let result = change(&mut tasks)?;
+        self.write_tasks(&tasks)?;
+        Ok(result)
     }
 }
 
 impl TaskRepository for FileTaskRepository {
     fn create(&self, task: Task) -> Result<Task> {
-        let mut tasks = self.read_tasks()?;
-        tasks.push(task.clone());
-        self.write_tasks(&tasks)?;
+        self.modify_tasks(|tasks| {
+            tasks.push(task.clone());
+            Ok(())
+        })?;
         Ok(task)
     }
 
@@ -79,26 +95,78 @@ impl TaskRepository for FileTaskRepository {
     }
 
     fn update(&self, task: &Task) -> Result<()> {
-        let mut tasks = self.read_tasks()?;
-        if let Some(pos) = tasks.iter().position(|t| t.id == task.id) {
-            tasks[pos] = task.clone();
-            self.write_tasks(&tasks)?;
+        self.modify_tasks(|tasks| {
+            let slot = tasks.iter_mut()
+                .find(|t| t.id == task.id)
+                .ok_or_else(|| anyhow!("Task with ID {} not found", task.id))?;
+            *slot = task.clone();
             Ok(())
-        } else {
-            Err(anyhow!("Task with ID {} not found", task.id))
-        }
+        })
+    }
+
+    fn update_many(&self, updated: &[Task]) -> Result<()> {
+        self.modify_tasks(|tasks| {
+            for task in updated {
+                let slot = tasks.iter_mut()
+                    .find(|t| t.id == task.id)
+                    .ok_or_else(|| anyhow!("Task with ID {} not found", task.id))?;
+                *slot = task.clone();
+            }
+            Ok(())
+        })
+    }
+
+    fn replace_all(&self, tasks: Vec<Task>) -> Result<()> {
+        let _lock = FileLock::acquire(&self.lock_path, LOCK_TIMEOUT)?;
+        self.write_tasks(&tasks)
     }
 
     fn delete(&self, id: &Uuid) -> Result<()> {
-        let mut tasks = self.read_tasks()?;
-        let initial_len = tasks.len();
-        tasks.retain(|t| t.id != *id);
-        
-        if tasks.len() == initial_len {
-            return Err(anyhow!("Task with ID {} not found", id));
+        self.modify_tasks(|tasks| {
+            let initial_len = tasks.len();
+            tasks.retain(|t| t.id != *id);
+            if tasks.len() == initial_len {
+                return Err(anyhow!("Task with ID {} not found", id));
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_concurrent_writers_lose_no_tasks() {
+        let dir = TempDir::new();
+        let repo = FileTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+
+        // Separate repositories stand in for separate processes
+        let writers: Vec<_> = (0..4).map(|w| {
+            let repo = FileTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+            std::thread::spawn(move || {
+                for i in 0..10 {
+                    repo.create(Task::new(format!("Task {}-{}", w, i), None)).unwrap();
+                }
+            })
+        }).collect();
+        for writer in writers {
+            writer.join().unwrap();
         }
 
-        self.write_tasks(&tasks)?;
-        Ok(())
+        assert_eq!(repo.list().unwrap().len(), 40);
+    }
+
+    #[test]
+    fn test_write_fails_while_lock_is_held() {
+        let dir = TempDir::new();
+        let repo = FileTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+
+        let held = FileLock::acquire(&repo.lock_path, LOCK_TIMEOUT).unwrap();
+        assert!(FileLock::acquire(&repo.lock_path, std::time::Duration::from_millis(50)).is_err());
+        drop(held);
+        repo.create(Task::new("After".to_string(), None)).unwrap();
     }
 }
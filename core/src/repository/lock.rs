@@ -0,0 +1,43 @@
+use std::fs::{File, OpenOptions, TryLockError};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+
+/// How long a writer waits for another todoism process before giving up.
+pub(crate) const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+const RETRY_INTERVAL: Duration = Duration::from_millis(20);
+
+/// An exclusive advisory lock on a lock file, released when dropped.
+/// Only todoism itself honours it; other programs can still write the data files.
+pub(crate) struct FileLock {
+    _file: File,
+}
+
+impl FileLock {
+    /// Waits up to `timeout` for the lock. When another process keeps holding it the
+    /// write is refused with an error rather than risking a lost update.
+    pub(crate) fn acquire(path: &Path, timeout: Duration) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+        let started = Instant::now();
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Self { _file: file }),
+                Err(TryLockError::WouldBlock) if started.elapsed() < timeout => thread::sleep(RETRY_INTERVAL),
+                Err(TryLockError::WouldBlock) => {
+                    return Err(anyhow!(
+                        "Another todoism process is holding {} (waited {}s); try again once it has finished",
+                        path.display(),
+                        timeout.as_secs()
+                    ))
+                }
+                Err(TryLockError::Error(e)) => return Err(e.into()),
+            }
+        }
+    }
+}
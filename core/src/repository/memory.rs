@@ -0,0 +1,101 @@
+use std::sync::RwLock;
+
+use anyhow::{anyhow, Result};
+use uuid::Uuid;
+
+use crate::model::task::Task;
+use crate::repository::traits::TaskRepository;
+
+/// Keeps tasks in memory only, for embedding the core without a data directory and for tests.
+#[derive(Default)]
+pub struct MemoryTaskRepository {
+    tasks: RwLock<Vec<Task>>,
+}
+
+impl MemoryTaskRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_tasks(tasks: Vec<Task>) -> Self {
+        Self { tasks: RwLock::new(tasks) }
+    }
+}
+
+impl TaskRepository for MemoryTaskRepository {
+    fn create(&self, task: Task) -> Result<Task> {
+        let mut tasks = self.tasks.write().map_err(|_| anyhow!("Task store lock poisoned"))?;
+        tasks.push(task.clone());
+        Ok(task)
+    }
+
+    fn get(&self, id: &Uuid) -> Result<Task> {
+        let tasks = self.tasks.read().map_err(|_| anyhow!("Task store lock poisoned"))?;
+        tasks.iter()
+            .find(|t| t.id == *id)
+            .cloned()
+            .ok_or_else(|| anyhow!("Task with ID {} not found", id))
+    }
+
+    fn list(&self) -> Result<Vec<Task>> {
+        let tasks = self.tasks.read().map_err(|_| anyhow!("Task store lock poisoned"))?;
+        Ok(tasks.clone())
+    }
+
+    fn update(&self, task: &Task) -> Result<()> {
+        let mut tasks = self.tasks.write().map_err(|_| anyhow!("Task store lock poisoned"))?;
+        match tasks.iter_mut().find(|t| t.id == task.id) {
+            Some(existing) => {
+                *existing = task.clone();
+                Ok(())
+            },
+            None => Err(anyhow!("Task with ID {} not found", task.id)),
+        }
+    }
+
+    fn replace_all(&self, new_tasks: Vec<Task>) -> Result<()> {
+        let mut tasks = self.tasks.write().map_err(|_| anyhow!("Task store lock poisoned"))?;
+        *tasks = new_tasks;
+        Ok(())
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<()> {
+        let mut tasks = self.tasks.write().map_err(|_| anyhow!("Task store lock poisoned"))?;
+        let initial_len = tasks.len();
+        tasks.retain(|t| t.id != *id);
+
+        if tasks.len() == initial_len {
+            return Err(anyhow!("Task with ID {} not found", id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crud_round_trip() {
+        let repo = MemoryTaskRepository::new();
+        let task = repo.create(Task::new("Write docs".to_string(), None)).unwrap();
+        assert_eq!(repo.list().unwrap().len(), 1);
+
+        let mut fetched = repo.get(&task.id).unwrap();
+        fetched.name = "Write better docs".to_string();
+        repo.update(&fetched).unwrap();
+        assert_eq!(repo.get(&task.id).unwrap().name, "Write better docs");
+
+        repo.delete(&task.id).unwrap();
+        assert!(repo.list().unwrap().is_empty());
+        assert!(repo.get(&task.id).is_err());
+    }
+
+    #[test]
+    fn test_missing_task_errors() {
+        let repo = MemoryTaskRepository::new();
+        let task = Task::new("Ghost".to_string(), None);
+        assert!(repo.update(&task).is_err());
+        assert!(repo.delete(&task.id).is_err());
+    }
+}
@@ -1,11 +1,19 @@
+pub(crate) mod atomic;
+mod lock;
 pub mod daily_log;
 pub mod file;
+pub mod memory;
 pub mod mod_stats; // Renamed to avoid collision if needed, or just stats.rs
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod traits;
 
 // Re-export
 pub use daily_log::FileDailyLogRepository;
 pub use file::FileTaskRepository;
+pub use memory::MemoryTaskRepository;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteTaskRepository;
 pub use traits::TaskRepository;
 pub use daily_log::DailyLogRepository;
 pub use mod_stats::FileStatsRepository;
\ No newline at end of file
@@ -1,7 +1,9 @@
 use crate::model::stats::MonthlyStats;
+use crate::paths;
+use crate::repository::atomic::write_json_atomic;
 use anyhow::Result;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 #[derive(Clone)]
 pub struct FileStatsRepository {
@@ -12,12 +14,7 @@ impl FileStatsRepository {
     pub fn new(base_dir: Option<PathBuf>) -> Result<Self> {
         let path = match base_dir {
             Some(p) => p,
-            None => {
-                let mut p = dirs::home_dir().expect("Could not find home directory");
-                p.push(".todoism");
-                p.push("stats");
-                p
-            }
+            None => paths::data_dir()?.join("stats"),
         };
         fs::create_dir_all(&path)?;
         Ok(Self { base_dir: path })
@@ -39,9 +36,7 @@ impl FileStatsRepository {
     pub fn save_stats(&self, stats: &MonthlyStats) -> Result<()> {
         let filename = format!("stats_{:04}_{:02}.json", stats.year, stats.month);
         let path = self.base_dir.join(filename);
-        let content = serde_json::to_string_pretty(stats)?;
-        fs::write(path, content)?;
-        Ok(())
+        write_json_atomic(&path, stats)
     }
     
     pub fn list_stats(&self) -> Result<Vec<MonthlyStats>> {
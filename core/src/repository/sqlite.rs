@@ -0,0 +1,310 @@
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use uuid::Uuid;
+
+use crate::model::task::{Priority, Task, TaskState};
+use crate::paths;
+use crate::repository::traits::TaskRepository;
+
+const DB_FILE_NAME: &str = "tasks.db";
+const JSON_FILE_NAME: &str = "tasks.json";
+const SCHEMA_VERSION: i32 = 2;
+
+const COLUMNS: &str = "id, name, priority, status, state, due, description, project, estimate, url, \
+                       remind_before, tags, recurrence, created_at, depends_on";
+
+/// Tasks in a SQLite database (`tasks.db` in the data dir), so single-task operations
+/// don't rewrite every task. The state enum is kept as a JSON column; status and due
+/// are plain indexed columns. The first open imports an existing `tasks.json`.
+pub struct SqliteTaskRepository {
+    conn: Connection,
+    db_path: PathBuf,
+}
+
+impl SqliteTaskRepository {
+    pub fn new(base_dir: Option<PathBuf>) -> Result<Self> {
+        let dir = match base_dir {
+            Some(dir) => dir,
+            None => paths::data_dir()?,
+        };
+        fs::create_dir_all(&dir)?;
+        let db_path = dir.join(DB_FILE_NAME);
+        let conn = Connection::open(&db_path)?;
+        let repo = Self { conn, db_path };
+        repo.migrate(&dir.join(JSON_FILE_NAME))?;
+        Ok(repo)
+    }
+
+    /// The database file backing this repository.
+    pub fn file_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    fn migrate(&self, json_path: &Path) -> Result<()> {
+        let version: i32 = self.conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version >= SCHEMA_VERSION {
+            return Ok(());
+        }
+
+        let tx = self.conn.unchecked_transaction()?;
+        if version < 1 {
+            tx.execute_batch(
+                "CREATE TABLE IF NOT EXISTS tasks (
+                    id            TEXT PRIMARY KEY,
+                    name          TEXT NOT NULL,
+                    priority      TEXT NOT NULL,
+                    status        TEXT NOT NULL,
+                    state         TEXT NOT NULL,
+                    due           TEXT,
+                    description   TEXT,
+                    project       TEXT,
+                    estimate      TEXT,
+                    url           TEXT,
+                    remind_before TEXT,
+                    tags          TEXT NOT NULL,
+                    recurrence    TEXT,
+                    created_at    TEXT NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS tasks_status ON tasks (status);
+                CREATE INDEX IF NOT EXISTS tasks_due ON tasks (due);",
+            )?;
+        }
+        if version < 2 {
+            tx.execute_batch("ALTER TABLE tasks ADD COLUMN depends_on TEXT NOT NULL DEFAULT '[]';")?;
+        }
+        // The JSON file is left in place, so switching back loses nothing made before the switch
+        if version < 1 && json_path.exists() {
+            let tasks: Vec<Task> = serde_json::from_reader(BufReader::new(File::open(json_path)?))
+                .map_err(|e| anyhow!("Cannot import {}: {}", json_path.display(), e))?;
+            for task in &tasks {
+                insert(&tx, task)?;
+            }
+        }
+        tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+fn status(state: &TaskState) -> &'static str {
+    match state {
+        TaskState::Pending { .. } => "Pending",
+        TaskState::Completed { .. } => "Completed",
+        TaskState::Deleted { .. } => "Deleted",
+    }
+}
+
+fn priority_name(priority: &Priority) -> String {
+    format!("{:?}", priority)
+}
+
+fn parse_priority(name: &str) -> Priority {
+    match name {
+        "High" => Priority::High,
+        "Low" => Priority::Low,
+        _ => Priority::Medium,
+    }
+}
+
+fn insert(conn: &Connection, task: &Task) -> Result<()> {
+    conn.execute(
+        &format!("INSERT INTO tasks ({}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)", COLUMNS),
+        params![
+            task.id.to_string(),
+            task.name,
+            priority_name(&task.priority),
+            status(&task.state),
+            serde_json::to_string(&task.state)?,
+            task.due,
+            task.description,
+            task.project,
+            task.estimate,
+            task.url,
+            task.remind_before,
+            serde_json::to_string(&task.tags)?,
+            task.recurrence,
+            task.created_at,
+            serde_json::to_string(&task.depends_on)?,
+        ],
+    )?;
+    Ok(())
+}
+
+fn update(conn: &Connection, task: &Task) -> Result<()> {
+    let changed = conn.execute(
+        "UPDATE tasks SET name = ?2, priority = ?3, status = ?4, state = ?5, due = ?6, description = ?7,
+             project = ?8, estimate = ?9, url = ?10, remind_before = ?11, tags = ?12, recurrence = ?13,
+             created_at = ?14, depends_on = ?15
+         WHERE id = ?1",
+        params![
+            task.id.to_string(),
+            task.name,
+            priority_name(&task.priority),
+            status(&task.state),
+            serde_json::to_string(&task.state)?,
+            task.due,
+            task.description,
+            task.project,
+            task.estimate,
+            task.url,
+            task.remind_before,
+            serde_json::to_string(&task.tags)?,
+            task.recurrence,
+            task.created_at,
+            serde_json::to_string(&task.depends_on)?,
+        ],
+    )?;
+    if changed == 0 {
+        return Err(anyhow!("Task with ID {} not found", task.id));
+    }
+    Ok(())
+}
+
+fn task_from_row(row: &Row) -> Result<Task> {
+    let id: String = row.get("id")?;
+    let state: String = row.get("state")?;
+    let tags: String = row.get("tags")?;
+    let created_at: DateTime<Utc> = row.get("created_at")?;
+    let depends_on: String = row.get("depends_on")?;
+    let mut task = Task {
+        id: Uuid::parse_str(&id)?,
+        name: row.get("name")?,
+        priority: parse_priority(&row.get::<_, String>("priority")?),
+        state: serde_json::from_str(&state)?,
+        due: row.get("due")?,
+        description: row.get("description")?,
+        project: row.get("project")?,
+        estimate: row.get("estimate")?,
+        url: row.get("url")?,
+        remind_before: row.get("remind_before")?,
+        tags: serde_json::from_str(&tags)?,
+        recurrence: row.get("recurrence")?,
+        depends_on: serde_json::from_str(&depends_on)?,
+        created_at,
+    };
+    // Same repair the file repository applies on load
+    task.normalize_time_logs();
+    Ok(task)
+}
+
+impl TaskRepository for SqliteTaskRepository {
+    fn create(&self, task: Task) -> Result<Task> {
+        insert(&self.conn, &task)?;
+        Ok(task)
+    }
+
+    fn get(&self, id: &Uuid) -> Result<Task> {
+        let mut stmt = self.conn.prepare(&format!("SELECT {} FROM tasks WHERE id = ?1", COLUMNS))?;
+        let task = stmt
+            .query_row([id.to_string()], |row| Ok(task_from_row(row)))
+            .optional()?
+            .ok_or_else(|| anyhow!("Task with ID {} not found", id))?;
+        task
+    }
+
+    fn list(&self) -> Result<Vec<Task>> {
+        // Insertion order, like the JSON file
+        let mut stmt = self.conn.prepare(&format!("SELECT {} FROM tasks ORDER BY rowid", COLUMNS))?;
+        let rows = stmt.query_map([], |row| Ok(task_from_row(row)))?;
+        let mut tasks = Vec::new();
+        for row in rows {
+            tasks.push(row??);
+        }
+        Ok(tasks)
+    }
+
+    fn update(&self, task: &Task) -> Result<()> {
+        update(&self.conn, task)
+    }
+
+    fn update_many(&self, tasks: &[Task]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for task in tasks {
+            update(&tx, task)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn replace_all(&self, tasks: Vec<Task>) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        tx.execute("DELETE FROM tasks", [])?;
+        for task in &tasks {
+            insert(&tx, task)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn delete(&self, id: &Uuid) -> Result<()> {
+        let changed = self.conn.execute("DELETE FROM tasks WHERE id = ?1", [id.to_string()])?;
+        if changed == 0 {
+            return Err(anyhow!("Task with ID {} not found", id));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::task::TimeLog;
+    use crate::repository::FileTaskRepository;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_sqlite_round_trip() {
+        let dir = TempDir::new();
+        let repo = SqliteTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+
+        let mut task = Task::new("Write report".to_string(), Some(Utc::now()));
+        task.priority = Priority::High;
+        task.tags = vec!["work".to_string()];
+        task.depends_on = vec![Uuid::new_v4()];
+        task.state = TaskState::Pending {
+            time_logs: vec![TimeLog { start: Utc::now(), end: None }],
+        };
+        repo.create(task.clone()).unwrap();
+        repo.create(Task::new("Second".to_string(), None)).unwrap();
+
+        let loaded = repo.get(&task.id).unwrap();
+        assert_eq!(loaded.name, "Write report");
+        assert_eq!(loaded.priority, Priority::High);
+        assert_eq!(loaded.tags, ["work"]);
+        assert_eq!(loaded.depends_on, task.depends_on);
+        assert_eq!(loaded.state, task.state);
+        assert_eq!(loaded.due, task.due);
+
+        let mut done = loaded;
+        done.complete(None);
+        repo.update(&done).unwrap();
+        assert!(matches!(repo.get(&task.id).unwrap().state, TaskState::Completed { .. }));
+
+        let names: Vec<String> = repo.list().unwrap().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["Write report", "Second"]);
+
+        repo.delete(&task.id).unwrap();
+        assert!(repo.get(&task.id).is_err());
+        assert!(repo.delete(&task.id).is_err());
+    }
+
+    #[test]
+    fn test_first_open_imports_tasks_json() {
+        let dir = TempDir::new();
+        let file_repo = FileTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+        let imported = file_repo.create(Task::new("From JSON".to_string(), None)).unwrap();
+
+        let repo = SqliteTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+        assert_eq!(repo.get(&imported.id).unwrap().name, "From JSON");
+
+        // Only the first open imports; later ones don't duplicate
+        drop(repo);
+        let repo = SqliteTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+        assert_eq!(repo.list().unwrap().len(), 1);
+    }
+}
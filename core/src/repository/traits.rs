@@ -1,5 +1,6 @@
 use crate::model::task::Task;
 use anyhow::Result;
+use std::collections::HashSet;
 use uuid::Uuid;
 
 pub trait TaskRepository {
@@ -8,4 +9,62 @@ pub trait TaskRepository {
     fn list(&self) -> Result<Vec<Task>>;
     fn update(&self, task: &Task) -> Result<()>;
     fn delete(&self, id: &Uuid) -> Result<()>;
+
+    /// Updates several tasks at once. Stores that can should do it in one write.
+    fn update_many(&self, tasks: &[Task]) -> Result<()> {
+        for task in tasks {
+            self.update(task)?;
+        }
+        Ok(())
+    }
+
+    /// Makes `tasks` the entire contents of the store. The default goes task by task
+    /// (tasks already stored keep their place); stores that can should swap everything
+    /// in one write, in the given order.
+    fn replace_all(&self, tasks: Vec<Task>) -> Result<()> {
+        let wanted: HashSet<Uuid> = tasks.iter().map(|t| t.id).collect();
+        let existing: HashSet<Uuid> = self.list()?.into_iter().map(|t| t.id).collect();
+        for id in existing.difference(&wanted) {
+            self.delete(id)?;
+        }
+        for task in tasks {
+            if existing.contains(&task.id) {
+                self.update(&task)?;
+            } else {
+                self.create(task)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::MemoryTaskRepository;
+
+    // Forwards everything but `replace_all`, so the default implementation runs
+    struct Plain(MemoryTaskRepository);
+
+    impl TaskRepository for Plain {
+        fn create(&self, task: Task) -> Result<Task> { self.0.create(task) }
+        fn get(&self, id: &Uuid) -> Result<Task> { self.0.get(id) }
+        fn list(&self) -> Result<Vec<Task>> { self.0.list() }
+        fn update(&self, task: &Task) -> Result<()> { self.0.update(task) }
+        fn delete(&self, id: &Uuid) -> Result<()> { self.0.delete(id) }
+    }
+
+    #[test]
+    fn test_default_replace_all() {
+        let gone = Task::new("Gone".to_string(), None);
+        let mut kept = Task::new("Kept".to_string(), None);
+        let repo = Plain(MemoryTaskRepository::with_tasks(vec![gone, kept.clone()]));
+
+        kept.name = "Kept and renamed".to_string();
+        let added = Task::new("Added".to_string(), None);
+        repo.replace_all(vec![kept, added]).unwrap();
+
+        let names: Vec<String> = repo.list().unwrap().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["Kept and renamed", "Added"]);
+    }
 }
@@ -1,15 +1,19 @@
 use crate::model::task::{Task, TaskState};
-use crate::model::stats::MonthlyStats;
+use crate::model::stats::{MonthlyStats, TaskCredit};
+use crate::paths;
 use crate::repository::{TaskRepository, FileStatsRepository}; // Assuming generic Repo is hard, we use FileStatsRepo directly or trait? 
 // For simplicity in this script-like service, we use concrete FileStatsRepo or define a trait if needed.
 // But wait, TaskRepository is a trait.
 // Let's use concrete FileStatsRepository for now as it's new.
 
+use crate::repository::atomic::write_json_atomic;
+
 use chrono::{Datelike, Utc, Duration, DateTime};
-use anyhow::Result;
-use std::collections::HashMap;
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::fs;
+use uuid::Uuid;
 
 pub struct ArchiveService<R: TaskRepository> {
     task_repo: R,
@@ -19,9 +23,11 @@ pub struct ArchiveService<R: TaskRepository> {
 
 impl<R: TaskRepository> ArchiveService<R> {
     pub fn new(task_repo: R, stats_repo: FileStatsRepository) -> Self {
-        let mut archive_dir = dirs::home_dir().expect("Home dir not found");
-        archive_dir.push(".todoism");
-        archive_dir.push("archive");
+        let archive_dir = paths::data_dir().expect("Data dir not found").join("archive");
+        Self::with_archive_dir(task_repo, stats_repo, archive_dir)
+    }
+
+    pub fn with_archive_dir(task_repo: R, stats_repo: FileStatsRepository, archive_dir: PathBuf) -> Self {
         fs::create_dir_all(&archive_dir).unwrap(); // Ensure exists
 
         Self {
@@ -31,22 +37,22 @@ impl<R: TaskRepository> ArchiveService<R> {
         }
     }
 
+    /// The tasks `archive_old_tasks(cutoff_days)` would move, without moving them.
+    pub fn archivable_tasks(&self, cutoff_days: i64) -> Result<Vec<Task>> {
+        let cutoff_date = cutoff_date(Utc::now(), cutoff_days)?;
+        Ok(self.task_repo.list()?.into_iter().filter(|t| is_archivable(t, cutoff_date)).collect())
+    }
+
     pub fn archive_old_tasks(&self, cutoff_days: i64) -> Result<usize> {
         let all_tasks = self.task_repo.list()?;
         let now = Utc::now();
-        let cutoff_date = now - Duration::days(cutoff_days);
+        let cutoff_date = cutoff_date(now, cutoff_days)?;
 
         let mut tasks_to_archive = Vec::new();
         let mut tasks_to_keep = Vec::new();
 
         for task in all_tasks {
-            let should_archive = match &task.state {
-                TaskState::Completed { completed_at, .. } => *completed_at < cutoff_date,
-                TaskState::Deleted => task.created_at < cutoff_date, // Archive old deleted too? Sure.
-                _ => false,
-            };
-
-            if should_archive {
+            if is_archivable(&task, cutoff_date) {
                 tasks_to_archive.push(task);
             } else {
                 tasks_to_keep.push(task);
@@ -63,65 +69,67 @@ impl<R: TaskRepository> ArchiveService<R> {
         // 2. Write to Archive Files
         self.write_to_archive(&tasks_to_archive)?;
 
-        // 3. Update Task Repo (Delete archived)
-        // Since repo doesn't have "bulk update", we might need to delete one by one or overwrite file.
-        // If R is FileTaskRepository, it has `save_all`? No.
-        // It has `create`, `update`, `delete`.
-        // We can call `delete` for each.
-        for task in &tasks_to_archive {
-            self.task_repo.delete(&task.id)?;
-        }
+        // 3. Drop the archived tasks from the repo in a single write
+        self.task_repo.replace_all(tasks_to_keep)?;
 
         Ok(tasks_to_archive.len())
     }
 
+    /// Every task in the archive files, oldest month first.
+    pub fn archived_tasks(&self) -> Result<Vec<Task>> {
+        let mut tasks = Vec::new();
+        for path in self.archive_files()? {
+            tasks.extend(read_archive(&path)?);
+        }
+        Ok(tasks)
+    }
+
+    /// Takes the task with `id` out of its archive file and puts it back in the task list,
+    /// state unchanged. Errors when no archive file holds it or the list already has it.
+    pub fn restore_task(&self, id: &Uuid) -> Result<Task> {
+        for path in self.archive_files()? {
+            let mut tasks = read_archive(&path)?;
+            let Some(index) = tasks.iter().position(|t| t.id == *id) else {
+                continue;
+            };
+            if self.task_repo.get(id).is_ok() {
+                return Err(anyhow!("Task {} is already in the task list", id));
+            }
+
+            let task = tasks.remove(index);
+            // Into the list first: a failure in between leaves a duplicate, never a lost task
+            self.task_repo.create(task.clone())?;
+            if tasks.is_empty() {
+                fs::remove_file(&path)?;
+            } else {
+                write_json_atomic(&path, &tasks)?;
+            }
+            return Ok(task);
+        }
+        Err(anyhow!("Task {} is not in any archive file", id))
+    }
+
+    // `tasks_YYYY_MM.json` files, sorted by month
+    fn archive_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.archive_dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.file_name().and_then(|n| n.to_str())
+                .is_some_and(|name| name.starts_with("tasks_") && name.ends_with(".json")))
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+
     fn update_stats(&self, tasks: &[Task]) -> Result<()> {
         // Group by Month
         let mut monthly_groups: HashMap<(i32, u32), MonthlyStats> = HashMap::new();
 
         for task in tasks {
-            // Determine date for stats. For Completed, use completed_at. For Deleted, maybe created_at?
-            // Stats usually track "Work Done". Deleted tasks usually have 0 work done (unless logged).
-            // If they have logs, we should credit the logs to the date they happened?
-            // COMPLEXITY: Real stats should aggregate TimeLogs by their specific dates.
-            // Simplified MVP: Credit total Act/Est to the completion date.
-            // User agreed to: "monthly json... est,act,meeting structured".
-            // Let's stick to: Credit to Completed Date.
-            
-            if let TaskState::Completed { completed_at, actual, time_logs: _ } = &task.state {
-                let local_dt = DateTime::<chrono::Local>::from(*completed_at);
-                let date_str = local_dt.format("%Y-%m-%d").to_string();
-                let year = local_dt.year();
-                let month = local_dt.month();
-
+            for (year, month, credit) in completion_credit(task) {
                 let stats = monthly_groups.entry((year, month))
                     .or_insert_with(|| self.stats_repo.get_stats(year, month).unwrap_or(MonthlyStats::new(year, month)));
-
-                let est = crate::service::task_service::parse_est_hours(&task.estimate);
-                
-                // Act parsing (using days logic from DTO/History)
-                // Note: In DTO/history we divide by 3600*8? 
-                // Wait, previous logic: "input 1 = 8 hours". 
-                // `parse_est_hours` returns HOURS (input * 8).
-                // `act` input string is DAYS (e.g. "0.5").
-                
-                let act_hours = if let Some(act_str) = actual {
-                    act_str.parse::<f64>().unwrap_or(0.0) * 8.0
-                } else {
-                    0.0 // Ignore logs for archived tasks to simplify? Or sum logs?
-                    // Previous history logic summed logs if act is missing.
-                    // For archive, let's assume act exists or 0.
-                };
-                
-                // Mtg? Not stored in task. Mtg is in DailyLog. 
-                // DailyLog is NOT archived here (it's separate).
-                // So Stats JSON only holds Task Est/Act. 
-                // Mtg will be read from DailyLogRepo (which should arguably be archived too, but maybe later).
-                // For now, Stats JSON has `mtg` field but we might leave it 0 if we don't aggregate logs.
-                // Or does `HistoryUseCase` read DailyLog for meetings? Yes.
-                // So ArchiveService only handles Tasks.
-                
-                stats.add(date_str, est, act_hours, 0.0);
+                // Tasks credited live on completion are skipped here
+                stats.credit(task.id, credit);
             }
         }
 
@@ -139,7 +147,7 @@ impl<R: TaskRepository> ArchiveService<R> {
         for task in tasks {
             let dt = match &task.state {
                 TaskState::Completed { completed_at, .. } => *completed_at,
-                TaskState::Deleted => task.created_at, // Sort of arbitrary
+                TaskState::Deleted { deleted_at } => *deleted_at,
                 _ => task.created_at,
             };
             let local = DateTime::<chrono::Local>::from(dt);
@@ -150,22 +158,281 @@ impl<R: TaskRepository> ArchiveService<R> {
             let filename = format!("tasks_{:04}_{:02}.json", year, month);
             let path = self.archive_dir.join(filename);
             
-            // Read existing if any
+            // Read existing if any; a file that doesn't parse is an error, never overwritten
             let mut existing_tasks: Vec<Task> = if path.exists() {
-                let content = fs::read_to_string(&path)?;
-                serde_json::from_str(&content).unwrap_or_default()
+                read_archive(&path)?
             } else {
                 Vec::new()
             };
             
-            // Merge (avoid dupes? IDs should be unique. Just append)
+            // Merge: a task archived again (e.g. after a restore) replaces its old entry
             for t in tasks {
-                existing_tasks.push(t.clone());
+                match existing_tasks.iter_mut().find(|existing| existing.id == t.id) {
+                    Some(existing) => *existing = t.clone(),
+                    None => existing_tasks.push(t.clone()),
+                }
             }
-            
-            let content = serde_json::to_string_pretty(&existing_tasks)?;
-            fs::write(path, content)?;
+
+            write_json_atomic(&path, &existing_tasks)?;
         }
         Ok(())
     }
 }
+
+fn cutoff_date(now: DateTime<Utc>, cutoff_days: i64) -> Result<DateTime<Utc>> {
+    Duration::try_days(cutoff_days)
+        .and_then(|days| now.checked_sub_signed(days))
+        .ok_or_else(|| anyhow!("{} days reaches back too far", cutoff_days))
+}
+
+// Completed or deleted before the cutoff; pending tasks always stay
+fn is_archivable(task: &Task, cutoff_date: DateTime<Utc>) -> bool {
+    match &task.state {
+        TaskState::Completed { completed_at, .. } => *completed_at < cutoff_date,
+        TaskState::Deleted { deleted_at } => *deleted_at < cutoff_date,
+        _ => false,
+    }
+}
+
+fn read_archive(path: &std::path::Path) -> Result<Vec<Task>> {
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| anyhow!("Cannot read archive {}: {}", path.display(), e))
+}
+
+/// The stats contribution of a completed task, one credit per month it touches, the same
+/// way the history view counts it: est on the (local) completion day, tracked time on the
+/// days it was worked, or the manual actual on the completion day when nothing was tracked.
+/// Deleted and pending tasks contribute nothing. Mtg lives in DailyLog, not in tasks.
+pub(crate) fn completion_credit(task: &Task) -> Vec<(i32, u32, TaskCredit)> {
+    let TaskState::Completed { completed_at, actual, time_logs } = &task.state else {
+        return Vec::new();
+    };
+    let local_dt = DateTime::<chrono::Local>::from(*completed_at);
+    let completion_month = (local_dt.year(), local_dt.month());
+
+    let est = crate::service::task_service::parse_est_hours(&task.estimate);
+    // The manual actual reads like an estimate: `2h`, `1d` or bare days such as `0.5`
+    let act_hours = if time_logs.is_empty() {
+        crate::service::task_service::parse_est_hours(actual)
+    } else {
+        0.0
+    };
+
+    let mut months: BTreeMap<(i32, u32), TaskCredit> = BTreeMap::new();
+    months.insert(completion_month, TaskCredit {
+        date: local_dt.format("%Y-%m-%d").to_string(),
+        est,
+        act: act_hours,
+        logged: BTreeMap::new(),
+    });
+    for (date, hours) in crate::usecase::history::hours_by_day(time_logs) {
+        let day = date.format("%Y-%m-%d").to_string();
+        // Other months keep the credit under their first worked day
+        let credit = months.entry((date.year(), date.month())).or_insert_with(|| TaskCredit {
+            date: day.clone(),
+            est: 0.0,
+            act: 0.0,
+            logged: BTreeMap::new(),
+        });
+        credit.logged.insert(day, hours);
+    }
+
+    months.into_iter().map(|((year, month), credit)| (year, month, credit)).collect()
+}
+
+/// Credits a completed task to its month right away. Safe to repeat: archiving skips it later.
+pub(crate) fn credit_completed_task(stats_repo: &FileStatsRepository, task: &Task) -> Result<()> {
+    for (year, month, credit) in completion_credit(task) {
+        let mut stats = stats_repo.get_stats(year, month)?;
+        if stats.credit(task.id, credit) {
+            stats_repo.save_stats(&stats)?;
+        }
+    }
+    Ok(())
+}
+
+/// Reverts `credit_completed_task`; call while the task is still in its completed state.
+pub(crate) fn uncredit_completed_task(stats_repo: &FileStatsRepository, task: &Task) -> Result<()> {
+    for (year, month, _) in completion_credit(task) {
+        let mut stats = stats_repo.get_stats(year, month)?;
+        if stats.uncredit(&task.id) {
+            stats_repo.save_stats(&stats)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{FileDailyLogRepository, FileTaskRepository};
+    use crate::service::daily_log_service::DailyLogService;
+    use crate::service::task_service::TaskService;
+    use crate::test_util::TempDir;
+    use crate::usecase::history::HistoryUseCase;
+    use chrono::Local;
+
+    fn month_act(stats_repo: &FileStatsRepository) -> f64 {
+        let today = Local::now();
+        let stats = stats_repo.get_stats(today.year(), today.month()).unwrap();
+        stats.days.values().map(|d| d.act).sum()
+    }
+
+    #[test]
+    fn test_live_credit_is_not_counted_twice() {
+        let dir = TempDir::new();
+        let task_repo = FileTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+        let stats_repo = FileStatsRepository::new(Some(dir.join("stats"))).unwrap();
+        let service = TaskService::new(task_repo.clone()).with_live_stats(stats_repo.clone());
+
+        let mut task = Task::new("Ship it".to_string(), None);
+        task.estimate = Some("0.5".to_string());
+        let id = service.create_task(task).unwrap().id;
+        service.complete_task_with_effort(&id, "1".to_string(), true).unwrap();
+        assert_eq!(month_act(&stats_repo), 8.0);
+
+        // History sees the task in both the stats and the task list, but counts it once
+        let log_service = DailyLogService::new(FileDailyLogRepository::new(Some(dir.to_path_buf())).unwrap());
+        let history = HistoryUseCase::new(&task_repo, &log_service, &stats_repo).get_weekly_history().unwrap();
+        let act: f64 = history.iter().map(|w| w.stats.total_act_hours).sum();
+        assert_eq!(act, 8.0);
+
+        // Archiving skips the already credited task
+        let archive = ArchiveService::with_archive_dir(task_repo.clone(), stats_repo.clone(), dir.join("archive"));
+        assert_eq!(archive.archive_old_tasks(-1).unwrap(), 1);
+        assert_eq!(month_act(&stats_repo), 8.0);
+    }
+
+    #[test]
+    fn test_archive_credits_time_logs_to_the_days_worked() {
+        use crate::model::task::TimeLog;
+        use chrono::TimeZone;
+
+        let dir = TempDir::new();
+        let task_repo = FileTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+        let stats_repo = FileStatsRepository::new(Some(dir.join("stats"))).unwrap();
+        let at = |month: u32, day: u32, hour: u32| Local.with_ymd_and_hms(2025, month, day, hour, 0, 0).unwrap().with_timezone(&Utc);
+
+        let mut task = Task::new("Spread out".to_string(), None);
+        task.estimate = Some("4h".to_string());
+        task.state = TaskState::Completed {
+            completed_at: at(2, 3, 18),
+            time_logs: vec![
+                TimeLog { start: at(1, 30, 9), end: Some(at(1, 30, 11)) },
+                TimeLog { start: at(2, 3, 14), end: Some(at(2, 3, 15)) },
+            ],
+            actual: Some("1".to_string()), // Tracked time wins, as in the history view
+        };
+        task_repo.create(task).unwrap();
+
+        let archive = ArchiveService::with_archive_dir(task_repo, stats_repo.clone(), dir.join("archive"));
+        assert_eq!(archive.archive_old_tasks(-1).unwrap(), 1);
+
+        let january = stats_repo.get_stats(2025, 1).unwrap();
+        assert_eq!((january.days["2025-01-30"].est, january.days["2025-01-30"].act), (0.0, 2.0));
+        let february = stats_repo.get_stats(2025, 2).unwrap();
+        assert_eq!((february.days["2025-02-03"].est, february.days["2025-02-03"].act), (4.0, 1.0));
+        assert_eq!(february.days.values().map(|d| d.act).sum::<f64>(), 1.0);
+    }
+
+    #[test]
+    fn test_archive_keeps_pending_tasks_in_order() {
+        let dir = TempDir::new();
+        let task_repo = FileTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+        let stats_repo = FileStatsRepository::new(Some(dir.join("stats"))).unwrap();
+        for name in ["First", "Done", "Second"] {
+            let mut task = Task::new(name.to_string(), None);
+            if name == "Done" {
+                task.complete(None);
+            }
+            task_repo.create(task).unwrap();
+        }
+
+        let archive = ArchiveService::with_archive_dir(task_repo.clone(), stats_repo, dir.join("archive"));
+        assert!(archive.archivable_tasks(30).unwrap().is_empty());
+        assert!(archive.archivable_tasks(4_000_000_000).is_err());
+        assert!(archive.archive_old_tasks(4_000_000_000).is_err());
+        let preview: Vec<String> = archive.archivable_tasks(-1).unwrap().into_iter().map(|t| t.name).collect();
+        assert_eq!(preview, ["Done"]);
+        assert_eq!(task_repo.list().unwrap().len(), 3); // Previewing moves nothing
+        assert_eq!(archive.archive_old_tasks(-1).unwrap(), 1);
+        let names: Vec<String> = task_repo.list().unwrap().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["First", "Second"]);
+    }
+
+    #[test]
+    fn test_archiving_a_task_twice_keeps_one_entry() {
+        let dir = TempDir::new();
+        let task_repo = FileTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+        let stats_repo = FileStatsRepository::new(Some(dir.join("stats"))).unwrap();
+        let mut task = Task::new("Twice".to_string(), None);
+        task.complete(None);
+        task_repo.create(task.clone()).unwrap();
+
+        let archive = ArchiveService::with_archive_dir(task_repo.clone(), stats_repo, dir.join("archive"));
+        assert_eq!(archive.archive_old_tasks(-1).unwrap(), 1);
+        task.name = "Twice, renamed".to_string();
+        task_repo.create(task.clone()).unwrap();
+        assert_eq!(archive.archive_old_tasks(-1).unwrap(), 1);
+
+        let files: Vec<_> = fs::read_dir(dir.join("archive")).unwrap().collect();
+        assert_eq!(files.len(), 1);
+        let archived = read_archive(&files[0].as_ref().unwrap().path()).unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!((archived[0].id, archived[0].name.as_str()), (task.id, "Twice, renamed"));
+
+        // A corrupt archive file stops the archive instead of being replaced
+        let path = files[0].as_ref().unwrap().path();
+        fs::write(&path, "not json").unwrap();
+        task_repo.create(task.clone()).unwrap();
+        assert!(archive.archive_old_tasks(-1).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "not json");
+        assert_eq!(task_repo.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_restore_task_from_archive() {
+        let dir = TempDir::new();
+        let task_repo = FileTaskRepository::new(Some(dir.to_path_buf())).unwrap();
+        let stats_repo = FileStatsRepository::new(Some(dir.join("stats"))).unwrap();
+        let mut ids = Vec::new();
+        for name in ["Old", "Older"] {
+            let mut task = Task::new(name.to_string(), None);
+            task.complete(None);
+            ids.push(task_repo.create(task).unwrap().id);
+        }
+
+        let archive = ArchiveService::with_archive_dir(task_repo.clone(), stats_repo, dir.join("archive"));
+        assert_eq!(archive.archive_old_tasks(-1).unwrap(), 2);
+        assert_eq!(archive.archived_tasks().unwrap().len(), 2);
+
+        let restored = archive.restore_task(&ids[0]).unwrap();
+        assert_eq!(restored.name, "Old");
+        assert!(matches!(task_repo.get(&ids[0]).unwrap().state, TaskState::Completed { .. }));
+        let left: Vec<Uuid> = archive.archived_tasks().unwrap().into_iter().map(|t| t.id).collect();
+        assert_eq!(left, [ids[1]]);
+
+        // Gone from the archive now, and never archived at all
+        assert!(archive.restore_task(&ids[0]).is_err());
+        assert!(archive.restore_task(&Uuid::new_v4()).is_err());
+
+        // The last task of a file takes the file with it
+        archive.restore_task(&ids[1]).unwrap();
+        assert!(archive.archived_tasks().unwrap().is_empty());
+        assert_eq!(fs::read_dir(dir.join("archive")).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_reopen_takes_live_credit_back() {
+        let dir = TempDir::new();
+        let stats_repo = FileStatsRepository::new(Some(dir.join("stats"))).unwrap();
+        let service = TaskService::new(FileTaskRepository::new(Some(dir.to_path_buf())).unwrap()).with_live_stats(stats_repo.clone());
+
+        let id = service.create_task(Task::new("Oops".to_string(), None)).unwrap().id;
+        service.complete_task_with_effort(&id, "0.5".to_string(), false).unwrap();
+        assert_eq!(month_act(&stats_repo), 4.0);
+
+        service.toggle_status(&id).unwrap(); // reopen
+        assert_eq!(month_act(&stats_repo), 0.0);
+    }
+}
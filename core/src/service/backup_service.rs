@@ -0,0 +1,286 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::model::daily_log::DailyLog;
+use crate::model::stats::MonthlyStats;
+use crate::model::task::Task;
+use crate::paths;
+
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const TASKS_FILE: &str = "tasks.json";
+const DAILY_LOGS_FILE: &str = "daily_logs.json";
+const STATS_DIR: &str = "stats";
+const ARCHIVE_DIR: &str = "archive";
+const STAGING_DIR: &str = ".restore-staging";
+const PREVIOUS_DIR: &str = ".restore-previous";
+
+/// Everything that makes up the data dir (tasks, daily logs, monthly stats and the
+/// task archive) bundled into one JSON envelope.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// File contents keyed by their path relative to the data dir (e.g. `stats/stats_2025_01.json`)
+    pub files: BTreeMap<String, String>,
+}
+
+impl Backup {
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parses and validates a bundle; anything partial or corrupt is rejected here,
+    /// before a single file of the current data is touched.
+    pub fn from_json(content: &str) -> Result<Self> {
+        let backup: Backup = serde_json::from_str(content)
+            .map_err(|e| anyhow!("Not a todoism backup: {}", e))?;
+        backup.validate()?;
+        Ok(backup)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.version != BACKUP_FORMAT_VERSION {
+            return Err(anyhow!(
+                "Unsupported backup version {} (expected {})",
+                self.version,
+                BACKUP_FORMAT_VERSION
+            ));
+        }
+        if !self.files.contains_key(TASKS_FILE) {
+            return Err(anyhow!("Backup is incomplete: {} is missing", TASKS_FILE));
+        }
+
+        for (name, content) in &self.files {
+            let invalid = |e: serde_json::Error| anyhow!("Backup entry {} is corrupt: {}", name, e);
+            match name.split_once('/') {
+                None if name == TASKS_FILE => {
+                    serde_json::from_str::<Vec<Task>>(content).map_err(invalid)?;
+                }
+                None if name == DAILY_LOGS_FILE => {
+                    serde_json::from_str::<Vec<DailyLog>>(content).map_err(invalid)?;
+                }
+                Some((STATS_DIR, file)) if is_plain_json_name(file) => {
+                    serde_json::from_str::<MonthlyStats>(content).map_err(invalid)?;
+                }
+                Some((ARCHIVE_DIR, file)) if is_plain_json_name(file) => {
+                    serde_json::from_str::<Vec<Task>>(content).map_err(invalid)?;
+                }
+                _ => return Err(anyhow!("Backup contains an unexpected entry: {}", name)),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn task_count(&self) -> usize {
+        self.files
+            .get(TASKS_FILE)
+            .and_then(|content| serde_json::from_str::<Vec<Task>>(content).ok())
+            .map_or(0, |tasks| tasks.len())
+    }
+}
+
+fn is_plain_json_name(name: &str) -> bool {
+    !name.contains('/') && !name.contains('\\') && !name.starts_with('.') && name.ends_with(".json")
+}
+
+/// Creates and restores whole-data-dir backups.
+pub struct BackupService {
+    data_dir: PathBuf,
+}
+
+impl BackupService {
+    pub fn new(base_dir: Option<PathBuf>) -> Result<Self> {
+        let data_dir = match base_dir {
+            Some(dir) => dir,
+            None => paths::data_dir()?,
+        };
+        Ok(Self { data_dir })
+    }
+
+    pub fn create(&self) -> Result<Backup> {
+        let mut files = BTreeMap::new();
+
+        for name in [TASKS_FILE, DAILY_LOGS_FILE] {
+            let path = self.data_dir.join(name);
+            if path.exists() {
+                files.insert(name.to_string(), fs::read_to_string(path)?);
+            }
+        }
+        for dir in [STATS_DIR, ARCHIVE_DIR] {
+            let path = self.data_dir.join(dir);
+            if !path.exists() {
+                continue;
+            }
+            for entry in fs::read_dir(path)? {
+                let path = entry?.path();
+                let Some(file) = path.file_name().and_then(|s| s.to_str()) else { continue };
+                if path.is_file() && is_plain_json_name(file) {
+                    files.insert(format!("{}/{}", dir, file), fs::read_to_string(&path)?);
+                }
+            }
+        }
+
+        let backup = Backup {
+            version: BACKUP_FORMAT_VERSION,
+            created_at: Utc::now(),
+            files,
+        };
+        // Never hand out a bundle we would refuse to restore
+        backup.validate()?;
+        Ok(backup)
+    }
+
+    /// Replaces tasks, daily logs, stats and archive with the bundle's contents.
+    /// The bundle is written to a staging dir first and only then swapped in; if the
+    /// swap fails halfway the previous files are put back.
+    pub fn restore(&self, backup: &Backup) -> Result<()> {
+        backup.validate()?;
+        fs::create_dir_all(&self.data_dir)?;
+
+        let staging = self.data_dir.join(STAGING_DIR);
+        let previous = self.data_dir.join(PREVIOUS_DIR);
+        for dir in [&staging, &previous] {
+            if dir.exists() {
+                fs::remove_dir_all(dir)?;
+            }
+        }
+
+        fs::create_dir_all(staging.join(STATS_DIR))?;
+        fs::create_dir_all(staging.join(ARCHIVE_DIR))?;
+        fs::create_dir_all(&previous)?;
+        if !backup.files.contains_key(DAILY_LOGS_FILE) {
+            fs::write(staging.join(DAILY_LOGS_FILE), "[]")?;
+        }
+        for (name, content) in &backup.files {
+            fs::write(staging.join(name), content)?;
+        }
+
+        let targets = [TASKS_FILE, DAILY_LOGS_FILE, STATS_DIR, ARCHIVE_DIR];
+        let mut swapped = Vec::new();
+        for name in targets {
+            if let Err(e) = swap_in(&self.data_dir, &staging, &previous, name) {
+                for done in swapped.into_iter().rev() {
+                    let _ = roll_back(&self.data_dir, &previous, done);
+                }
+                let _ = roll_back(&self.data_dir, &previous, name);
+                return Err(anyhow!("Restore failed, previous data kept: {}", e));
+            }
+            swapped.push(name);
+        }
+
+        fs::remove_dir_all(&staging)?;
+        fs::remove_dir_all(&previous)?;
+        Ok(())
+    }
+}
+
+fn swap_in(data_dir: &Path, staging: &Path, previous: &Path, name: &str) -> Result<()> {
+    let current = data_dir.join(name);
+    if current.exists() {
+        fs::rename(&current, previous.join(name))?;
+    }
+    fs::rename(staging.join(name), current)?;
+    Ok(())
+}
+
+fn roll_back(data_dir: &Path, previous: &Path, name: &str) -> Result<()> {
+    let current = data_dir.join(name);
+    let saved = previous.join(name);
+    if !saved.exists() {
+        return Ok(());
+    }
+    if current.is_dir() {
+        fs::remove_dir_all(&current)?;
+    } else if current.exists() {
+        fs::remove_file(&current)?;
+    }
+    fs::rename(saved, current)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    fn tasks_json(names: &[&str]) -> String {
+        let tasks: Vec<Task> = names
+            .iter()
+            .map(|n| Task::new(n.to_string(), None))
+            .collect();
+        serde_json::to_string(&tasks).unwrap()
+    }
+
+    #[test]
+    fn test_backup_round_trip_replaces_everything() {
+        let source = TempDir::new();
+        fs::write(source.join(TASKS_FILE), tasks_json(&["Write report"])).unwrap();
+        fs::write(source.join(DAILY_LOGS_FILE), "[]").unwrap();
+        fs::create_dir_all(source.join(STATS_DIR)).unwrap();
+        let stats = serde_json::to_string(&MonthlyStats::new(2025, 1)).unwrap();
+        fs::write(source.join(STATS_DIR).join("stats_2025_01.json"), &stats).unwrap();
+        fs::create_dir_all(source.join(ARCHIVE_DIR)).unwrap();
+        fs::write(source.join(ARCHIVE_DIR).join("tasks_2025_01.json"), tasks_json(&["Old"])).unwrap();
+
+        let backup = BackupService::new(Some(source.to_path_buf())).unwrap().create().unwrap();
+        assert_eq!(backup.files.len(), 4);
+        let parsed = Backup::from_json(&backup.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, backup);
+
+        let target = TempDir::new();
+        fs::write(target.join(TASKS_FILE), tasks_json(&["A", "B"])).unwrap();
+        fs::create_dir_all(target.join(STATS_DIR)).unwrap();
+        fs::write(target.join(STATS_DIR).join("stats_2024_12.json"), &stats).unwrap();
+
+        BackupService::new(Some(target.to_path_buf())).unwrap().restore(&parsed).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(target.join(TASKS_FILE)).unwrap(),
+            fs::read_to_string(source.join(TASKS_FILE)).unwrap()
+        );
+        assert!(target.join(STATS_DIR).join("stats_2025_01.json").exists());
+        assert!(!target.join(STATS_DIR).join("stats_2024_12.json").exists());
+        assert!(target.join(ARCHIVE_DIR).join("tasks_2025_01.json").exists());
+        assert!(!target.join(STAGING_DIR).exists());
+        assert!(!target.join(PREVIOUS_DIR).exists());
+    }
+
+    #[test]
+    fn test_corrupt_or_partial_backup_is_refused() {
+        let mut files = BTreeMap::new();
+        files.insert(TASKS_FILE.to_string(), tasks_json(&["A"]));
+        files.insert(DAILY_LOGS_FILE.to_string(), "[{\"broken\":".to_string());
+        let backup = Backup { version: BACKUP_FORMAT_VERSION, created_at: Utc::now(), files };
+        assert!(Backup::from_json(&backup.to_json().unwrap()).is_err());
+
+        let partial = Backup { version: BACKUP_FORMAT_VERSION, created_at: Utc::now(), files: BTreeMap::new() };
+        assert!(partial.validate().is_err());
+
+        let mut files = BTreeMap::new();
+        files.insert(TASKS_FILE.to_string(), tasks_json(&["A"]));
+        files.insert("stats/../../evil.json".to_string(), "{}".to_string());
+        let escaping = Backup { version: BACKUP_FORMAT_VERSION, created_at: Utc::now(), files };
+        assert!(escaping.validate().is_err());
+
+        assert!(Backup::from_json("{\"version\": 1").is_err());
+    }
+
+    #[test]
+    fn test_refused_restore_leaves_data_untouched() {
+        let target = TempDir::new();
+        let original = tasks_json(&["Keep me"]);
+        fs::write(target.join(TASKS_FILE), &original).unwrap();
+
+        let mut files = BTreeMap::new();
+        files.insert(TASKS_FILE.to_string(), "not json".to_string());
+        let backup = Backup { version: BACKUP_FORMAT_VERSION, created_at: Utc::now(), files };
+        assert!(BackupService::new(Some(target.to_path_buf())).unwrap().restore(&backup).is_err());
+        assert_eq!(fs::read_to_string(target.join(TASKS_FILE)).unwrap(), original);
+    }
+}
@@ -1,7 +1,8 @@
-use crate::model::daily_log::DailyLog;
+use crate::model::daily_log::{DailyLog, Meeting, ALL_MEETINGS};
 use crate::repository::DailyLogRepository;
 use anyhow::Result;
-use chrono::NaiveDate;
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use std::collections::HashSet;
 
 pub struct DailyLogService<R: DailyLogRepository> {
     repo: R,
@@ -21,7 +22,72 @@ impl<R: DailyLogRepository> DailyLogService<R> {
         self.repo.upsert(log)
     }
 
+    /// Corrects the day's check-in: the "all" meeting is set to `hours`, replacing what
+    /// was logged instead of adding to it. Other named meetings are kept.
+    pub fn set_log(&self, date: NaiveDate, hours: f64) -> Result<()> {
+        let mut log = self.repo.get(date)?.unwrap_or(DailyLog { date, meetings: Vec::new() });
+        match log.meetings.iter_mut().find(|m| m.name == ALL_MEETINGS) {
+            Some(meeting) => meeting.hours = hours,
+            None => log.meetings.push(Meeting { name: ALL_MEETINGS.to_string(), hours }),
+        }
+        self.repo.upsert(log)
+    }
+
+    pub fn clear_log(&self, date: NaiveDate) -> Result<bool> {
+        self.repo.delete(date)
+    }
+
     pub fn has_log(&self, date: NaiveDate) -> Result<bool> {
         Ok(self.repo.get(date)?.is_some())
     }
+
+    /// Working days among the `days` before `today` that have no log, oldest first.
+    /// Today is left to the regular check-in prompt.
+    pub fn missing_dates(&self, today: NaiveDate, days: u32, working_days: &[Weekday]) -> Result<Vec<NaiveDate>> {
+        let logged: HashSet<NaiveDate> = self.repo.list()?.into_iter().map(|l| l.date).collect();
+        let first = today.checked_sub_days(Days::new(days as u64)).unwrap_or(NaiveDate::MIN);
+        Ok(first.iter_days()
+            .take_while(|date| *date < today)
+            .filter(|date| working_days.contains(&date.weekday()) && !logged.contains(date))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::FileDailyLogRepository;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_set_log_overwrites_the_check_in() {
+        let dir = TempDir::new();
+        let service = DailyLogService::new(FileDailyLogRepository::new(Some(dir.to_path_buf())).unwrap());
+        let date = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+
+        service.set_log(date, 2.0).unwrap();
+        assert_eq!(service.get_log(date).unwrap().unwrap().total_hours(), 2.0);
+        service.set_log(date, 0.5).unwrap();
+        let log = service.get_log(date).unwrap().unwrap();
+        assert_eq!(log.total_hours(), 0.5);
+        assert_eq!(log.meetings.len(), 1);
+    }
+
+    #[test]
+    fn test_missing_dates_skips_logged_and_non_working_days() {
+        let dir = TempDir::new();
+        let service = DailyLogService::new(FileDailyLogRepository::new(Some(dir.to_path_buf())).unwrap());
+        let working_days = [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri];
+
+        // Monday 2025-01-13; the week before runs Mon 6th .. Sun 12th
+        let today = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+        service.add_log(NaiveDate::from_ymd_opt(2025, 1, 8).unwrap(), 1.0).unwrap();
+
+        let missing = service.missing_dates(today, 7, &working_days).unwrap();
+        let days: Vec<u32> = missing.iter().map(|d| d.day()).collect();
+        assert_eq!(days, vec![6, 7, 9, 10]);
+
+        // Looking back past the earliest date stops there instead of overflowing
+        assert!(service.missing_dates(NaiveDate::MIN, 7, &working_days).unwrap().is_empty());
+    }
 }
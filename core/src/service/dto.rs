@@ -1,7 +1,10 @@
 use serde::{Serialize, Deserialize};
-use chrono::{DateTime, Utc, Local};
+use chrono::{DateTime, Duration, Utc, Local};
 use uuid::Uuid;
 use crate::model::task::{Task, TaskState, Priority};
+use crate::service::task_service::parse_est_hours;
+use crate::time::start_of_day;
+use crate::usecase::daily_plan::DailyPlanStats;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct TaskDto {
@@ -12,14 +15,31 @@ pub struct TaskDto {
     pub project: Option<String>,
     pub estimate: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub remind_before: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    #[serde(default)]
+    pub depends_on: Vec<Uuid>,
     
     // Flattened state fields for UI
     pub status: String,      // "Pending", "Completed", "Deleted"
     pub is_tracking: bool,
+    #[serde(default)]
+    pub open_session_time: u64, // In seconds. Elapsed time of the running time log, 0 when not tracking.
     pub accumulated_time: u64, // In seconds. For Pending: sum of logs. For Completed: actual_duration.
-    pub today_accumulated_time: u64, // In seconds. Work done strictly today.
+    pub today_accumulated_time: u64, // In seconds. Tracked work done strictly today.
+    pub today_untracked_time: u64, // In seconds. Manual effort of a task completed today without time logs.
     pub remaining_estimate: f64, // In hours. Estimate - Accumulated.
     pub fit: Option<bool>,   // Fits in today's remaining capacity?
+    #[serde(default)]
+    pub blocked: bool,       // A dependency is still pending. Set by the service, which sees all tasks.
+    #[serde(default)]
+    pub overdue: bool,       // Pending and past its due date when the DTO was made.
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     
@@ -27,53 +47,44 @@ pub struct TaskDto {
     pub score: f64,
 }
 
+/// A running timer older than this was most likely forgotten.
+pub const FORGOTTEN_TIMER_SECS: u64 = 8 * 3600;
+
 impl TaskDto {
+    /// Same as [`Task::age_days`], for callers that only hold the DTO.
+    pub fn age_days(&self, now: DateTime<Utc>) -> i64 {
+        (now - self.created_at).num_days()
+    }
+
+    /// Whether the running timer has been open longer than a working day.
+    pub fn has_forgotten_timer(&self) -> bool {
+        self.is_tracking && self.open_session_time > FORGOTTEN_TIMER_SECS
+    }
+
     pub fn from_entity(task: Task, score: f64) -> Self {
-        let now = Utc::now();
-        let today = now.date_naive();
+        Self::from_entity_at(task, score, Utc::now())
+    }
 
-        // Helper to calculate time spent strictly today
+    /// `from_entity` with the running and "today" times measured as of `now`.
+    pub fn from_entity_at(task: Task, score: f64, now: DateTime<Utc>) -> Self {
+        let today = now.with_timezone(&Local).date_naive();
+        let day_start = start_of_day(now);
+        // 36h past midnight is always inside the next local day, whatever DST does
+        let day_end = start_of_day(day_start + Duration::hours(36));
+
+        // Only the part of each session inside today's local midnight-to-midnight window,
+        // so work across midnight is split between the two days
         let calc_today_time = |logs: &Vec<crate::model::task::TimeLog>| -> u64 {
-            let mut today_sum = 0;
-            for log in logs {
-                let start_local = DateTime::<Local>::from(log.start);
-                let start_date = start_local.date_naive();
-                
-                // Simplify: just check if log started today. 
-                // Advanced: if log spans days, we should split. 
-                // For now, let's stick to start date logic as per previous patterns or user intent.
-                // But user wanted "today's work". Let's handle simple overlap.
-                
-                if let Some(end) = log.end {
-                     let end_local = DateTime::<Local>::from(end);
-                     let end_date = end_local.date_naive();
-                     // If both today
-                     if start_date == today && end_date == today {
-                         if let Ok(d) = end.signed_duration_since(log.start).to_std() {
-                             today_sum += d.as_secs();
-                         }
-                     } else if start_date == today {
-                         // Starts today, ends later? (unlikely for short tasks but possible)
-                         // Just count it.
-                         if let Ok(d) = end.signed_duration_since(log.start).to_std() {
-                             today_sum += d.as_secs();
-                         }
-                     }
-                     // If ends today but started yesterday, we might miss it.
-                     // Let's improve: split duration.
-                     // But for MVP, `start_date == today` is a reasonable approximation for daily logs.
-                } else if start_date == today {
-                    // Running task started today
-                    let duration = now.signed_duration_since(log.start).num_seconds();
-                    if duration > 0 {
-                        today_sum += duration as u64;
-                    }
-                }
-            }
-            today_sum
+            logs.iter()
+                .map(|log| {
+                    let start = log.start.max(day_start);
+                    let end = log.end.unwrap_or(now).min(day_end);
+                    end.signed_duration_since(start).num_seconds().max(0) as u64
+                })
+                .sum()
         };
 
-        let (status_str, is_tracking, accumulated_time, today_time, completed_at) = match &task.state {
+        let (status_str, is_tracking, accumulated_time, today_time, today_untracked, completed_at) = match &task.state {
             TaskState::Pending { time_logs } => {
                 let tracking = time_logs.last().map(|l| l.end.is_none()).unwrap_or(false);
                 let mut total = 0;
@@ -86,16 +97,12 @@ impl TaskDto {
                 
                 let today_sum = calc_today_time(time_logs);
                 
-                ("Pending", tracking, total, today_sum, None)
+                ("Pending", tracking, total, today_sum, 0, None)
             },
             TaskState::Completed { completed_at, time_logs, actual } => {
-                let total = if let Some(act_str) = actual {
-                     // Try to parse as float days
-                     if let Ok(days) = act_str.parse::<f64>() {
-                         (days * 8.0 * 3600.0) as u64
-                     } else {
-                         0 
-                     }
+                let total = if actual.is_some() {
+                    // Read like an estimate (`2h`, `1d`, bare days); unparseable counts as 0
+                    (parse_est_hours(actual) * 3600.0) as u64
                 } else if !time_logs.is_empty() {
                     let mut sum = 0;
                     for log in time_logs {
@@ -110,27 +117,33 @@ impl TaskDto {
                     0
                 };
                 
-                let today_sum = if !time_logs.is_empty() {
-                    calc_today_time(time_logs)
+                // Without logs the manual effort has no position in time; it is only
+                // attributable to the completion day, and callers decide whether to count it.
+                let (today_sum, untracked) = if !time_logs.is_empty() {
+                    (calc_today_time(time_logs), 0)
                 } else {
-                    // Logic for manual entry attribution to today? 
-                    // If completed today, attribute it all? 
-                    // This is tricky without logs. For now, if no logs but completed today, count all.
                     let completed_local = DateTime::<Local>::from(*completed_at);
                     if completed_local.date_naive() == today {
-                         total
+                        (0, total)
                     } else {
-                        0
+                        (0, 0)
                     }
                 };
                 
-                ("Completed", false, total, today_sum, Some(*completed_at))
+                ("Completed", false, total, today_sum, untracked, Some(*completed_at))
             },
-            TaskState::Deleted => {
-                ("Deleted", false, 0, 0, None)
+            TaskState::Deleted { .. } => {
+                ("Deleted", false, 0, 0, 0, None)
             }
         };
         
+        let overdue = task.is_overdue(now);
+
+        let open_session_time = task.time_logs().last()
+            .filter(|l| l.end.is_none())
+            .map(|l| now.signed_duration_since(l.start).num_seconds().max(0) as u64)
+            .unwrap_or(0);
+
         // Calculate remaining estimate
         let est_hours = crate::service::task_service::parse_est_hours(&task.estimate);
         let accumulated_hours = accumulated_time as f64 / 3600.0;
@@ -144,12 +157,21 @@ impl TaskDto {
             project: task.project,
             estimate: task.estimate,
             description: task.description,
+            url: task.url,
+            remind_before: task.remind_before,
+            tags: task.tags,
+            recurrence: task.recurrence,
+            depends_on: task.depends_on,
             status: status_str.to_string(),
             is_tracking,
+            open_session_time,
             accumulated_time,
             today_accumulated_time: today_time,
+            today_untracked_time: today_untracked,
             remaining_estimate: remaining_hours,
             fit: None, // Logic handled by UseCase
+            blocked: false,
+            overdue,
             created_at: task.created_at,
             completed_at,
             score,
@@ -163,6 +185,10 @@ pub struct HistoryStats {
     pub total_act_hours: f64,
     #[serde(default)]
     pub meeting_hours: f64,
+    /// Estimate credited to tasks still in progress (see `StatsConfig::in_progress_estimates`).
+    /// Kept apart from `total_est_hours`, which only counts completed work.
+    #[serde(default)]
+    pub in_progress_est_hours: f64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -180,3 +206,130 @@ pub struct WeeklyHistory {
     pub days: Vec<DailyHistory>,
     pub stats: HistoryStats,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TodaySummary {
+    pub date: String, // YYYY-MM-DD
+    pub tasks: Vec<TaskDto>,
+    pub stats: DailyPlanStats,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ActivitySummary {
+    pub completed: usize,
+    pub tracked_seconds: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ImportReport {
+    pub added: usize,
+    pub updated: usize,
+    pub skipped: usize, // Already present and left as they were
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TimeReportEntry {
+    pub task: TaskDto,
+    pub seconds: u64, // Tracked time inside the requested window
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StaleTaskEntry {
+    pub task: TaskDto,
+    pub last_activity: Option<DateTime<Utc>>, // None when never tracked
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeletedTaskEntry {
+    pub task: TaskDto,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReminderEntry {
+    pub task: TaskDto,
+    pub remind_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct EstimateSuggestion {
+    pub estimate: String,   // Ready to store on the task, e.g. "3.5h"
+    pub average_hours: f64, // Mean actual duration of the matching completed tasks
+    pub samples: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct YearSummary {
+    pub year: i32,
+    pub stats: HistoryStats,
+    pub active_weeks: usize,
+    pub busiest_week: Option<(u32, f64)>,   // (ISO week, actual hours)
+    pub top_project: Option<(String, f64)>, // (project, actual hours)
+    pub completed_count: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::task::TimeLog;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn tracking_for(hours: i64) -> TaskDto {
+        let mut task = Task::new("Timer".to_string(), None);
+        task.state = TaskState::Pending {
+            time_logs: vec![TimeLog { start: Utc::now() - Duration::hours(hours), end: None }],
+        };
+        TaskDto::from_entity(task, 0.0)
+    }
+
+    #[test]
+    fn test_open_session_time_and_forgotten_timer() {
+        let recent = tracking_for(1);
+        assert!(recent.open_session_time >= 3600 && recent.open_session_time < 3700);
+        assert!(!recent.has_forgotten_timer());
+
+        assert!(tracking_for(9).has_forgotten_timer());
+
+        let idle = TaskDto::from_entity(Task::new("Idle".to_string(), None), 0.0);
+        assert_eq!(idle.open_session_time, 0);
+        assert!(!idle.has_forgotten_timer());
+    }
+
+    #[test]
+    fn test_manual_actual_reads_units() {
+        let actual = |effort: &str| {
+            let mut task = Task::new("Done".to_string(), None);
+            task.complete(Some(effort.to_string()));
+            TaskDto::from_entity(task, 0.0).accumulated_time
+        };
+        assert_eq!(actual("2h"), 2 * 3600);
+        assert_eq!(actual("1d"), 8 * 3600);
+        assert_eq!(actual("0.5"), 4 * 3600);
+        assert_eq!(actual("lots"), 0);
+    }
+
+    #[test]
+    fn test_today_time_clips_sessions_at_midnight() {
+        let local = |d: u32, h: u32, m: u32| {
+            let naive = NaiveDate::from_ymd_opt(2025, 1, d).unwrap().and_hms_opt(h, m, 0).unwrap();
+            Local.from_local_datetime(&naive).unwrap().with_timezone(&Utc)
+        };
+        let now = local(15, 12, 0);
+        let with_logs = |logs: Vec<TimeLog>| {
+            let mut task = Task::new("Late night".to_string(), None);
+            task.state = TaskState::Pending { time_logs: logs };
+            TaskDto::from_entity_at(task, 0.0, now)
+        };
+
+        // 23:30 yesterday to 00:30 today: only the half hour after midnight is today's
+        let across = with_logs(vec![TimeLog { start: local(14, 23, 30), end: Some(local(15, 0, 30)) }]);
+        assert_eq!(across.today_accumulated_time, 30 * 60);
+        assert_eq!(across.accumulated_time, 60 * 60);
+
+        // Entirely yesterday counts nothing; a running timer counts up to now
+        let yesterday = with_logs(vec![TimeLog { start: local(14, 9, 0), end: Some(local(14, 10, 0)) }]);
+        assert_eq!(yesterday.today_accumulated_time, 0);
+        let running = with_logs(vec![TimeLog { start: local(14, 23, 0), end: None }]);
+        assert_eq!(running.today_accumulated_time, 12 * 3600);
+    }
+}
@@ -2,3 +2,5 @@ pub mod daily_log_service;
 pub mod dto;
 pub mod task_service;
 pub mod archive_service;
+pub mod backup_service;
+pub mod resolve;
@@ -0,0 +1,93 @@
+use std::fmt;
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use crate::model::task::Task;
+use crate::repository::TaskRepository;
+
+/// Why a typed ID prefix did not name exactly one task.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolveError {
+    NotFound { prefix: String },
+    Ambiguous { prefix: String, matches: Vec<Uuid> },
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResolveError::NotFound { prefix } => write!(f, "no task matches '{}'", prefix),
+            ResolveError::Ambiguous { prefix, matches } => {
+                let ids: Vec<String> = matches.iter().map(|id| id.to_string()[..8].to_string()).collect();
+                write!(
+                    f,
+                    "'{}' is ambiguous, it matches {} tasks ({}); use a longer prefix",
+                    prefix,
+                    matches.len(),
+                    ids.join(", ")
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+/// Turns a typed ID prefix (e.g. the 8 characters `list` shows) into the full ID.
+/// Errors carry a [`ResolveError`], so callers can tell not-found from ambiguous.
+pub fn resolve_task_id(repo: &impl TaskRepository, prefix: &str) -> Result<Uuid> {
+    resolve_task_id_where(repo, prefix, |_| true)
+}
+
+/// Like [`resolve_task_id`], only considering tasks accepted by `filter`
+/// (e.g. only pending ones for `done`, only deleted ones for restoring).
+pub fn resolve_task_id_where(
+    repo: &impl TaskRepository,
+    prefix: &str,
+    filter: impl Fn(&Task) -> bool,
+) -> Result<Uuid> {
+    let wanted = prefix.trim().to_lowercase();
+    let matches: Vec<Uuid> = repo
+        .list()?
+        .iter()
+        .filter(|t| filter(t) && t.id.to_string().starts_with(&wanted))
+        .map(|t| t.id)
+        .collect();
+
+    match matches.as_slice() {
+        [id] => Ok(*id),
+        [] => Err(ResolveError::NotFound { prefix: prefix.to_string() }.into()),
+        _ => Err(ResolveError::Ambiguous { prefix: prefix.to_string(), matches }.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::MemoryTaskRepository;
+
+    #[test]
+    fn test_resolve_task_id() {
+        let repo = MemoryTaskRepository::new();
+        let a = Task::new("A".to_string(), None);
+        let b = Task::new("B".to_string(), None);
+        repo.create(a.clone()).unwrap();
+        repo.create(b).unwrap();
+
+        let full = a.id.to_string();
+        assert_eq!(resolve_task_id(&repo, &full[..8]).unwrap(), a.id);
+        assert_eq!(resolve_task_id(&repo, &full[..8].to_uppercase()).unwrap(), a.id);
+        assert_eq!(resolve_task_id_where(&repo, &full[..8], |t| t.name == "B").unwrap_err()
+            .downcast_ref::<ResolveError>(), Some(&ResolveError::NotFound { prefix: full[..8].to_string() }));
+
+        let err = resolve_task_id(&repo, "").unwrap_err();
+        match err.downcast_ref::<ResolveError>() {
+            Some(ResolveError::Ambiguous { matches, .. }) => assert_eq!(matches.len(), 2),
+            other => panic!("expected an ambiguous match, got {:?}", other),
+        }
+        assert!(matches!(
+            resolve_task_id(&repo, "not-an-id").unwrap_err().downcast_ref::<ResolveError>(),
+            Some(ResolveError::NotFound { .. })
+        ));
+    }
+}
@@ -1,29 +1,180 @@
+use crate::model::estimate::Estimate;
 use crate::model::task::{Task, Priority, TaskState};
-use crate::repository::TaskRepository;
+use crate::repository::{FileStatsRepository, TaskRepository};
+use crate::service::archive_service::{credit_completed_task, uncredit_completed_task};
+use crate::service::resolve::resolve_task_id_where;
 
-use crate::service::dto::TaskDto;
-use chrono::Utc;
-use anyhow::Result;
+use crate::service::dto::{ActivitySummary, DeletedTaskEntry, EstimateSuggestion, ImportReport, ReminderEntry, StaleTaskEntry, TaskDto, TimeReportEntry};
+use crate::clock::{Clock, SystemClock};
+use crate::config::ScoringConfig;
+use crate::time::{parse_human_date, start_of_day};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::OnceLock;
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Completed tasks needed before `suggest_estimate` trusts their average.
+pub const MIN_ESTIMATE_SAMPLES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SortStrategy {
+    #[default]
     Urgency,
     Priority,
+    #[serde(rename = "due")]
     DueDate,
 }
 
-impl Default for SortStrategy {
-    fn default() -> Self {
-        SortStrategy::Urgency
+/// A scoring formula: higher scores sort first. The built-in `SortStrategy` values are
+/// strategies too, and any `Fn(&Task) -> f64` closure works as one.
+pub trait ScoringStrategy: Send + Sync {
+    fn score(&self, task: &Task) -> f64;
+}
+
+/// Due date, priority, age and estimate combined (the default order).
+pub struct UrgencyScoring;
+
+/// Priority only.
+pub struct PriorityScoring;
+
+/// Earliest due date first; tasks without a due date last.
+pub struct DueDateScoring;
+
+impl ScoringStrategy for UrgencyScoring {
+    fn score(&self, task: &Task) -> f64 {
+        calculate_urgency(task, None)
     }
 }
 
-// Coefficients
-const COEFFICIENT_DUE: f64 = 12.0;
-const COEFFICIENT_PRIORITY: f64 = 6.0;
-const COEFFICIENT_AGE: f64 = 2.0;
-const COEFFICIENT_ESTIMATE: f64 = 5.0;
+impl ScoringStrategy for PriorityScoring {
+    fn score(&self, task: &Task) -> f64 {
+        calculate_priority_score(task)
+    }
+}
+
+impl ScoringStrategy for DueDateScoring {
+    fn score(&self, task: &Task) -> f64 {
+        calculate_due_score(task)
+    }
+}
+
+impl ScoringStrategy for SortStrategy {
+    fn score(&self, task: &Task) -> f64 {
+        match self {
+            SortStrategy::Urgency => UrgencyScoring.score(task),
+            SortStrategy::Priority => PriorityScoring.score(task),
+            SortStrategy::DueDate => DueDateScoring.score(task),
+        }
+    }
+}
+
+impl<F: Fn(&Task) -> f64 + Send + Sync> ScoringStrategy for F {
+    fn score(&self, task: &Task) -> f64 {
+        self(task)
+    }
+}
+
+/// Due-date window for `list --due`. Tasks without a due date never match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DueFilter {
+    /// Due before the end of today, overdue included
+    Today,
+    /// Due within the next 7 days, overdue included
+    Week,
+    /// Pending and already past due
+    Overdue,
+    /// Due on or before an explicit date
+    Before(DateTime<Utc>),
+}
+
+impl DueFilter {
+    /// Accepts `today`, `week`, `overdue`, or anything `parse_human_date` understands.
+    pub fn parse(input: &str) -> Result<Self> {
+        match input.to_lowercase().as_str() {
+            "today" | "tod" => Ok(DueFilter::Today),
+            "week" => Ok(DueFilter::Week),
+            "overdue" => Ok(DueFilter::Overdue),
+            _ => Ok(DueFilter::Before(parse_human_date(input)?)),
+        }
+    }
+
+    pub fn matches(&self, task: &TaskDto, now: DateTime<Utc>) -> bool {
+        let Some(due) = task.due else {
+            return false;
+        };
+        match self {
+            DueFilter::Today => due < start_of_day(now) + Duration::days(1),
+            DueFilter::Week => due <= now + Duration::days(7),
+            DueFilter::Overdue => task.status == "Pending" && due < now,
+            DueFilter::Before(bound) => due <= *bound,
+        }
+    }
+}
+
+/// Task status for `list --status`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatusFilter {
+    Pending,
+    Completed,
+    Deleted,
+}
+
+impl StatusFilter {
+    /// Accepts `pending`, `completed` or `deleted`, in any case.
+    pub fn parse(input: &str) -> Result<Self> {
+        match input.to_lowercase().as_str() {
+            "pending" => Ok(StatusFilter::Pending),
+            "completed" => Ok(StatusFilter::Completed),
+            "deleted" => Ok(StatusFilter::Deleted),
+            _ => Err(anyhow!("Unknown status '{}' (valid: pending, completed, deleted)", input)),
+        }
+    }
+
+    pub fn matches(&self, task: &TaskDto) -> bool {
+        let wanted = match self {
+            StatusFilter::Pending => "Pending",
+            StatusFilter::Completed => "Completed",
+            StatusFilter::Deleted => "Deleted",
+        };
+        task.status.eq_ignore_ascii_case(wanted)
+    }
+}
+
+/// What `import` does with a task whose ID already exists.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum MergeStrategy {
+    /// Keep the existing task
+    Skip,
+    /// Replace the existing task with the imported one
+    Overwrite,
+}
+
+impl MergeStrategy {
+    /// Accepts `skip` or `overwrite`, in any case.
+    pub fn parse(input: &str) -> Result<Self> {
+        match input.to_lowercase().as_str() {
+            "skip" => Ok(MergeStrategy::Skip),
+            "overwrite" => Ok(MergeStrategy::Overwrite),
+            _ => Err(anyhow!("Unknown merge strategy '{}' (valid: skip, overwrite)", input)),
+        }
+    }
+}
+
+static SCORING_CONFIG: OnceLock<ScoringConfig> = OnceLock::new();
+
+/// Sets the scoring tunables for the rest of the process (from `config.toml`).
+/// Later calls are ignored; without a call the defaults apply.
+pub fn set_scoring_config(config: ScoringConfig) {
+    let _ = SCORING_CONFIG.set(config);
+}
+
+fn scoring_config() -> &'static ScoringConfig {
+    SCORING_CONFIG.get_or_init(ScoringConfig::default)
+}
 
 pub struct TaskService<R: TaskRepository> {
     pub repo: R, // Making repo public so UseCase can access it, or expose get_all methods. UseCases usually access Repos directly. 
@@ -31,27 +182,109 @@ pub struct TaskService<R: TaskRepository> {
                  // Wait, I implemented HistoryUseCase to take &R. 
                  // So TaskService doesn't need to expose repo if UseCase gets repo instance separately. 
                  // OR TaskService exposes repo. Let's make it pub for now or just allow UseCase to have the repo reference passed in main.
+    live_stats: Option<FileStatsRepository>,
+    urgency: Option<Box<dyn ScoringStrategy>>,
+    clock: Box<dyn Clock>,
 }
 
 impl<R: TaskRepository> TaskService<R> {
     pub fn new(repo: R) -> Self {
-        Self { repo }
+        Self { repo, live_stats: None, urgency: None, clock: Box::new(SystemClock) }
+    }
+
+    /// Reads "now" from `clock` instead of the system time (scores, overdue, reports, DTO times).
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Credits completions to the monthly stats immediately instead of at archive time.
+    /// Each task is recorded by id, so archiving it later does not count it again;
+    /// reopening or deleting a completed task takes its credit back out.
+    pub fn with_live_stats(mut self, stats_repo: FileStatsRepository) -> Self {
+        self.live_stats = Some(stats_repo);
+        self
+    }
+
+    /// Replaces the built-in urgency formula behind `get_sorted_tasks(SortStrategy::Urgency)`.
+    pub fn with_scoring_strategy(mut self, strategy: Box<dyn ScoringStrategy>) -> Self {
+        self.urgency = Some(strategy);
+        self
+    }
+
+    /// The urgency in effect: the custom strategy if one was set, else the built-in formula
+    /// as of the service clock.
+    fn urgency_score(&self, task: &Task) -> f64 {
+        match &self.urgency {
+            Some(custom) => custom.score(task),
+            None => calculate_urgency(task, Some(self.clock.now())),
+        }
+    }
+
+    fn sort_by_urgency(&self, tasks: &mut [Task]) {
+        match &self.urgency {
+            Some(custom) => sort_tasks_by(tasks, custom.as_ref()),
+            None => {
+                let now = self.clock.now();
+                sort_tasks_by(tasks, &move |t: &Task| calculate_urgency(t, Some(now)));
+            }
+        }
+    }
+
+    fn to_dto(&self, task: Task) -> TaskDto {
+        let score = self.urgency_score(&task);
+        TaskDto::from_entity_at(task, score, self.clock.now())
+    }
+
+    fn credit_completion(&self, task: &Task) -> Result<()> {
+        match &self.live_stats {
+            Some(stats_repo) => credit_completed_task(stats_repo, task),
+            None => Ok(()),
+        }
+    }
+
+    fn uncredit_completion(&self, task: &Task) -> Result<()> {
+        match &self.live_stats {
+            Some(stats_repo) => uncredit_completed_task(stats_repo, task),
+            None => Ok(()),
+        }
     }
 
     pub fn create_task(&self, task: Task) -> Result<TaskDto> {
         let created = self.repo.create(task)?;
-        let score = calculate_score(&created, SortStrategy::Urgency);
-        Ok(TaskDto::from_entity(created, score))
+        Ok(self.to_dto(created))
     }
 
+    /// Pending and completed tasks; deleted ones only show up in `deleted_tasks`.
     pub fn get_sorted_tasks(&self, strategy: SortStrategy) -> Result<Vec<TaskDto>> {
-        let mut tasks = self.repo.list()?;
-        sort_tasks(&mut tasks, strategy);
+        match (&self.urgency, strategy) {
+            (Some(custom), SortStrategy::Urgency) => self.get_sorted_tasks_by(custom.as_ref()),
+            (None, SortStrategy::Urgency) => {
+                let now = self.clock.now();
+                self.get_sorted_tasks_by(&move |t: &Task| calculate_urgency(t, Some(now)))
+            },
+            _ => self.sorted_dtos(&strategy, |tasks| sort_tasks(tasks, strategy)),
+        }
+    }
+
+    /// Like `get_sorted_tasks`, ordered and scored by any strategy.
+    pub fn get_sorted_tasks_by(&self, strategy: &dyn ScoringStrategy) -> Result<Vec<TaskDto>> {
+        self.sorted_dtos(strategy, |tasks| sort_tasks_by(tasks, strategy))
+    }
+
+    fn sorted_dtos(&self, strategy: &dyn ScoringStrategy, sort: impl FnOnce(&mut [Task])) -> Result<Vec<TaskDto>> {
+        let mut tasks: Vec<Task> = self.repo.list()?.into_iter()
+            .filter(|t| !matches!(t.state, TaskState::Deleted { .. }))
+            .collect();
+        sort(&mut tasks);
+        let pending = pending_ids(&tasks);
         
         // Convert to DTOs
+        let now = self.clock.now();
         let dtos = tasks.into_iter().map(|t| {
-            let score = calculate_score(&t, strategy);
-            TaskDto::from_entity(t, score)
+            let score = strategy.score(&t);
+            let blocked = t.is_blocked(&pending);
+            TaskDto { blocked, ..TaskDto::from_entity_at(t, score, now) }
         }).collect();
         
         Ok(dtos)
@@ -65,8 +298,44 @@ impl<R: TaskRepository> TaskService<R> {
         self.repo.update(task)
     }
 
+    /// Moves a task to the trash; `restore_task` brings it back.
     pub fn delete_task(&self, id: &Uuid) -> Result<()> {
-        self.repo.delete(id)
+        let mut task = self.repo.get(id)?;
+        self.uncredit_completion(&task)?;
+        task.delete();
+        self.repo.update(&task)
+    }
+
+    /// Puts a task back exactly as `before` had it (the TUI's undo). With live stats the
+    /// current completion is uncredited and `before`'s completion, if any, credited again.
+    pub fn revert_task(&self, before: &Task) -> Result<()> {
+        let current = self.repo.get(&before.id)?;
+        self.uncredit_completion(&current)?;
+        self.repo.update(before)?;
+        self.credit_completion(before)
+    }
+
+    /// Deleted tasks, most recently deleted first.
+    pub fn deleted_tasks(&self) -> Result<Vec<DeletedTaskEntry>> {
+        let mut entries: Vec<DeletedTaskEntry> = self.repo.list()?.into_iter()
+            .filter_map(|t| {
+                let deleted_at = t.deleted_at()?;
+                Some(DeletedTaskEntry { task: TaskDto::from_entity_at(t, 0.0, self.clock.now()), deleted_at })
+            })
+            .collect();
+        entries.sort_by_key(|e| std::cmp::Reverse(e.deleted_at));
+        Ok(entries)
+    }
+
+    /// Puts a deleted task back as pending. Its earlier time logs and completion are gone,
+    /// so there is nothing to re-credit.
+    pub fn restore_task(&self, id: &Uuid) -> Result<TaskDto> {
+        let mut task = self.repo.get(id)?;
+        if !task.restore() {
+            return Err(anyhow!("Task {} is not deleted", id));
+        }
+        self.repo.update(&task)?;
+        Ok(self.to_dto(task))
     }
     
     // State management methods
@@ -83,51 +352,346 @@ impl<R: TaskRepository> TaskService<R> {
         self.repo.update(&task)
     }
 
+    /// Stops every running timer and returns the tasks that were stopped.
+    pub fn stop_all_tracking(&self) -> Result<Vec<TaskDto>> {
+        let mut stopped = Vec::new();
+        for mut task in self.repo.list()? {
+            if task.is_tracking() {
+                task.stop_tracking();
+                self.repo.update(&task)?;
+                stopped.push(self.to_dto(task));
+            }
+        }
+        Ok(stopped)
+    }
+
     pub fn complete_task(&self, id: &Uuid) -> Result<()> {
         let mut task = self.repo.get(id)?;
+        let was_pending = matches!(task.state, TaskState::Pending { .. });
         task.complete(None);
-        self.repo.update(&task)
+        self.repo.update(&task)?;
+        self.credit_completion(&task)?;
+        if was_pending {
+            self.spawn_next_occurrence(&task)?;
+        }
+        Ok(())
     }
 
-    pub fn complete_task_with_effort(&self, id: &Uuid, effort: String) -> Result<()> {
+    /// Adds the next instance of a recurring task; the completed one stays in history.
+    fn spawn_next_occurrence(&self, task: &Task) -> Result<()> {
+        if let Some(next) = task.next_occurrence(self.clock.now()) {
+            self.repo.create(next)?;
+        }
+        Ok(())
+    }
+
+    /// Completes with a manual effort in days. With `log_effort`, an untracked task also gets
+    /// the effort as a time log ending now (see `Task::complete_with_logged_effort`).
+    pub fn complete_task_with_effort(&self, id: &Uuid, effort: String, log_effort: bool) -> Result<()> {
         let mut task = self.repo.get(id)?;
-        let effort_opt = if effort.trim().is_empty() { None } else { Some(effort) };
-        task.complete(effort_opt);
-        self.repo.update(&task)
+        let was_pending = matches!(task.state, TaskState::Pending { .. });
+        if effort.trim().is_empty() {
+            task.complete(None);
+        } else if log_effort {
+            task.complete_with_logged_effort(effort, self.clock.now())?;
+        } else {
+            task.complete(Some(effort));
+        }
+        self.repo.update(&task)?;
+        self.credit_completion(&task)?;
+        if was_pending {
+            self.spawn_next_occurrence(&task)?;
+        }
+        Ok(())
+    }
+
+    pub fn time_spent_between(&self, id: &Uuid, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Duration> {
+        let task = self.repo.get(id)?;
+        Ok(task.time_spent_between(start, end, self.clock.now()))
+    }
+
+    /// Tracked time per task inside [start, end), optionally limited to one project.
+    /// Tasks without time in the window are left out.
+    pub fn time_report(&self, start: DateTime<Utc>, end: DateTime<Utc>, project: Option<&str>) -> Result<Vec<TimeReportEntry>> {
+        let now = self.clock.now();
+        let mut tasks = self.repo.list()?;
+        self.sort_by_urgency(&mut tasks);
+
+        let entries = tasks.into_iter()
+            .filter(|t| in_project(t, project))
+            .filter_map(|t| {
+                let spent = t.time_spent_between(start, end, now).num_seconds();
+                if spent <= 0 {
+                    return None;
+                }
+                Some(TimeReportEntry {
+                    task: self.to_dto(t),
+                    seconds: spent as u64,
+                })
+            })
+            .collect();
+
+        Ok(entries)
+    }
+
+    /// Tasks completed and time tracked between `since` and `now`.
+    pub fn activity_since(&self, since: DateTime<Utc>, now: DateTime<Utc>) -> Result<ActivitySummary> {
+        let mut summary = ActivitySummary::default();
+        for task in self.repo.list()? {
+            if let TaskState::Completed { completed_at, .. } = task.state {
+                if completed_at >= since && completed_at <= now {
+                    summary.completed += 1;
+                }
+            }
+            summary.tracked_seconds += task.time_spent_between(since, now, now).num_seconds().max(0) as u64;
+        }
+        Ok(summary)
+    }
+
+    /// Average actual duration of completed tasks like `id`: those containing `pattern` in their
+    /// name when given, otherwise those in the same project, otherwise those containing the
+    /// task's own name. `None` until at least `MIN_ESTIMATE_SAMPLES` such tasks have an actual.
+    pub fn suggest_estimate(&self, id: &Uuid, pattern: Option<&str>) -> Result<Option<EstimateSuggestion>> {
+        let task = self.repo.get(id)?;
+        let project = if pattern.is_none() { task.project.as_deref() } else { None };
+        let needle = pattern.unwrap_or(&task.name).to_lowercase();
+        let similar = |t: &Task| match project {
+            Some(p) => in_project(t, Some(p)),
+            None => t.name.to_lowercase().contains(&needle),
+        };
+
+        let actual_hours: Vec<f64> = self.repo.list()?.into_iter()
+            .filter(|t| t.id != task.id && matches!(t.state, TaskState::Completed { .. }))
+            .filter(|t| similar(t))
+            .map(|t| TaskDto::from_entity_at(t, 0.0, self.clock.now()).accumulated_time as f64 / 3600.0)
+            .filter(|hours| *hours > 0.0)
+            .collect();
+
+        if actual_hours.len() < MIN_ESTIMATE_SAMPLES {
+            return Ok(None);
+        }
+        let average_hours = actual_hours.iter().sum::<f64>() / actual_hours.len() as f64;
+        // Half-hour steps; anything shorter still suggests 0.5h
+        let rounded = ((average_hours * 2.0).round() / 2.0).max(0.5);
+        Ok(Some(EstimateSuggestion {
+            estimate: format!("{}h", rounded),
+            average_hours,
+            samples: actual_hours.len(),
+        }))
+    }
+
+    /// Pending tasks whose reminder time has passed but which aren't due yet, skipping those
+    /// already fired for the same reminder time (`fired`: task ID -> reminder time). Soonest due first.
+    pub fn due_reminders(&self, now: DateTime<Utc>, fired: &BTreeMap<Uuid, DateTime<Utc>>) -> Result<Vec<ReminderEntry>> {
+        let mut entries: Vec<ReminderEntry> = self.repo.list()?.into_iter()
+            .filter(|t| matches!(t.state, TaskState::Pending { .. }))
+            .filter_map(|t| {
+                let remind_at = t.reminder_at()?;
+                let window_open = remind_at <= now && t.due.is_some_and(|due| now < due);
+                (window_open && fired.get(&t.id) != Some(&remind_at))
+                    .then(|| ReminderEntry { task: TaskDto::from_entity_at(t, 0.0, now), remind_at })
+            })
+            .collect();
+        entries.sort_by_key(|e| e.task.due);
+        Ok(entries)
+    }
+
+    /// Pending tasks already past due, optionally limited to one project, most urgent first.
+    pub fn overdue_tasks(&self, project: Option<&str>) -> Result<Vec<TaskDto>> {
+        let now = self.clock.now();
+        let mut tasks: Vec<Task> = self.repo.list()?.into_iter()
+            .filter(|t| t.is_overdue(now) && in_project(t, project))
+            .collect();
+        self.sort_by_urgency(&mut tasks);
+
+        Ok(tasks.into_iter()
+            .map(|t| self.to_dto(t))
+            .collect())
+    }
+
+    /// Moves every overdue pending task (optionally only in `project`) to `new_due`
+    /// in a single write. Returns how many tasks were moved.
+    pub fn reschedule_overdue(&self, new_due: DateTime<Utc>, project: Option<&str>) -> Result<usize> {
+        let now = self.clock.now();
+        let rescheduled: Vec<Task> = self.repo.list()?.into_iter()
+            .filter(|t| t.is_overdue(now) && in_project(t, project))
+            .map(|mut t| {
+                t.due = Some(new_due);
+                t
+            })
+            .collect();
+
+        if !rescheduled.is_empty() {
+            self.repo.update_many(&rescheduled)?;
+        }
+        Ok(rescheduled.len())
+    }
+
+    /// Resolves the `dep:` prefix of a dependency for `task_id` among the tasks not deleted.
+    /// Refuses the task itself and a dependency that already (indirectly) depends on it.
+    pub fn resolve_dependency(&self, task_id: &Uuid, prefix: &str) -> Result<Uuid> {
+        let dep_id = resolve_task_id_where(&self.repo, prefix, |t| !matches!(t.state, TaskState::Deleted { .. }))?;
+        if dep_id == *task_id {
+            return Err(anyhow!("A task can't depend on itself"));
+        }
+
+        let tasks = self.repo.list()?;
+        let mut stack = vec![dep_id];
+        let mut seen = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if id == *task_id {
+                return Err(anyhow!("'{}' already depends on this task; that would be a cycle", prefix));
+            }
+            if seen.insert(id) {
+                if let Some(task) = tasks.iter().find(|t| t.id == id) {
+                    stack.extend(&task.depends_on);
+                }
+            }
+        }
+        Ok(dep_id)
+    }
+
+    /// Adds `tasks` (e.g. from `export`) keeping their IDs. Tasks whose ID already exists
+    /// are skipped or overwritten per `strategy`; within `tasks` the first of a repeated ID wins.
+    pub fn import_tasks(&self, tasks: Vec<Task>, strategy: MergeStrategy) -> Result<ImportReport> {
+        let existing: HashSet<Uuid> = self.repo.list()?.iter().map(|t| t.id).collect();
+        let mut seen = HashSet::new();
+        let mut report = ImportReport::default();
+        let mut updates = Vec::new();
+        let mut additions = Vec::new();
+
+        for task in tasks {
+            if !seen.insert(task.id) {
+                report.skipped += 1;
+            } else if !existing.contains(&task.id) {
+                additions.push(task);
+            } else if strategy == MergeStrategy::Overwrite {
+                updates.push(task);
+            } else {
+                report.skipped += 1;
+            }
+        }
+
+        if !updates.is_empty() {
+            self.repo.update_many(&updates)?;
+        }
+        report.updated = updates.len();
+        for task in additions {
+            self.repo.create(task)?;
+            report.added += 1;
+        }
+        Ok(report)
+    }
+
+    /// Pending tasks created more than `days` ago with no time logged in that window either,
+    /// oldest first. A running timer counts as activity.
+    pub fn stale_tasks(&self, days: u32) -> Result<Vec<StaleTaskEntry>> {
+        let now = self.clock.now();
+        let cutoff = now.checked_sub_signed(Duration::days(days as i64))
+            .ok_or_else(|| anyhow!("{} days reaches back too far", days))?;
+
+        let mut stale: Vec<Task> = self.repo.list()?.into_iter()
+            .filter(|t| matches!(t.state, TaskState::Pending { .. }))
+            .filter(|t| t.age_days(now) >= days as i64)
+            .filter(|t| t.last_activity(now).is_none_or(|last| last < cutoff))
+            .collect();
+        stale.sort_by_key(|t| t.created_at);
+
+        Ok(stale.into_iter()
+            .map(|t| {
+                let last_activity = t.last_activity(now);
+                StaleTaskEntry { task: self.to_dto(t), last_activity }
+            })
+            .collect())
     }
 
     pub fn toggle_status(&self, id: &Uuid) -> Result<()> {
         let mut task = self.repo.get(id)?;
         if matches!(task.state, TaskState::Completed { .. }) {
+             self.uncredit_completion(&task)?;
              task.reopen();
+             self.repo.update(&task)
         } else {
              task.complete(None);
+             self.repo.update(&task)?;
+             self.credit_completion(&task)?;
+             self.spawn_next_occurrence(&task)
         }
-        self.repo.update(&task)
     }
     
     // Sort helper specifically for the service if needed externally, 
     // but better to use the standalone function.
-    pub fn sort(tasks: &mut Vec<Task>, strategy: SortStrategy) {
+    pub fn sort(tasks: &mut [Task], strategy: SortStrategy) {
         sort_tasks(tasks, strategy);
     }
 
 // get_weekly_history, has_daily_log, add_daily_log removed
 }
 
+fn in_project(task: &Task, project: Option<&str>) -> bool {
+    match project {
+        Some(p) => task.project.as_deref().is_some_and(|tp| tp.eq_ignore_ascii_case(p)),
+        None => true,
+    }
+}
+
+/// IDs of the pending tasks among `tasks`, i.e. those that still block their dependents.
+fn pending_ids(tasks: &[Task]) -> HashSet<Uuid> {
+    tasks.iter()
+        .filter(|t| matches!(t.state, TaskState::Pending { .. }))
+        .map(|t| t.id)
+        .collect()
+}
+
+/// What to finish before the `blocked` tasks can start: their pending prerequisites,
+/// followed through blocked ones down to those that can be started now, in `tasks` order.
+/// Empty when the chain only loops back on itself (or `tasks` doesn't hold it).
+pub fn prerequisites_to_finish<'a>(tasks: &'a [TaskDto], blocked: &[&TaskDto]) -> Vec<&'a TaskDto> {
+    let mut stack: Vec<Uuid> = blocked.iter().flat_map(|t| t.depends_on.iter().copied()).collect();
+    let mut seen = HashSet::new();
+    let mut ready = HashSet::new();
+    while let Some(id) = stack.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        let Some(task) = tasks.iter().find(|t| t.id == id && t.status == "Pending") else {
+            continue;
+        };
+        if task.blocked {
+            stack.extend(&task.depends_on);
+        } else {
+            ready.insert(id);
+        }
+    }
+    tasks.iter().filter(|t| ready.contains(&t.id)).collect()
+}
+
+/// Planning hours of an estimate; ranges count as their midpoint. Unparseable or missing is 0.
+/// The one reading of `Task::estimate` that scoring, the daily plan and history share:
+/// `2h` is hours, `1d` and a bare number like `0.5` are days (see [`Estimate::parse`]).
 pub fn parse_est_hours(est_opt: &Option<String>) -> f64 {
-    est_opt.as_ref()
-        .and_then(|s| s.parse::<f64>().ok())
-        .map(|days| days * 8.0)
+    est_opt.as_deref()
+        .and_then(Estimate::parse)
+        .map(|est| est.midpoint_hours())
         .unwrap_or(0.0)
 }
 
 // Standalone functions for pure logic
 
-pub fn sort_tasks(tasks: &mut Vec<Task>, strategy: SortStrategy) {
+/// Sorts by a built-in strategy. Under `Priority`, tasks left tied by priority and
+/// estimate come newest first, so the order doesn't depend on how they are stored.
+pub fn sort_tasks(tasks: &mut [Task], strategy: SortStrategy) {
+    if strategy == SortStrategy::Priority {
+        // `sort_tasks_by` is stable, so this order survives for the ties
+        tasks.sort_by_key(|t| std::cmp::Reverse(t.created_at));
+    }
+    sort_tasks_by(tasks, &strategy);
+}
+
+pub fn sort_tasks_by(tasks: &mut [Task], strategy: &dyn ScoringStrategy) {
     tasks.sort_by(|a, b| {
-        let score_a = calculate_score(a, strategy);
-        let score_b = calculate_score(b, strategy);
+        let score_a = strategy.score(a);
+        let score_b = strategy.score(b);
         match score_b.partial_cmp(&score_a).unwrap_or(std::cmp::Ordering::Equal) {
             std::cmp::Ordering::Equal => {
                  // Break ties by estimate (shorter first)
@@ -153,50 +717,57 @@ pub fn sort_tasks(tasks: &mut Vec<Task>, strategy: SortStrategy) {
 }
 
 pub fn calculate_score(task: &Task, strategy: SortStrategy) -> f64 {
-    match strategy {
-        SortStrategy::Urgency => calculate_urgency(task),
-        SortStrategy::Priority => calculate_priority_score(task),
-        SortStrategy::DueDate => calculate_due_score(task),
-    }
+    strategy.score(task)
 }
 
-fn calculate_urgency(task: &Task) -> f64 {
+/// Urgency under the configured scoring, as of `now` (the current time when `None`).
+pub fn calculate_urgency(task: &Task, now: Option<DateTime<Utc>>) -> f64 {
+    calculate_urgency_with(task, scoring_config(), now.unwrap_or_else(Utc::now))
+}
+
+pub fn calculate_urgency_with(task: &Task, config: &ScoringConfig, now: DateTime<Utc>) -> f64 {
     // Only pending tasks have urgency
     if !matches!(task.state, TaskState::Pending { .. }) {
         return -100.0;
     }
 
     let mut score = 0.0;
-    let now = Utc::now();
 
     if let Some(due) = task.due {
         if due < now {
-            score += COEFFICIENT_DUE * 2.0; 
+            score += config.coefficient_due * 2.0; 
         } else {
             let diff = due - now;
             let days = diff.num_days();
             if days < 7 {
-                score += COEFFICIENT_DUE;
+                score += config.coefficient_due;
                 score += (7.0 - days as f64) * 0.5; 
             } else if days < 14 {
-                score += COEFFICIENT_DUE * 0.5;
+                score += config.coefficient_due * 0.5;
             } else {
-                score += COEFFICIENT_DUE * 0.2;
+                score += config.coefficient_due * 0.2;
             }
         }
     }
 
     match task.priority {
-        Priority::High => score += COEFFICIENT_PRIORITY,
-        Priority::Medium => score += COEFFICIENT_PRIORITY * 0.5,
-        Priority::Low => score += COEFFICIENT_PRIORITY * 0.1,
+        Priority::High => score += config.coefficient_priority,
+        Priority::Medium => score += config.coefficient_priority * 0.5,
+        Priority::Low => score += config.coefficient_priority * 0.1,
     }
 
-    let age = now - task.created_at;
-    let days_old = age.num_days();
+    let days_old = task.age_days(now);
     if days_old > 0 {
-        let age_score = (days_old as f64 / 100.0) * COEFFICIENT_AGE;
-        score += age_score.min(COEFFICIENT_AGE);
+        let age_score = (days_old as f64 / 100.0) * config.coefficient_age;
+        score += age_score.min(config.coefficient_age);
+    }
+
+    // Starvation: the capped age bonus can't lift an old task above a pile of due ones
+    if config.starvation_days > 0
+        && task.priority == config.starvation_priority
+        && days_old >= config.starvation_days as i64
+    {
+        score += config.starvation_boost;
     }
 
     // Estimate scoring
@@ -204,11 +775,11 @@ fn calculate_urgency(task: &Task) -> f64 {
     if est_hours > 0.0 {
         let minutes = est_hours * 60.0;
         if minutes <= 30.0 {
-            score += COEFFICIENT_ESTIMATE;
+            score += config.coefficient_estimate;
         } else if minutes <= 60.0 {
-            score += COEFFICIENT_ESTIMATE * 0.5;
+            score += config.coefficient_estimate * 0.5;
         } else if minutes <= 120.0 {
-            score += COEFFICIENT_ESTIMATE * 0.2;
+            score += config.coefficient_estimate * 0.2;
         }
     }
 
@@ -230,3 +801,462 @@ fn calculate_due_score(task: &Task) -> f64 {
         f64::MIN 
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::model::task::TimeLog;
+    use crate::repository::MemoryTaskRepository;
+
+    #[test]
+    fn test_trash_restore_round_trip() {
+        let service = TaskService::new(MemoryTaskRepository::new());
+        let first = service.create_task(Task::new("First".to_string(), None)).unwrap();
+        let second = service.create_task(Task::new("Second".to_string(), None)).unwrap();
+
+        service.delete_task(&first.id).unwrap();
+        service.delete_task(&second.id).unwrap();
+        assert!(service.get_sorted_tasks(SortStrategy::Urgency).unwrap().is_empty());
+
+        let trash = service.deleted_tasks().unwrap();
+        assert_eq!(trash.iter().map(|e| e.task.id).collect::<Vec<_>>(), vec![second.id, first.id]);
+
+        let restored = service.restore_task(&first.id).unwrap();
+        assert_eq!(restored.status, "Pending");
+        assert!(restored.score > 0.0);
+        let listed = service.get_sorted_tasks(SortStrategy::Urgency).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, first.id);
+        assert_eq!(service.deleted_tasks().unwrap().len(), 1);
+
+        assert!(service.restore_task(&first.id).is_err());
+    }
+
+    #[test]
+    fn test_custom_scoring_strategy() {
+        let mut short = Task::new("Short name".to_string(), None);
+        short.priority = Priority::High;
+        let long = Task::new("A much longer name".to_string(), None);
+        let service = TaskService::new(MemoryTaskRepository::with_tasks(vec![short, long]))
+            .with_scoring_strategy(Box::new(|t: &Task| t.name.len() as f64));
+
+        // The closure replaces urgency, and its value becomes the score
+        let tasks = service.get_sorted_tasks(SortStrategy::Urgency).unwrap();
+        assert_eq!(tasks[0].name, "A much longer name");
+        assert_eq!(tasks[0].score, 18.0);
+
+        // The other built-in strategies are untouched
+        let tasks = service.get_sorted_tasks(SortStrategy::Priority).unwrap();
+        assert_eq!(tasks[0].name, "Short name");
+        assert_eq!(service.get_sorted_tasks_by(&PriorityScoring).unwrap(), tasks);
+    }
+
+    #[test]
+    fn test_priority_sort_breaks_ties_newest_first() {
+        let now = Utc::now();
+        let task = |name: &str, priority: Priority, age_hours: i64| {
+            let mut task = Task::new(name.to_string(), None);
+            task.priority = priority;
+            task.created_at = now - Duration::hours(age_hours);
+            task
+        };
+        let service = TaskService::new(MemoryTaskRepository::with_tasks(vec![
+            task("Old high", Priority::High, 48),
+            task("Low", Priority::Low, 1),
+            task("New high", Priority::High, 2),
+            task("Middle high", Priority::High, 24),
+        ]));
+
+        let names: Vec<String> = service.get_sorted_tasks(SortStrategy::Priority).unwrap()
+            .into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["New high", "Middle high", "Old high", "Low"]);
+    }
+
+    #[test]
+    fn test_prerequisites_to_finish_follows_blocked_chains() {
+        let mut write = Task::new("Write".to_string(), None);
+        let mut review = Task::new("Review".to_string(), None);
+        let outline = Task::new("Outline".to_string(), None);
+        let mut done = Task::new("Research".to_string(), None);
+        done.complete(None);
+        write.depends_on = vec![outline.id, done.id];
+        review.depends_on = vec![write.id];
+        let service = TaskService::new(MemoryTaskRepository::with_tasks(vec![review, write, outline, done]));
+
+        let tasks = service.get_sorted_tasks(SortStrategy::Priority).unwrap();
+        let review = tasks.iter().find(|t| t.name == "Review").unwrap();
+        let names: Vec<&str> = prerequisites_to_finish(&tasks, &[review]).iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["Outline"]);
+
+        // A loop leaves nothing that can be started
+        let mut a = TaskDto::from_entity(Task::new("A".to_string(), None), 0.0);
+        let mut b = TaskDto::from_entity(Task::new("B".to_string(), None), 0.0);
+        (a.depends_on, b.depends_on, a.blocked, b.blocked) = (vec![b.id], vec![a.id], true, true);
+        let looped = [a, b];
+        assert!(prerequisites_to_finish(&looped, &[&looped[0]]).is_empty());
+    }
+
+    #[test]
+    fn test_parse_est_hours_reads_hours_and_days() {
+        let hours = |est: &str| parse_est_hours(&Some(est.to_string()));
+        assert_eq!(hours("2h"), 2.0);
+        assert_eq!(hours("0.5"), 4.0);
+        assert_eq!(hours("1d"), 8.0);
+        assert_eq!(hours("2-4h"), 3.0);
+        assert_eq!(hours("soon"), 0.0);
+        assert_eq!(parse_est_hours(&None), 0.0);
+    }
+
+    #[test]
+    fn test_suggest_estimate_from_history() {
+        let done = |name: &str, project: Option<&str>, actual: &str| {
+            let mut task = Task::new(name.to_string(), None);
+            task.project = project.map(str::to_string);
+            task.complete(Some(actual.to_string()));
+            task
+        };
+        let mut target = Task::new("Weekly report".to_string(), None);
+        target.project = Some("Ops".to_string());
+        let target_id = target.id;
+
+        let service = TaskService::new(MemoryTaskRepository::with_tasks(vec![
+            target,
+            done("Weekly report", Some("Ops"), "0.5"),   // 4h
+            done("Weekly report", Some("ops"), "0.25"),  // 2h
+            done("Rotate keys", Some("Ops"), "0.375"),   // 3h
+            done("Weekly report", Some("Home"), "2"),
+        ]));
+
+        let suggestion = service.suggest_estimate(&target_id, None).unwrap().unwrap();
+        assert_eq!(suggestion.samples, 3);
+        assert_eq!(suggestion.average_hours, 3.0);
+        assert_eq!(suggestion.estimate, "3h");
+        assert!(Estimate::parse(&suggestion.estimate).is_some());
+
+        // A pattern overrides the project; one match is too few to suggest anything
+        assert_eq!(service.suggest_estimate(&target_id, Some("rotate")).unwrap(), None);
+        let by_name = service.suggest_estimate(&target_id, Some("REPORT")).unwrap().unwrap();
+        assert_eq!(by_name.samples, 3);
+    }
+
+    #[test]
+    fn test_urgency_pinned_for_fixed_now() {
+        let now = "2025-01-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let task = |name: &str, priority: Priority, due: Option<DateTime<Utc>>, age_days: i64, estimate: Option<&str>| {
+            let mut task = Task::new(name.to_string(), due);
+            task.priority = priority;
+            task.created_at = now - Duration::days(age_days);
+            task.estimate = estimate.map(str::to_string);
+            task
+        };
+        let mut done = task("Done", Priority::High, None, 0, None);
+        done.complete(None);
+
+        let config = ScoringConfig::default();
+        let cases = [
+            (task("Soon", Priority::High, Some(now + Duration::days(2)), 10, Some("0.5h")), 25.7),
+            (task("Overdue", Priority::Low, Some(now - Duration::days(1)), 1, None), 24.62),
+            (task("Someday", Priority::Medium, None, 0, None), 3.0),
+            (done, -100.0),
+        ];
+        for (task, expected) in cases {
+            let score = calculate_urgency_with(&task, &config, now);
+            assert!((score - expected).abs() < 1e-9, "{}: {} != {}", task.name, score, expected);
+        }
+    }
+
+    #[test]
+    fn test_service_clock_drives_overdue_and_scores() {
+        let now = "2025-01-15T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut task = Task::new("Report".to_string(), Some(now + Duration::hours(1)));
+        task.created_at = now - Duration::days(10);
+        let repo = MemoryTaskRepository::with_tasks(vec![task]);
+
+        let before = TaskService::new(repo).with_clock(Box::new(FixedClock(now)));
+        assert!(before.overdue_tasks(None).unwrap().is_empty());
+        let score = before.get_sorted_tasks(SortStrategy::Urgency).unwrap()[0].score;
+        assert!((score - 18.7).abs() < 1e-9, "{}", score);
+
+        let after = TaskService::new(before.repo).with_clock(Box::new(FixedClock(now + Duration::days(1))));
+        assert_eq!(after.overdue_tasks(None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_due_reminders_fire_once_per_reminder_time() {
+        let now = Utc::now();
+        let reminded = |name: &str, due: DateTime<Utc>, before: &str| {
+            let mut task = Task::new(name.to_string(), Some(due));
+            task.remind_before = Some(before.to_string());
+            task
+        };
+        let soon = reminded("Soon", now + Duration::minutes(20), "30m");
+        let soon_id = soon.id;
+        let service = TaskService::new(MemoryTaskRepository::with_tasks(vec![
+            soon,
+            reminded("Later", now + Duration::hours(3), "1h"),
+            reminded("Past due", now - Duration::minutes(5), "1h"),
+            Task::new("No reminder".to_string(), Some(now + Duration::minutes(1))),
+        ]));
+
+        let mut fired = BTreeMap::new();
+        let due = service.due_reminders(now, &fired).unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].task.name, "Soon");
+        assert_eq!(due[0].remind_at, now - Duration::minutes(10));
+
+        fired.insert(soon_id, due[0].remind_at);
+        assert!(service.due_reminders(now, &fired).unwrap().is_empty());
+
+        // A new due date means a new reminder
+        let mut task = service.get_task(&soon_id).unwrap();
+        task.due = Some(now + Duration::minutes(25));
+        service.update_task(&task).unwrap();
+        assert_eq!(service.due_reminders(now, &fired).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_revert_task_undoes_completion() {
+        let service = TaskService::new(MemoryTaskRepository::new());
+        let mut task = Task::new("Tracked".to_string(), None);
+        task.start_tracking();
+        task.stop_tracking();
+        let before = task.clone();
+        service.create_task(task).unwrap();
+
+        service.complete_task(&before.id).unwrap();
+        service.revert_task(&before).unwrap();
+        assert_eq!(service.get_task(&before.id).unwrap(), before);
+    }
+
+    #[test]
+    fn test_dependencies_block_until_done() {
+        let service = TaskService::new(MemoryTaskRepository::new());
+        let blocker = service.create_task(Task::new("Blocker".to_string(), None)).unwrap();
+        let mut dependent = Task::new("Dependent".to_string(), None);
+        let prefix = &blocker.id.to_string()[..8];
+        dependent.add_dependency(service.resolve_dependency(&dependent.id, prefix).unwrap());
+        let dependent = service.create_task(dependent).unwrap();
+
+        let blocked = |service: &TaskService<MemoryTaskRepository>| {
+            service.get_sorted_tasks(SortStrategy::Urgency).unwrap().into_iter()
+                .find(|t| t.id == dependent.id).unwrap().blocked
+        };
+        assert!(blocked(&service));
+        service.complete_task(&blocker.id).unwrap();
+        assert!(!blocked(&service));
+
+        // Neither the task itself nor a cycle
+        assert!(service.resolve_dependency(&blocker.id, &blocker.id.to_string()).is_err());
+        assert!(service.resolve_dependency(&blocker.id, &dependent.id.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_import_tasks_merge_strategies() {
+        let service = TaskService::new(MemoryTaskRepository::new());
+        let mut existing = Task::new("Existing".to_string(), None);
+        service.create_task(existing.clone()).unwrap();
+
+        existing.name = "Renamed".to_string();
+        let new = Task::new("New".to_string(), None);
+        let incoming = vec![existing.clone(), new.clone(), new.clone()];
+
+        let report = service.import_tasks(incoming.clone(), MergeStrategy::Skip).unwrap();
+        assert_eq!(report, ImportReport { added: 1, updated: 0, skipped: 2 });
+        assert_eq!(service.get_task(&existing.id).unwrap().name, "Existing");
+
+        let report = service.import_tasks(incoming, MergeStrategy::Overwrite).unwrap();
+        assert_eq!(report, ImportReport { added: 0, updated: 2, skipped: 1 });
+        assert_eq!(service.get_task(&existing.id).unwrap().name, "Renamed");
+        assert_eq!(service.repo.list().unwrap().len(), 2);
+
+        assert!(MergeStrategy::parse("Overwrite").is_ok());
+        assert!(MergeStrategy::parse("merge").is_err());
+    }
+
+    #[test]
+    fn test_reschedule_overdue() {
+        let now = Utc::now();
+        let new_due = now + Duration::days(1);
+        let task_due = |name: &str, due: DateTime<Utc>, project: Option<&str>| {
+            let mut task = Task::new(name.to_string(), Some(due));
+            task.project = project.map(str::to_string);
+            task
+        };
+        let mut done = task_due("Done late", now - Duration::days(3), None);
+        done.complete(None);
+
+        let service = TaskService::new(MemoryTaskRepository::with_tasks(vec![
+            task_due("Late work", now - Duration::days(2), Some("Work")),
+            task_due("Late home", now - Duration::days(1), Some("Home")),
+            task_due("Upcoming", now + Duration::days(5), Some("Work")),
+            done,
+        ]));
+
+        assert_eq!(service.overdue_tasks(Some("work")).unwrap().len(), 1);
+        assert_eq!(service.reschedule_overdue(new_due, Some("work")).unwrap(), 1);
+        assert_eq!(service.reschedule_overdue(new_due, None).unwrap(), 1);
+        assert!(service.overdue_tasks(None).unwrap().is_empty());
+
+        let tasks = service.get_sorted_tasks(SortStrategy::Urgency).unwrap();
+        let due_of = |name: &str| tasks.iter().find(|t| t.name == name).unwrap().due;
+        assert_eq!(due_of("Late work"), Some(new_due));
+        assert_eq!(due_of("Late home"), Some(new_due));
+        assert_eq!(due_of("Upcoming"), Some(now + Duration::days(5)));
+        assert_eq!(due_of("Done late"), Some(now - Duration::days(3)));
+    }
+
+    #[test]
+    fn test_activity_since() {
+        let now = Utc::now();
+        let since = now - Duration::hours(10);
+
+        let mut recent = Task::new("Recent".to_string(), None);
+        recent.state = TaskState::Completed {
+            completed_at: now - Duration::hours(1),
+            time_logs: vec![TimeLog { start: now - Duration::hours(12), end: Some(now - Duration::hours(8)) }],
+            actual: None,
+        };
+        let mut old = Task::new("Old".to_string(), None);
+        old.state = TaskState::Completed { completed_at: now - Duration::days(2), time_logs: vec![], actual: None };
+        let mut pending = Task::new("Pending".to_string(), None);
+        pending.state = TaskState::Pending {
+            time_logs: vec![TimeLog { start: now - Duration::hours(3), end: Some(now - Duration::hours(2)) }],
+        };
+
+        let service = TaskService::new(MemoryTaskRepository::with_tasks(vec![recent, old, pending]));
+        let summary = service.activity_since(since, now).unwrap();
+        assert_eq!(summary.completed, 1);
+        // 2h of the first log fall inside the window, plus the pending task's hour
+        assert_eq!(summary.tracked_seconds, 3 * 3600);
+    }
+
+    fn dto_due(due: Option<DateTime<Utc>>, completed: bool) -> TaskDto {
+        let mut task = Task::new("Due".to_string(), due);
+        if completed {
+            task.complete(None);
+        }
+        TaskDto::from_entity(task, 0.0)
+    }
+
+    #[test]
+    fn test_stale_tasks_rejects_out_of_range_days() {
+        let service = TaskService::new(MemoryTaskRepository::new());
+        service.create_task(Task::new("Old".to_string(), None)).unwrap();
+        assert_eq!(service.stale_tasks(0).unwrap().len(), 1);
+        assert!(service.stale_tasks(u32::MAX).is_err());
+    }
+
+    #[test]
+    fn test_due_filter_overdue() {
+        let now = Utc::now();
+        let filter = DueFilter::parse("overdue").unwrap();
+
+        assert!(filter.matches(&dto_due(Some(now - Duration::days(1)), false), now));
+        assert!(!filter.matches(&dto_due(Some(now - Duration::days(1)), true), now));
+        assert!(!filter.matches(&dto_due(Some(now + Duration::hours(1)), false), now));
+        assert!(!filter.matches(&dto_due(None, false), now));
+
+        // The DTO flag agrees with the filter
+        assert!(dto_due(Some(now - Duration::days(1)), false).overdue);
+        assert!(!dto_due(Some(now - Duration::days(1)), true).overdue);
+        assert!(!dto_due(Some(now + Duration::hours(1)), false).overdue);
+    }
+
+    #[test]
+    fn test_due_filter_week() {
+        let now = Utc::now();
+        let filter = DueFilter::parse("week").unwrap();
+
+        assert!(filter.matches(&dto_due(Some(now + Duration::days(3)), false), now));
+        assert!(filter.matches(&dto_due(Some(now + Duration::days(7)), false), now));
+        assert!(!filter.matches(&dto_due(Some(now + Duration::days(8)), false), now));
+        assert!(!filter.matches(&dto_due(None, false), now));
+    }
+
+    #[test]
+    fn test_completing_recurring_task_adds_next_instance() {
+        let now = "2025-03-10T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut chore = Task::new("Take out trash".to_string(), Some(now - Duration::hours(1)));
+        chore.recurrence = Some("1w".to_string());
+        chore.tags = vec!["home".to_string()];
+        let id = chore.id;
+        let service = TaskService::new(MemoryTaskRepository::with_tasks(vec![chore]))
+            .with_clock(Box::new(FixedClock(now)));
+
+        service.complete_task(&id).unwrap();
+        let tasks = service.repo.list().unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert!(matches!(service.get_task(&id).unwrap().state, TaskState::Completed { .. }));
+        let next = tasks.iter().find(|t| t.id != id).unwrap();
+        assert!(matches!(next.state, TaskState::Pending { .. }));
+        assert_eq!(next.due, Some(now - Duration::hours(1) + Duration::weeks(1)));
+        assert_eq!(next.tags, ["home"]);
+
+        // Completing the finished one again spawns nothing
+        service.complete_task(&id).unwrap();
+        assert_eq!(service.repo.list().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_status_filter() {
+        let completed = StatusFilter::parse("Completed").unwrap();
+        assert!(completed.matches(&dto_due(None, true)));
+        assert!(!completed.matches(&dto_due(None, false)));
+        assert!(StatusFilter::parse("pending").unwrap().matches(&dto_due(None, false)));
+
+        let err = StatusFilter::parse("open").unwrap_err().to_string();
+        assert!(err.contains("pending, completed, deleted"), "{}", err);
+    }
+
+    fn task_aged(days: i64, priority: Priority, now: DateTime<Utc>) -> Task {
+        let mut task = Task::new("Old".to_string(), None);
+        task.priority = priority;
+        task.created_at = now - Duration::days(days);
+        task
+    }
+
+    #[test]
+    fn test_starvation_boost_needs_threshold() {
+        let now = Utc::now();
+        let config = ScoringConfig { starvation_days: 30, starvation_boost: 10.0, ..Default::default() };
+        let off = ScoringConfig::default();
+
+        let young = task_aged(29, Priority::High, now);
+        let old = task_aged(30, Priority::High, now);
+        assert_eq!(calculate_urgency_with(&young, &config, now), calculate_urgency_with(&young, &off, now));
+        assert_eq!(calculate_urgency_with(&old, &config, now), calculate_urgency_with(&old, &off, now) + 10.0);
+    }
+
+    #[test]
+    fn test_starvation_boost_only_for_configured_priority() {
+        let now = Utc::now();
+        let config = ScoringConfig {
+            starvation_days: 30,
+            starvation_boost: 10.0,
+            starvation_priority: Priority::Medium,
+            ..Default::default()
+        };
+        let off = ScoringConfig::default();
+
+        let high = task_aged(60, Priority::High, now);
+        let medium = task_aged(60, Priority::Medium, now);
+        assert_eq!(calculate_urgency_with(&high, &config, now), calculate_urgency_with(&high, &off, now));
+        assert_eq!(calculate_urgency_with(&medium, &config, now), calculate_urgency_with(&medium, &off, now) + 10.0);
+    }
+
+    #[test]
+    fn test_coefficient_due_outweighs_priority() {
+        let now = Utc::now();
+        let mut due_soon = Task::new("Due".to_string(), Some(now + Duration::days(3)));
+        due_soon.priority = Priority::Low;
+        let mut important = Task::new("Important".to_string(), None);
+        important.priority = Priority::High;
+        important.estimate = Some("0.05".to_string());
+
+        let due_first = ScoringConfig { coefficient_due: 30.0, coefficient_priority: 1.0, ..Default::default() };
+        let flat = ScoringConfig { coefficient_due: 0.0, ..Default::default() };
+        assert!(calculate_urgency_with(&due_soon, &due_first, now) > calculate_urgency_with(&important, &due_first, now) + 20.0);
+        assert!(calculate_urgency_with(&due_soon, &flat, now) < calculate_urgency_with(&important, &flat, now));
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::DetailLayout;
+use crate::paths;
+use crate::repository::atomic::write_json_atomic;
+
+const STATE_FILE_NAME: &str = "state.json";
+
+/// Small bits of app state that survive restarts, kept next to the data in `state.json`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct AppState {
+    /// When the TUI was last opened
+    pub last_opened: Option<DateTime<Utc>>,
+    /// Reminders the daemon already fired: task ID -> the reminder time it fired for.
+    /// Moving the due date changes the reminder time, so the task is reminded about again.
+    pub fired_reminders: BTreeMap<Uuid, DateTime<Utc>>,
+    /// Detail panel layout last picked in the TUI; overrides `tui.detail_layout`
+    pub detail_layout: Option<DetailLayout>,
+}
+
+impl AppState {
+    pub fn load(base_dir: Option<PathBuf>) -> Result<Self> {
+        let path = state_path(base_dir)?;
+        if !path.exists() {
+            return Ok(AppState::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Invalid state file {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, base_dir: Option<PathBuf>) -> Result<()> {
+        let path = state_path(base_dir)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        write_json_atomic(&path, self)
+    }
+}
+
+fn state_path(base_dir: Option<PathBuf>) -> Result<PathBuf> {
+    let dir = match base_dir {
+        Some(dir) => dir,
+        None => paths::data_dir()?,
+    };
+    Ok(dir.join(STATE_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn test_state_round_trip() {
+        let dir = TempDir::new();
+        assert_eq!(AppState::load(Some(dir.to_path_buf())).unwrap(), AppState::default());
+
+        let mut state = AppState { last_opened: Some(Utc::now()), detail_layout: Some(DetailLayout::Full), ..Default::default() };
+        state.fired_reminders.insert(Uuid::new_v4(), Utc::now());
+        state.save(Some(dir.to_path_buf())).unwrap();
+        assert_eq!(AppState::load(Some(dir.to_path_buf())).unwrap(), state);
+    }
+}
@@ -1,33 +1,70 @@
+use crate::clock::{Clock, SystemClock};
+use crate::config::PlanConfig;
 use crate::repository::DailyLogRepository;
 use crate::service::daily_log_service::DailyLogService;
-use crate::service::dto::TaskDto;
+use crate::service::dto::{TaskDto, TodaySummary};
 use anyhow::Result;
-use chrono::Local;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
 
-#[derive(Default, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct DailyPlanStats {
     pub total_capacity: f64,
     pub meeting_hours: f64,
     pub work_done_today: f64,
     pub remaining_active_capacity: f64,
+    #[serde(default)]
+    pub project_budgets: Vec<ProjectBudget>, // One per configured project, by name
 }
 
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ProjectBudget {
+    pub project: String,
+    pub budget_hours: f64,    // Share of the effective capacity
+    pub remaining_hours: f64, // Budget minus today's work on the project
+}
+
+/// Hours available on a working day unless `plan.daily_capacity_hours` says otherwise.
+pub const DAILY_CAPACITY_HOURS: f64 = 8.0;
+
 pub struct DailyPlanUseCase<'a, L: DailyLogRepository> {
     daily_log_service: &'a DailyLogService<L>,
+    config: PlanConfig,
+    clock: Box<dyn Clock>,
 }
 
 impl<'a, L: DailyLogRepository> DailyPlanUseCase<'a, L> {
-    pub fn new(daily_log_service: &'a DailyLogService<L>) -> Self {
+    pub fn new(daily_log_service: &'a DailyLogService<L>, config: PlanConfig) -> Self {
         Self {
             daily_log_service,
+            config,
+            clock: Box::new(SystemClock),
+        }
+    }
+
+    /// Decides which day is "today" from `clock` instead of the system time.
+    pub fn with_clock(mut self, clock: Box<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Target hours for `date`: the configured daily capacity on working days, nothing otherwise.
+    pub fn capacity_for(&self, date: NaiveDate) -> f64 {
+        if self.config.working_days.contains(&date.weekday()) {
+            self.config.daily_capacity_hours
+        } else {
+            0.0
         }
     }
 
     pub fn apply_daily_plan(&self, tasks: &mut [TaskDto]) -> Result<DailyPlanStats> {
-        let today = Local::now().date_naive();
-        
+        self.apply_daily_plan_on(tasks, self.clock.today())
+    }
+
+    /// Plans `date` as if it were today; the tasks' `today_*` times are taken as that day's work.
+    pub fn apply_daily_plan_on(&self, tasks: &mut [TaskDto], date: NaiveDate) -> Result<DailyPlanStats> {
         // 1. Get Meeting Hours
-        let meeting_hours = self.daily_log_service.get_log(today)
+        let meeting_hours = self.daily_log_service.get_log(date)
             .ok().flatten()
             .map(|l| l.total_hours())
             .unwrap_or(0.0);
@@ -35,23 +72,51 @@ impl<'a, L: DailyLogRepository> DailyPlanUseCase<'a, L> {
         // 2. Tasks are passed in
 
         // 3. Calculate Work Done Today (across ALL tasks, assuming caller passed all relevant tasks)
+        // Consumed capacity is (a) time tracked today, plus (b) when `count_untracked_completions`
+        // is set, the full effort of tasks completed today that have no time logs.
         let work_done_today: u64 = tasks.iter()
-            .map(|t| t.today_accumulated_time)
+            .map(|t| self.work_done_seconds(t))
             .sum();
         let work_done_hours = work_done_today as f64 / 3600.0;
         
         // 4. Calculate Capacity
-        let total_capacity = 8.0;
+        let total_capacity = self.capacity_for(date);
         let effective_capacity = (total_capacity - meeting_hours).max(0.0);
         let remaining_active_capacity = (effective_capacity - work_done_hours).max(0.0);
 
+        // Per-project budgets are a share of the effective capacity, less today's work on that project
+        let project_budgets: Vec<ProjectBudget> = self.config.project_budgets.iter()
+            .map(|(project, share)| {
+                let budget_hours = effective_capacity * share.clamp(0.0, 1.0);
+                let done_seconds: u64 = tasks.iter()
+                    .filter(|t| in_project(t, project))
+                    .map(|t| self.work_done_seconds(t))
+                    .sum();
+                ProjectBudget {
+                    project: project.clone(),
+                    budget_hours,
+                    remaining_hours: (budget_hours - done_seconds as f64 / 3600.0).max(0.0),
+                }
+            })
+            .collect();
+        // Like the global pool, a project stops fitting tasks once one of them doesn't fit
+        let mut project_capacity: Vec<f64> = project_budgets.iter().map(|b| b.remaining_hours).collect();
+        let mut project_exhausted = vec![false; project_budgets.len()];
+
         // 5. Calculate Fit for Pending Tasks Sequentially
+        // A task within `fit_tolerance_hours` of the remaining capacity still counts as fitting.
+        let tolerance = self.config.fit_tolerance_hours.max(0.0);
         let mut current_capacity = remaining_active_capacity;
         let mut capacity_exhausted = false;
 
         for task in tasks.iter_mut() {
             if task.status == "Pending" && !task.is_tracking {
-                
+                // Can't be started yet, so it neither fits nor takes up capacity
+                if task.blocked {
+                    task.fit = Some(false);
+                    continue;
+                }
+
                 if capacity_exhausted {
                     if task.remaining_estimate > 0.0 {
                          task.fit = Some(false);
@@ -62,12 +127,27 @@ impl<'a, L: DailyLogRepository> DailyPlanUseCase<'a, L> {
                 }
 
                 if task.remaining_estimate > 0.0 {
-                    if task.remaining_estimate <= current_capacity {
-                        task.fit = Some(true);
-                        current_capacity -= task.remaining_estimate;
-                    } else {
+                    let budget = project_budgets.iter().position(|b| in_project(task, &b.project));
+                    let budget_allows = match budget {
+                        Some(i) => !project_exhausted[i] && task.remaining_estimate <= project_capacity[i] + tolerance,
+                        None => true,
+                    };
+
+                    if task.remaining_estimate > current_capacity + tolerance {
                         task.fit = Some(false);
                         capacity_exhausted = true; // Stop fitting subsequent tasks
+                    } else if !budget_allows {
+                        // Over the project's budget only; other projects may still fit
+                        task.fit = Some(false);
+                        if let Some(i) = budget {
+                            project_exhausted[i] = true;
+                        }
+                    } else {
+                        task.fit = Some(true);
+                        current_capacity = (current_capacity - task.remaining_estimate).max(0.0);
+                        if let Some(i) = budget {
+                            project_capacity[i] = (project_capacity[i] - task.remaining_estimate).max(0.0);
+                        }
                     }
                 } else {
                     task.fit = None; // No estimate, skipped in calculation (or treat as 0? Plan implies estimate needed)
@@ -82,6 +162,245 @@ impl<'a, L: DailyLogRepository> DailyPlanUseCase<'a, L> {
             meeting_hours,
             work_done_today: work_done_hours,
             remaining_active_capacity,
+            project_budgets,
         })
     }
+
+    /// Capacity a task consumed today in seconds; see `count_untracked_completions`.
+    fn work_done_seconds(&self, task: &TaskDto) -> u64 {
+        if self.config.count_untracked_completions {
+            task.today_accumulated_time + task.today_untracked_time
+        } else {
+            task.today_accumulated_time
+        }
+    }
+
+    /// Applies the daily plan and keeps only the tasks that received work today.
+    pub fn get_today_summary(&self, mut tasks: Vec<TaskDto>) -> Result<TodaySummary> {
+        let stats = self.apply_daily_plan(&mut tasks)?;
+        let worked_today = tasks.into_iter()
+            .filter(|t| t.today_accumulated_time > 0 || t.today_untracked_time > 0)
+            .collect();
+
+        Ok(TodaySummary {
+            date: self.clock.today().format("%Y-%m-%d").to_string(),
+            tasks: worked_today,
+            stats,
+        })
+    }
+}
+
+fn in_project(task: &TaskDto, project: &str) -> bool {
+    task.project.as_deref().is_some_and(|p| p.eq_ignore_ascii_case(project))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::daily_log::DailyLog;
+    use crate::model::task::{Task, TaskState};
+    use crate::clock::FixedClock;
+    use chrono::{Local, NaiveDate, TimeZone, Utc};
+
+    // Reports a fixed amount of meeting hours for every day.
+    struct MeetingRepo(f64);
+    impl DailyLogRepository for MeetingRepo {
+        fn get(&self, date: NaiveDate) -> Result<Option<DailyLog>> { Ok(Some(DailyLog::new(date, self.0))) }
+        fn list(&self) -> Result<Vec<DailyLog>> { Ok(Vec::new()) }
+        fn upsert(&self, _log: DailyLog) -> Result<()> { Ok(()) }
+        fn delete(&self, _date: NaiveDate) -> Result<bool> { Ok(false) }
+    }
+
+    // Monday 2025-01-13, noon local time: a working day under the default config
+    fn monday() -> FixedClock {
+        let noon = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap().and_hms_opt(12, 0, 0).unwrap();
+        FixedClock(Local.from_local_datetime(&noon).unwrap().with_timezone(&Utc))
+    }
+
+    fn one_day_task() -> Vec<TaskDto> {
+        let mut task = Task::new("Full day".to_string(), None);
+        task.estimate = Some("1".to_string()); // 8h
+        vec![TaskDto::from_entity(task, 0.0)]
+    }
+
+    #[test]
+    fn test_fit_boundary_without_tolerance() {
+        // 8h capacity - 0.01h meetings leaves 7.99h for an 8h task
+        let service = DailyLogService::new(MeetingRepo(0.01));
+        let usecase = DailyPlanUseCase::new(&service, PlanConfig::default()).with_clock(Box::new(monday()));
+
+        let mut tasks = one_day_task();
+        usecase.apply_daily_plan(&mut tasks).unwrap();
+        assert_eq!(tasks[0].fit, Some(false));
+    }
+
+    #[test]
+    fn test_fit_boundary_with_tolerance() {
+        let service = DailyLogService::new(MeetingRepo(0.01));
+        let usecase = DailyPlanUseCase::new(&service, PlanConfig { fit_tolerance_hours: 0.05, ..Default::default() }).with_clock(Box::new(monday()));
+
+        let mut tasks = one_day_task();
+        usecase.apply_daily_plan(&mut tasks).unwrap();
+        assert_eq!(tasks[0].fit, Some(true));
+    }
+
+    #[test]
+    fn test_exact_fit_needs_no_tolerance() {
+        let service = DailyLogService::new(MeetingRepo(0.0));
+        let usecase = DailyPlanUseCase::new(&service, PlanConfig::default()).with_clock(Box::new(monday()));
+
+        let mut tasks = one_day_task();
+        usecase.apply_daily_plan(&mut tasks).unwrap();
+        assert_eq!(tasks[0].fit, Some(true));
+    }
+
+    fn effort_only_completion() -> Vec<TaskDto> {
+        let mut task = Task::new("Done today".to_string(), None);
+        task.state = TaskState::Completed {
+            completed_at: monday().0,
+            time_logs: vec![],
+            actual: Some("0.5".to_string()), // 4h
+        };
+        vec![TaskDto::from_entity_at(task, 0.0, monday().0)]
+    }
+
+    #[test]
+    fn test_untracked_completion_counts_when_enabled() {
+        let service = DailyLogService::new(MeetingRepo(0.0));
+        let usecase = DailyPlanUseCase::new(&service, PlanConfig::default()).with_clock(Box::new(monday()));
+
+        let stats = usecase.apply_daily_plan(&mut effort_only_completion()).unwrap();
+        assert_eq!(stats.work_done_today, 4.0);
+        assert_eq!(stats.remaining_active_capacity, 4.0);
+    }
+
+    #[test]
+    fn test_untracked_completion_ignored_when_disabled() {
+        let service = DailyLogService::new(MeetingRepo(0.0));
+        let config = PlanConfig { count_untracked_completions: false, ..Default::default() };
+        let usecase = DailyPlanUseCase::new(&service, config).with_clock(Box::new(monday()));
+
+        let stats = usecase.apply_daily_plan(&mut effort_only_completion()).unwrap();
+        assert_eq!(stats.work_done_today, 0.0);
+        assert_eq!(stats.remaining_active_capacity, 8.0);
+    }
+
+    #[test]
+    fn test_saturday_has_zero_capacity() {
+        let service = DailyLogService::new(MeetingRepo(0.0));
+        let usecase = DailyPlanUseCase::new(&service, PlanConfig::default()).with_clock(Box::new(monday()));
+        let saturday = NaiveDate::from_ymd_opt(2025, 1, 11).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+
+        assert_eq!(usecase.capacity_for(saturday), 0.0);
+        assert_eq!(usecase.capacity_for(monday), DAILY_CAPACITY_HOURS);
+
+        let mut tasks = one_day_task();
+        let stats = usecase.apply_daily_plan_on(&mut tasks, saturday).unwrap();
+        assert_eq!(stats.total_capacity, 0.0);
+        assert_eq!(stats.remaining_active_capacity, 0.0);
+        assert_eq!(tasks[0].fit, Some(false));
+    }
+
+    #[test]
+    fn test_configured_weekend_work_day() {
+        let service = DailyLogService::new(MeetingRepo(0.0));
+        let config = PlanConfig { working_days: vec![chrono::Weekday::Sat], ..Default::default() };
+        let usecase = DailyPlanUseCase::new(&service, config).with_clock(Box::new(monday()));
+
+        let saturday = NaiveDate::from_ymd_opt(2025, 1, 11).unwrap();
+        let stats = usecase.apply_daily_plan_on(&mut one_day_task(), saturday).unwrap();
+        assert_eq!(stats.total_capacity, DAILY_CAPACITY_HOURS);
+    }
+
+    #[test]
+    fn test_blocked_task_does_not_fit_or_use_capacity() {
+        let service = DailyLogService::new(MeetingRepo(0.0));
+        let usecase = DailyPlanUseCase::new(&service, PlanConfig::default()).with_clock(Box::new(monday()));
+        let mut tasks = vec![one_day_task().remove(0), one_day_task().remove(0)];
+        tasks[0].blocked = true;
+
+        usecase.apply_daily_plan(&mut tasks).unwrap();
+        assert_eq!(tasks[0].fit, Some(false));
+        assert_eq!(tasks[1].fit, Some(true));
+    }
+
+    #[test]
+    fn test_part_time_capacity() {
+        let service = DailyLogService::new(MeetingRepo(1.0));
+        let config = PlanConfig { daily_capacity_hours: 6.0, ..Default::default() };
+        let usecase = DailyPlanUseCase::new(&service, config).with_clock(Box::new(monday()));
+
+        // One 8h day of work no longer fits a 6h day
+        let mut tasks = one_day_task();
+        let stats = usecase.apply_daily_plan(&mut tasks).unwrap();
+        assert_eq!(stats.total_capacity, 6.0);
+        assert_eq!(stats.remaining_active_capacity, 5.0);
+        assert_eq!(tasks[0].fit, Some(false));
+    }
+
+    fn project_task(name: &str, project: Option<&str>, estimate: &str) -> TaskDto {
+        let mut task = Task::new(name.to_string(), None);
+        task.project = project.map(str::to_string);
+        task.estimate = Some(estimate.to_string());
+        TaskDto::from_entity(task, 0.0)
+    }
+
+    fn budget_config(share: f64) -> PlanConfig {
+        PlanConfig {
+            project_budgets: [("Work".to_string(), share)].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_project_budget_limits_fit() {
+        let service = DailyLogService::new(MeetingRepo(0.0));
+        let usecase = DailyPlanUseCase::new(&service, budget_config(0.5)).with_clock(Box::new(monday()));
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+
+        // 4h Work budget: the first 3h task fits, the second would exceed the budget,
+        // but an unbudgeted task can still use the global capacity.
+        let mut tasks = vec![
+            project_task("A", Some("Work"), "3h"),
+            project_task("B", Some("work"), "3h"),
+            project_task("C", None, "3h"),
+        ];
+        let stats = usecase.apply_daily_plan_on(&mut tasks, monday).unwrap();
+
+        assert_eq!(stats.project_budgets, vec![ProjectBudget {
+            project: "Work".to_string(),
+            budget_hours: 4.0,
+            remaining_hours: 4.0,
+        }]);
+        assert_eq!(tasks[0].fit, Some(true));
+        assert_eq!(tasks[1].fit, Some(false));
+        assert_eq!(tasks[2].fit, Some(true));
+    }
+
+    #[test]
+    fn test_project_budget_is_share_of_effective_capacity() {
+        // 6h of meetings leave 2h, so a full-day budget is only 2h too
+        let service = DailyLogService::new(MeetingRepo(6.0));
+        let usecase = DailyPlanUseCase::new(&service, budget_config(1.0)).with_clock(Box::new(monday()));
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 13).unwrap();
+
+        let mut tasks = vec![project_task("A", Some("Work"), "1h"), project_task("B", Some("Work"), "2h")];
+        let stats = usecase.apply_daily_plan_on(&mut tasks, monday).unwrap();
+
+        assert_eq!(stats.project_budgets[0].budget_hours, 2.0);
+        assert_eq!(tasks[0].fit, Some(true));
+        assert_eq!(tasks[1].fit, Some(false));
+    }
+
+    #[test]
+    fn test_today_comes_from_the_clock() {
+        let service = DailyLogService::new(MeetingRepo(0.0));
+        let saturday = FixedClock(monday().0 - chrono::Duration::days(2));
+        let usecase = DailyPlanUseCase::new(&service, PlanConfig::default()).with_clock(Box::new(saturday));
+
+        let summary = usecase.get_today_summary(one_day_task()).unwrap();
+        assert_eq!(summary.date, "2025-01-11");
+        assert_eq!(summary.stats.total_capacity, 0.0);
+    }
 }
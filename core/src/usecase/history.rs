@@ -1,16 +1,20 @@
 use crate::repository::{TaskRepository, DailyLogRepository, FileStatsRepository};
 use crate::service::daily_log_service::DailyLogService;
-use crate::service::dto::{TaskDto, WeeklyHistory, DailyHistory, HistoryStats};
+use crate::service::dto::{TaskDto, WeeklyHistory, DailyHistory, HistoryStats, YearSummary};
 use crate::model::task::TaskState;
 use crate::service::task_service::parse_est_hours;
-use chrono::{DateTime, Local, Datelike, NaiveDate};
+use chrono::{DateTime, Local, Datelike, NaiveDate, TimeZone, Utc};
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+// Map: (Year, Week) -> Date -> (Tasks, EstHours, ActHours, MtgHours, InProgressEstHours)
+type WeeklyData = HashMap<(i32, u32), HashMap<NaiveDate, (Vec<TaskDto>, f64, f64, f64, f64)>>;
 
 pub struct HistoryUseCase<'a, R: TaskRepository, L: DailyLogRepository> {
     task_repo: &'a R,
     daily_log_service: &'a DailyLogService<L>,
     stats_repo: &'a FileStatsRepository,
+    in_progress_estimates: bool,
 }
 
 impl<'a, R: TaskRepository, L: DailyLogRepository> HistoryUseCase<'a, R, L> {
@@ -19,15 +23,25 @@ impl<'a, R: TaskRepository, L: DailyLogRepository> HistoryUseCase<'a, R, L> {
             task_repo,
             daily_log_service,
             stats_repo,
+            in_progress_estimates: false,
         }
     }
 
+    /// Credit in-progress tasks' estimates as they are worked (see `StatsConfig::in_progress_estimates`).
+    pub fn with_in_progress_estimates(mut self, enabled: bool) -> Self {
+        self.in_progress_estimates = enabled;
+        self
+    }
+
     pub fn get_weekly_history(&self) -> Result<Vec<WeeklyHistory>> {
-        let mut weekly_data: HashMap<(i32, u32), HashMap<chrono::NaiveDate, (Vec<TaskDto>, f64, f64, f64)>> = HashMap::new();
-        // Map: (Year, Week) -> Date -> (Tasks, EstHours, ActHours, MtgHours)
+        let mut weekly_data: WeeklyData = HashMap::new();
 
         // 1. Load from Stats Repository (Archived Data)
         let stats_list = self.stats_repo.list_stats()?;
+        // Tasks whose hours the stats already hold (credited on completion); still listed below, not re-added
+        let credited: std::collections::HashSet<uuid::Uuid> = stats_list.iter()
+            .flat_map(|s| s.credited.keys().copied())
+            .collect();
         for monthly_stats in stats_list {
             for (date_str, daily_stats) in monthly_stats.days {
                 if let Ok(date) = NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
@@ -70,23 +84,35 @@ impl<'a, R: TaskRepository, L: DailyLogRepository> HistoryUseCase<'a, R, L> {
                      
                      let entry = weekly_data.entry(week_key).or_default().entry(date).or_default();
                      entry.0.push(task_dto);
+                     if credited.contains(&task.id) {
+                         continue;
+                     }
                      
                      let est = parse_est_hours(&task.estimate);
                      entry.1 += est;
                      
                      // Distribute logs
                      if time_logs.is_empty() {
-                         if let Some(act_str) = actual {
-                             if let Ok(days) = act_str.parse::<f64>() {
-                                 entry.2 += days * 8.0;
-                             }
-                         }
+                         entry.2 += parse_est_hours(actual);
                      } else {
                          distribute_logs(time_logs, &mut weekly_data);
                      }
                 },
                 TaskState::Pending { time_logs } => {
                     distribute_logs(time_logs, &mut weekly_data);
+
+                    if self.in_progress_estimates {
+                        let mut est_left = parse_est_hours(&task.estimate);
+                        for (date, hours) in hours_by_day(time_logs) {
+                            if est_left <= 0.0 {
+                                break;
+                            }
+                            let credit = hours.min(est_left);
+                            est_left -= credit;
+                            let iso = date.iso_week();
+                            weekly_data.entry((iso.year(), iso.week())).or_default().entry(date).or_default().4 += credit;
+                        }
+                    }
                     
                     // Now ensure task is listed on days it has activity
                     let mut days_active = std::collections::HashSet::new();
@@ -118,15 +144,16 @@ impl<'a, R: TaskRepository, L: DailyLogRepository> HistoryUseCase<'a, R, L> {
         for (year, week) in sorted_weeks {
             let days_map = weekly_data.get(&(year, week)).unwrap();
             let mut sorted_days: Vec<_> = days_map.keys().cloned().collect();
-            sorted_days.sort_by(|a, b| a.cmp(b));
+            sorted_days.sort();
             
             let mut daily_histories = Vec::new();
             let mut week_est = 0.0;
             let mut week_act = 0.0;
             let mut week_mtg = 0.0;
+            let mut week_in_progress = 0.0;
             
             for day in sorted_days {
-                let (day_tasks, est, act, _) = days_map.get(&day).cloned().unwrap(); 
+                let (day_tasks, est, act, _, in_progress) = days_map.get(&day).cloned().unwrap(); 
                 
                 // Get meeting hours for this day
                 // Note: We use DailyLogService to get meetings. 
@@ -154,6 +181,7 @@ impl<'a, R: TaskRepository, L: DailyLogRepository> HistoryUseCase<'a, R, L> {
                 week_est += est;
                 week_act += act;
                 week_mtg += total_mtg;
+                week_in_progress += in_progress;
                 
                 daily_histories.push(DailyHistory {
                     date: day.format("%Y-%m-%d").to_string(),
@@ -163,6 +191,7 @@ impl<'a, R: TaskRepository, L: DailyLogRepository> HistoryUseCase<'a, R, L> {
                         total_est_hours: est,
                         total_act_hours: act,
                         meeting_hours: total_mtg,
+                        in_progress_est_hours: in_progress,
                     }
                 });
             }
@@ -175,6 +204,7 @@ impl<'a, R: TaskRepository, L: DailyLogRepository> HistoryUseCase<'a, R, L> {
                     total_est_hours: week_est,
                     total_act_hours: week_act,
                     meeting_hours: week_mtg,
+                    in_progress_est_hours: week_in_progress,
                 }
             });
         }
@@ -183,26 +213,81 @@ impl<'a, R: TaskRepository, L: DailyLogRepository> HistoryUseCase<'a, R, L> {
     }
 }
 
+/// Rolls every week of `year` up into one summary.
+/// Metrics that can't be derived from the data (e.g. archived weeks carry no tasks) stay `None`.
+pub fn summarize_year(histories: &[WeeklyHistory], year: i32) -> YearSummary {
+    let mut summary = YearSummary { year, ..Default::default() };
+    let mut seen_tasks = std::collections::HashSet::new();
+    let mut project_hours: HashMap<String, f64> = HashMap::new();
+
+    for week in histories.iter().filter(|w| w.year == year) {
+        summary.stats.total_est_hours += week.stats.total_est_hours;
+        summary.stats.total_act_hours += week.stats.total_act_hours;
+        summary.stats.meeting_hours += week.stats.meeting_hours;
+        summary.stats.in_progress_est_hours += week.stats.in_progress_est_hours;
+        summary.active_weeks += 1;
+
+        let act = week.stats.total_act_hours;
+        if act > 0.0 && summary.busiest_week.is_none_or(|(_, best)| act > best) {
+            summary.busiest_week = Some((week.week, act));
+        }
+
+        // Pending tasks are listed on every active day, so count each task once
+        for task in week.days.iter().flat_map(|d| &d.tasks) {
+            if !seen_tasks.insert(task.id) {
+                continue;
+            }
+            if task.status == "Completed" {
+                summary.completed_count += 1;
+            }
+            if let Some(project) = &task.project {
+                *project_hours.entry(project.clone()).or_default() += task.accumulated_time as f64 / 3600.0;
+            }
+        }
+    }
+
+    summary.top_project = project_hours.into_iter()
+        .filter(|(_, hours)| *hours > 0.0)
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    summary
+}
+
 // Helper to distribute logs into weekly_data
 fn distribute_logs(
-    logs: &Vec<crate::model::task::TimeLog>, 
-    weekly_data: &mut HashMap<(i32, u32), HashMap<chrono::NaiveDate, (Vec<TaskDto>, f64, f64, f64)>>
+    logs: &[crate::model::task::TimeLog],
+    weekly_data: &mut WeeklyData
 ) {
+    for (log_date, hrs) in hours_by_day(logs) {
+        let log_iso = log_date.iso_week();
+        let log_week_key = (log_iso.year(), log_iso.week());
+        let log_entry = weekly_data.entry(log_week_key).or_default().entry(log_date).or_default();
+        log_entry.2 += hrs;
+    }
+}
+
+// Closed logs' hours per local day, oldest day first. Logs are split at local midnights
+// so a log running past midnight counts on each day it covers.
+pub(crate) fn hours_by_day(logs: &[crate::model::task::TimeLog]) -> BTreeMap<NaiveDate, f64> {
+    let mut days = BTreeMap::new();
     for log in logs {
-        if let Some(end) = log.end {
-            let log_local: DateTime<Local> = DateTime::from(log.start);
-            let log_date = log_local.date_naive();
-            let log_iso = log_local.iso_week();
-            let log_week_key = (log_iso.year(), log_iso.week());
-            
-            let start_ts = log.start.timestamp();
-            let end_ts = end.timestamp();
-            let dur_sec = end_ts - start_ts;
-            if dur_sec > 0 {
-                let hrs = dur_sec as f64 / 3600.0;
-                let log_entry = weekly_data.entry(log_week_key).or_default().entry(log_date).or_default();
-                log_entry.2 += hrs;
+        let Some(end) = log.end else { continue };
+
+        let mut cursor = log.start;
+        while cursor < end {
+            let log_date = DateTime::<Local>::from(cursor).date_naive();
+            let segment_end = next_local_midnight(log_date).map_or(end, |midnight| end.min(midnight));
+            if segment_end <= cursor {
+                break;
             }
+            *days.entry(log_date).or_insert(0.0) += (segment_end - cursor).num_seconds() as f64 / 3600.0;
+            cursor = segment_end;
         }
     }
+    days
+}
+
+fn next_local_midnight(date: NaiveDate) -> Option<DateTime<Utc>> {
+    let next = date.succ_opt()?.and_hms_opt(0, 0, 0)?;
+    Local.from_local_datetime(&next).earliest().map(|dt| dt.with_timezone(&Utc))
 }
@@ -1,38 +1,31 @@
 
 #[cfg(test)]
 mod tests {
-    use crate::usecase::history::HistoryUseCase;
-    use crate::repository::{TaskRepository, DailyLogRepository};
+    use crate::usecase::history::{HistoryUseCase, summarize_year};
+    use crate::repository::{DailyLogRepository, FileStatsRepository, MemoryTaskRepository};
     use crate::service::daily_log_service::DailyLogService;
     use crate::model::task::{Task, TaskState, TimeLog};
     use crate::model::daily_log::DailyLog;
-    use chrono::{Utc, Duration};
-    use uuid::Uuid;
+    use crate::service::dto::{WeeklyHistory, DailyHistory, HistoryStats, TaskDto};
+    use crate::test_util::TempDir;
+    use chrono::{Utc, Duration, Local, NaiveDate};
     use anyhow::Result;
 
-    struct MockTaskRepo {
-        tasks: Vec<Task>,
-    }
-
-    impl TaskRepository for MockTaskRepo {
-        fn create(&self, _task: Task) -> Result<Task> { unimplemented!() }
-        fn get(&self, _id: &Uuid) -> Result<Task> { unimplemented!() }
-        fn update(&self, _task: &Task) -> Result<()> { unimplemented!() }
-        fn delete(&self, _id: &Uuid) -> Result<()> { unimplemented!() }
-        fn list(&self) -> Result<Vec<Task>> { Ok(self.tasks.clone()) }
-    }
-
     struct MockDailyLogRepo;
     impl DailyLogRepository for MockDailyLogRepo {
         fn get(&self, _date: chrono::NaiveDate) -> Result<Option<DailyLog>> { Ok(None) }
+        fn list(&self) -> Result<Vec<DailyLog>> { Ok(Vec::new()) }
         fn upsert(&self, _log: DailyLog) -> Result<()> { Ok(()) }
+        fn delete(&self, _date: chrono::NaiveDate) -> Result<bool> { Ok(false) }
     }
 
     #[test]
     fn test_get_weekly_history_split_days() {
         let mut task = Task::new("Split Task".to_string(), None);
         
-        let now = Utc::now();
+        // Local noon keeps both logs inside their day now that logs split at midnight
+        let now = Local::now().date_naive().and_hms_opt(12, 0, 0).unwrap()
+            .and_local_timezone(Local).earliest().unwrap().with_timezone(&Utc);
         let yesterday = now - Duration::days(1);
         
         let log1 = TimeLog {
@@ -50,10 +43,12 @@ mod tests {
             actual: None,
         };
 
-        let task_repo = MockTaskRepo { tasks: vec![task] };
+        let task_repo = MemoryTaskRepository::with_tasks(vec![task]);
         let log_repo = MockDailyLogRepo;
         let log_service = DailyLogService::new(log_repo);
-        let history_usecase = HistoryUseCase::new(&task_repo, &log_service);
+        let stats_dir = TempDir::new();
+        let stats_repo = FileStatsRepository::new(Some(stats_dir.to_path_buf())).unwrap();
+        let history_usecase = HistoryUseCase::new(&task_repo, &log_service, &stats_repo);
 
         let history = history_usecase.get_weekly_history().unwrap();
         
@@ -80,4 +75,136 @@ mod tests {
         assert!(found_yesterday, "Should have found stats for yesterday");
         assert!(found_today, "Should have found stats for today");
     }
+
+    #[test]
+    fn test_weekly_history_json_round_trip() {
+        let mut task = Task::new("Known Week Task".to_string(), None);
+        let start = Utc::now() - Duration::hours(3);
+        task.estimate = Some("0.5".to_string());
+        task.state = TaskState::Completed {
+            completed_at: Utc::now(),
+            time_logs: vec![TimeLog { start, end: Some(start + Duration::hours(2)) }],
+            actual: None,
+        };
+
+        let task_repo = MemoryTaskRepository::with_tasks(vec![task]);
+        let log_service = DailyLogService::new(MockDailyLogRepo);
+        let stats_dir = TempDir::new();
+        let stats_repo = FileStatsRepository::new(Some(stats_dir.to_path_buf())).unwrap();
+        let history_usecase = HistoryUseCase::new(&task_repo, &log_service, &stats_repo);
+
+        let history = history_usecase.get_weekly_history().unwrap();
+        assert!(!history.is_empty());
+
+        let json = serde_json::to_string_pretty(&history).unwrap();
+        let parsed: Vec<WeeklyHistory> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, history);
+        assert_eq!(parsed[0].stats.total_est_hours, 4.0);
+    }
+
+    fn week(year: i32, week: u32, act: f64, tasks: Vec<TaskDto>) -> WeeklyHistory {
+        let stats = HistoryStats { total_est_hours: 0.0, total_act_hours: act, meeting_hours: 0.0, in_progress_est_hours: 0.0 };
+        WeeklyHistory {
+            year,
+            week,
+            days: vec![DailyHistory {
+                date: format!("{}-01-01", year),
+                day_of_week: "Mon".to_string(),
+                tasks,
+                stats: stats.clone(),
+            }],
+            stats,
+        }
+    }
+
+    #[test]
+    fn test_summarize_year() {
+        let mut done = Task::new("Done".to_string(), None);
+        done.project = Some("Work".to_string());
+        done.state = TaskState::Completed { completed_at: Utc::now(), time_logs: vec![], actual: Some("1".to_string()) };
+        let done = TaskDto::from_entity(done, 0.0);
+
+        let mut side = Task::new("Side".to_string(), None);
+        side.project = Some("Hobby".to_string());
+        side.state = TaskState::Completed { completed_at: Utc::now(), time_logs: vec![], actual: Some("0.5".to_string()) };
+        let side = TaskDto::from_entity(side, 0.0);
+
+        let histories = vec![
+            week(2025, 3, 4.0, vec![side]),
+            week(2025, 2, 10.0, vec![done.clone()]),
+            week(2025, 1, 0.0, vec![done]), // Same task listed again must not be double counted
+            week(2024, 52, 40.0, vec![]),
+        ];
+
+        let summary = summarize_year(&histories, 2025);
+        assert_eq!(summary.stats.total_act_hours, 14.0);
+        assert_eq!(summary.active_weeks, 3);
+        assert_eq!(summary.busiest_week, Some((2, 10.0)));
+        assert_eq!(summary.top_project, Some(("Work".to_string(), 8.0)));
+        assert_eq!(summary.completed_count, 2);
+    }
+
+    #[test]
+    fn test_summarize_sparse_year() {
+        let histories = vec![week(2025, 1, 0.0, vec![])];
+        let summary = summarize_year(&histories, 2025);
+        assert_eq!(summary.busiest_week, None);
+        assert_eq!(summary.top_project, None);
+        assert_eq!(summary.completed_count, 0);
+    }
+
+    #[test]
+    fn test_logged_effort_spreads_across_midnight() {
+        // One day of effort (8h) entered at 03:00: 5h the evening before, 3h that morning
+        let completed_at = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap().and_hms_opt(3, 0, 0).unwrap()
+            .and_local_timezone(Local).earliest().unwrap().with_timezone(&Utc);
+        let mut task = Task::new("Late night".to_string(), None);
+        task.complete_with_logged_effort("1".to_string(), completed_at).unwrap();
+
+        let task_repo = MemoryTaskRepository::with_tasks(vec![task]);
+        let log_service = DailyLogService::new(MockDailyLogRepo);
+        let stats_dir = TempDir::new();
+        let stats_repo = FileStatsRepository::new(Some(stats_dir.to_path_buf())).unwrap();
+        let history = HistoryUseCase::new(&task_repo, &log_service, &stats_repo).get_weekly_history().unwrap();
+
+        let act_on = |date: &str| history.iter()
+            .flat_map(|w| &w.days)
+            .find(|d| d.date == date)
+            .map(|d| d.stats.total_act_hours);
+        assert_eq!(act_on("2025-01-14"), Some(5.0));
+        assert_eq!(act_on("2025-01-15"), Some(3.0));
+    }
+
+    #[test]
+    fn test_in_progress_estimates_credited_as_worked() {
+        // Mon 2025-01-13 and Tue 2025-01-14, same ISO week; 3h tracked each day against a 4h estimate
+        let local = |day: u32, hour: u32| NaiveDate::from_ymd_opt(2025, 1, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+            .and_local_timezone(Local).earliest().unwrap().with_timezone(&Utc);
+        let mut task = Task::new("Ongoing".to_string(), None);
+        task.estimate = Some("4h".to_string());
+        task.state = TaskState::Pending { time_logs: vec![
+            TimeLog { start: local(13, 9), end: Some(local(13, 12)) },
+            TimeLog { start: local(14, 9), end: Some(local(14, 12)) },
+        ] };
+
+        let task_repo = MemoryTaskRepository::with_tasks(vec![task]);
+        let log_service = DailyLogService::new(MockDailyLogRepo);
+        let stats_dir = TempDir::new();
+        let stats_repo = FileStatsRepository::new(Some(stats_dir.to_path_buf())).unwrap();
+
+        let history = HistoryUseCase::new(&task_repo, &log_service, &stats_repo).get_weekly_history().unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].stats.total_act_hours, 6.0);
+        assert_eq!(history[0].stats.total_est_hours, 0.0);
+        assert_eq!(history[0].stats.in_progress_est_hours, 0.0);
+
+        let history = HistoryUseCase::new(&task_repo, &log_service, &stats_repo)
+            .with_in_progress_estimates(true)
+            .get_weekly_history().unwrap();
+        let week = &history[0];
+        assert_eq!(week.stats.total_est_hours, 0.0);
+        assert_eq!(week.stats.in_progress_est_hours, 4.0);
+        let credited: Vec<_> = week.days.iter().map(|d| (d.date.as_str(), d.stats.in_progress_est_hours)).collect();
+        assert_eq!(credited, [("2025-01-13", 3.0), ("2025-01-14", 1.0)]);
+    }
 }
@@ -1,5 +1,6 @@
 pub mod history;
 pub mod daily_plan;
+pub mod project_stats;
 
 #[cfg(test)]
 mod history_test;
@@ -0,0 +1,122 @@
+use crate::repository::TaskRepository;
+use crate::service::dto::TaskDto;
+use crate::service::task_service::parse_est_hours;
+use crate::model::task::TaskState;
+use anyhow::Result;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+
+/// The group of tasks without a project.
+pub const NO_PROJECT: &str = "(none)";
+
+/// Totals for one project over the tasks still in the repository (archived ones are not included).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ProjectStats {
+    pub project: String,
+    pub tasks: usize,
+    pub completed: usize,
+    pub estimated_hours: f64,
+    pub actual_hours: f64, // Tracked time, or the recorded actual of a completed task
+    pub pending: usize,
+    pub remaining_hours: f64, // Estimate not yet worked off, over the pending tasks only
+}
+
+pub struct ProjectStatsUseCase<'a, R: TaskRepository> {
+    task_repo: &'a R,
+}
+
+impl<'a, R: TaskRepository> ProjectStatsUseCase<'a, R> {
+    pub fn new(task_repo: &'a R) -> Self {
+        Self { task_repo }
+    }
+
+    /// One entry per project, deleted tasks left out, most actual hours first.
+    pub fn project_stats(&self) -> Result<Vec<ProjectStats>> {
+        let mut by_project: HashMap<String, ProjectStats> = HashMap::new();
+        for task in self.task_repo.list()? {
+            if matches!(task.state, TaskState::Deleted { .. }) {
+                continue;
+            }
+            let project = task.project.clone().unwrap_or_else(|| NO_PROJECT.to_string());
+            let completed = matches!(task.state, TaskState::Completed { .. });
+            let pending = matches!(task.state, TaskState::Pending { .. });
+            let estimated_hours = parse_est_hours(&task.estimate);
+            let dto = TaskDto::from_entity(task, 0.0);
+
+            let entry = by_project.entry(project.clone()).or_insert_with(|| ProjectStats {
+                project,
+                tasks: 0,
+                completed: 0,
+                estimated_hours: 0.0,
+                actual_hours: 0.0,
+                pending: 0,
+                remaining_hours: 0.0,
+            });
+            entry.tasks += 1;
+            if completed {
+                entry.completed += 1;
+            }
+            entry.estimated_hours += estimated_hours;
+            entry.actual_hours += dto.accumulated_time as f64 / 3600.0;
+            if pending {
+                entry.pending += 1;
+                entry.remaining_hours += dto.remaining_estimate;
+            }
+        }
+
+        let mut stats: Vec<ProjectStats> = by_project.into_values().collect();
+        stats.sort_by(|a, b| b.actual_hours.total_cmp(&a.actual_hours).then_with(|| a.project.cmp(&b.project)));
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::task::{Task, TimeLog};
+    use crate::repository::MemoryTaskRepository;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn test_project_stats_groups_and_sorts_by_actual() {
+        let repo = MemoryTaskRepository::new();
+        let now = Utc::now();
+
+        let mut tracked = Task::new("Tracked".to_string(), None);
+        tracked.project = Some("Work".to_string());
+        tracked.estimate = Some("2h".to_string());
+        tracked.state = TaskState::Pending {
+            time_logs: vec![TimeLog { start: now - Duration::hours(3), end: Some(now) }],
+        };
+        repo.create(tracked).unwrap();
+
+        let mut done = Task::new("Done".to_string(), None);
+        done.project = Some("Work".to_string());
+        done.estimate = Some("1h".to_string());
+        done.complete(Some("0.125".to_string())); // 1h in days
+        repo.create(done).unwrap();
+
+        let mut loose = Task::new("Loose".to_string(), None);
+        loose.estimate = Some("0.5".to_string());
+        repo.create(loose).unwrap();
+
+        let mut gone = Task::new("Gone".to_string(), None);
+        gone.delete();
+        repo.create(gone).unwrap();
+
+        let stats = ProjectStatsUseCase::new(&repo).project_stats().unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].project, "Work");
+        assert_eq!(stats[0].tasks, 2);
+        assert_eq!(stats[0].completed, 1);
+        assert_eq!(stats[0].estimated_hours, 3.0);
+        assert!((stats[0].actual_hours - 4.0).abs() < 0.01);
+        assert_eq!(stats[1].project, NO_PROJECT);
+        assert_eq!(stats[1].estimated_hours, 4.0);
+        assert_eq!(stats[1].actual_hours, 0.0);
+
+        // Only pending work is left: 2h estimated with 3h tracked leaves nothing
+        assert_eq!((stats[0].pending, stats[0].remaining_hours), (1, 0.0));
+        assert_eq!((stats[1].pending, stats[1].remaining_hours), (1, 4.0));
+    }
+}